@@ -0,0 +1,8 @@
+-- Add down migration script here
+ALTER TABLE "comments" DROP COLUMN IF EXISTS "version";
+
+ALTER TABLE "posts" DROP COLUMN IF EXISTS "version";
+
+ALTER TABLE "categories" DROP COLUMN IF EXISTS "version";
+
+ALTER TABLE "users" DROP COLUMN IF EXISTS "version";
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE "users" ADD COLUMN IF NOT EXISTS "version" INT NOT NULL DEFAULT 1;
+
+ALTER TABLE "categories" ADD COLUMN IF NOT EXISTS "version" INT NOT NULL DEFAULT 1;
+
+ALTER TABLE "posts" ADD COLUMN IF NOT EXISTS "version" INT NOT NULL DEFAULT 1;
+
+ALTER TABLE "comments" ADD COLUMN IF NOT EXISTS "version" INT NOT NULL DEFAULT 1;
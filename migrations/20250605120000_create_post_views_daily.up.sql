@@ -0,0 +1,11 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS "post_views_daily" (
+        "post_id" INT NOT NULL,
+        "day" DATE NOT NULL,
+        "views" BIGINT NOT NULL DEFAULT 0,
+        PRIMARY KEY (post_id, day),
+        FOREIGN KEY (post_id) REFERENCES posts(id) ON UPDATE CASCADE ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS post_views_daily_day_idx ON post_views_daily (day);
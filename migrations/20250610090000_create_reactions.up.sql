@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS "reactions" (
+        "id" SERIAL PRIMARY KEY,
+        "user_id" INT NOT NULL,
+        "target_type" VARCHAR(20) NOT NULL CHECK (target_type IN ('post', 'comment')),
+        "target_id" INT NOT NULL,
+        "kind" VARCHAR(20) NOT NULL,
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE DEFAULT NOW(),
+            UNIQUE (user_id, target_type, target_id, kind),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS reactions_target_idx ON reactions (target_type, target_id);
@@ -0,0 +1,14 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS "bookmarks" (
+        "user_id" INT NOT NULL,
+        "post_id" INT NOT NULL,
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE DEFAULT NOW(),
+            PRIMARY KEY (user_id, post_id),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            FOREIGN KEY (post_id) REFERENCES posts(id) ON UPDATE CASCADE ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS bookmarks_user_created_idx ON bookmarks (user_id, created_at DESC);
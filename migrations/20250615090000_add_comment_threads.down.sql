@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS comments_parent_idx;
+DROP INDEX IF EXISTS comments_post_root_idx;
+
+ALTER TABLE "comments"
+    DROP COLUMN IF EXISTS "is_deleted",
+    DROP COLUMN IF EXISTS "depth",
+    DROP COLUMN IF EXISTS "parent_id";
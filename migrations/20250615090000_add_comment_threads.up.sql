@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE "comments"
+    ADD COLUMN IF NOT EXISTS "parent_id" INT REFERENCES comments(id) ON UPDATE CASCADE ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS "depth" INT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS "is_deleted" BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS comments_post_root_idx ON comments (id_post_comment, id) WHERE parent_id IS NULL;
+CREATE INDEX IF NOT EXISTS comments_parent_idx ON comments (parent_id, id);
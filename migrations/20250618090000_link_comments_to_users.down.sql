@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS comments_user_idx;
+
+ALTER TABLE "comments" DROP COLUMN IF EXISTS "user_id";
+
+ALTER TABLE "users" DROP COLUMN IF EXISTS "role";
@@ -0,0 +1,11 @@
+-- Add up migration script here
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS "role" VARCHAR(20) NOT NULL DEFAULT 'user'
+        CHECK (role IN ('user', 'moderator', 'admin'));
+
+-- Existing comments keep a NULL user_id and are served as guest comments
+-- under their stored user_name_comment.
+ALTER TABLE "comments"
+    ADD COLUMN IF NOT EXISTS "user_id" INT REFERENCES users(id) ON UPDATE CASCADE ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS comments_user_idx ON comments (user_id);
@@ -0,0 +1,8 @@
+-- Add down migration script here
+ALTER TABLE "posts" DROP COLUMN IF EXISTS "comment_policy";
+
+ALTER TABLE "categories" DROP COLUMN IF EXISTS "comment_policy";
+
+DROP INDEX IF EXISTS comments_status_idx;
+
+ALTER TABLE "comments" DROP COLUMN IF EXISTS "status";
@@ -0,0 +1,15 @@
+-- Add up migration script here
+ALTER TABLE "comments"
+    ADD COLUMN IF NOT EXISTS "status" VARCHAR(20) NOT NULL DEFAULT 'approved'
+        CHECK (status IN ('pending', 'approved', 'rejected', 'spam'));
+
+CREATE INDEX IF NOT EXISTS comments_status_idx ON comments (status, id);
+
+ALTER TABLE "categories"
+    ADD COLUMN IF NOT EXISTS "comment_policy" VARCHAR(20) NOT NULL DEFAULT 'open'
+        CHECK (comment_policy IN ('open', 'premoderated', 'closed'));
+
+-- NULL means the post follows its category's policy.
+ALTER TABLE "posts"
+    ADD COLUMN IF NOT EXISTS "comment_policy" VARCHAR(20)
+        CHECK (comment_policy IN ('open', 'premoderated', 'closed'));
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "spam_documents";
+DROP TABLE IF EXISTS "spam_tokens";
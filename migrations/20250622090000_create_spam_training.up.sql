@@ -0,0 +1,15 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS "spam_tokens" (
+        "token" VARCHAR(64) PRIMARY KEY,
+        "spam_count" BIGINT NOT NULL DEFAULT 0,
+        "ham_count" BIGINT NOT NULL DEFAULT 0
+    );
+
+CREATE TABLE
+    IF NOT EXISTS "spam_documents" (
+        "label" VARCHAR(10) PRIMARY KEY CHECK (label IN ('spam', 'ham')),
+        "count" BIGINT NOT NULL DEFAULT 0
+    );
+
+INSERT INTO spam_documents (label, count) VALUES ('spam', 0), ('ham', 0) ON CONFLICT DO NOTHING;
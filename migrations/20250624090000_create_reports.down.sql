@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "posts" DROP COLUMN IF EXISTS "is_hidden";
+
+DROP TABLE IF EXISTS "reports";
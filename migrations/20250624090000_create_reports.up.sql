@@ -0,0 +1,30 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS "reports" (
+        "id" SERIAL PRIMARY KEY,
+        "reporter_id" INT NOT NULL,
+        "target_type" VARCHAR(20) NOT NULL CHECK (target_type IN ('post', 'comment')),
+        "target_id" INT NOT NULL,
+        "reason" VARCHAR(20) NOT NULL
+            CHECK (reason IN ('spam', 'harassment', 'hate', 'violence', 'misinformation', 'other')),
+        "details" TEXT,
+        "status" VARCHAR(20) NOT NULL DEFAULT 'open'
+            CHECK (status IN ('open', 'triaged', 'resolved', 'dismissed')),
+        "resolved_by" INT,
+        "resolved_at" TIMESTAMP
+        WITH
+            TIME ZONE,
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE DEFAULT NOW(),
+            UNIQUE (reporter_id, target_type, target_id),
+            FOREIGN KEY (reporter_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            FOREIGN KEY (resolved_by) REFERENCES users(id) ON UPDATE CASCADE ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS reports_target_idx ON reports (target_type, target_id, status);
+
+CREATE INDEX IF NOT EXISTS reports_status_idx ON reports (status, id);
+
+-- Set once enough reports arrive; cleared when a moderator dismisses them.
+ALTER TABLE "posts" ADD COLUMN IF NOT EXISTS "is_hidden" BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,36 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS "mentions" (
+        "target_type" VARCHAR(20) NOT NULL CHECK (target_type IN ('post', 'comment')),
+        "target_id" INT NOT NULL,
+        "user_id" INT NOT NULL,
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE DEFAULT NOW(),
+            PRIMARY KEY (target_type, target_id, user_id),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS mentions_user_idx ON mentions (user_id);
+
+CREATE TABLE
+    IF NOT EXISTS "notifications" (
+        "id" SERIAL PRIMARY KEY,
+        "user_id" INT NOT NULL,
+        "actor_id" INT,
+        "kind" VARCHAR(30) NOT NULL CHECK (kind IN ('mention')),
+        "target_type" VARCHAR(20) NOT NULL CHECK (target_type IN ('post', 'comment')),
+        "target_id" INT NOT NULL,
+        "read_at" TIMESTAMP
+        WITH
+            TIME ZONE,
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE DEFAULT NOW(),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            FOREIGN KEY (actor_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS notifications_user_idx ON notifications (user_id, id DESC);
+
+CREATE INDEX IF NOT EXISTS notifications_target_idx ON notifications (target_type, target_id);
@@ -0,0 +1,11 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "notification_preferences";
+
+DROP INDEX IF EXISTS notifications_unread_idx;
+
+DELETE FROM notifications WHERE kind <> 'mention';
+
+ALTER TABLE "notifications" DROP CONSTRAINT IF EXISTS notifications_kind_check;
+
+ALTER TABLE "notifications"
+    ADD CONSTRAINT notifications_kind_check CHECK (kind IN ('mention'));
@@ -0,0 +1,20 @@
+-- Add up migration script here
+ALTER TABLE "notifications" DROP CONSTRAINT IF EXISTS notifications_kind_check;
+
+ALTER TABLE "notifications"
+    ADD CONSTRAINT notifications_kind_check CHECK (kind IN ('mention', 'comment', 'reply'));
+
+CREATE INDEX IF NOT EXISTS notifications_unread_idx ON notifications (user_id) WHERE read_at IS NULL;
+
+-- A missing row means every notification kind is enabled.
+CREATE TABLE
+    IF NOT EXISTS "notification_preferences" (
+        "user_id" INT PRIMARY KEY,
+        "mention" BOOLEAN NOT NULL DEFAULT TRUE,
+        "comment" BOOLEAN NOT NULL DEFAULT TRUE,
+        "reply" BOOLEAN NOT NULL DEFAULT TRUE,
+        "updated_at" TIMESTAMP
+        WITH
+            TIME ZONE DEFAULT NOW(),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE
+    );
@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS notifications_notify_insert ON notifications;
+DROP TRIGGER IF EXISTS comments_notify_delete ON comments;
+DROP TRIGGER IF EXISTS comments_notify_update ON comments;
+DROP TRIGGER IF EXISTS comments_notify_insert ON comments;
+
+DROP FUNCTION IF EXISTS notify_notification_event();
+DROP FUNCTION IF EXISTS notify_comment_event();
@@ -0,0 +1,53 @@
+-- Add up migration script here
+-- Payloads only carry ids; every replica loads the row itself, which keeps
+-- them well under the 8000 byte NOTIFY limit.
+CREATE OR REPLACE FUNCTION notify_comment_event() RETURNS trigger AS $$
+DECLARE
+    target comments;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        target := OLD;
+    ELSE
+        target := NEW;
+    END IF;
+
+    PERFORM pg_notify(
+        'comment_events',
+        json_build_object('id', target.id, 'post_id', target.id_post_comment)::text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION notify_notification_event() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify(
+        'notification_events',
+        json_build_object('id', NEW.id, 'user_id', NEW.user_id)::text
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Held comments are not announced until a moderator approves them.
+CREATE TRIGGER comments_notify_insert
+    AFTER INSERT ON comments
+    FOR EACH ROW
+    WHEN (NEW.status = 'approved')
+    EXECUTE FUNCTION notify_comment_event();
+
+CREATE TRIGGER comments_notify_update
+    AFTER UPDATE ON comments
+    FOR EACH ROW
+    WHEN (OLD.* IS DISTINCT FROM NEW.*)
+    EXECUTE FUNCTION notify_comment_event();
+
+CREATE TRIGGER comments_notify_delete
+    AFTER DELETE ON comments
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_comment_event();
+
+CREATE TRIGGER notifications_notify_insert
+    AFTER INSERT ON notifications
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_notification_event();
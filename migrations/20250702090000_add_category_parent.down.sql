@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS categories_parent_idx;
+
+ALTER TABLE "categories" DROP CONSTRAINT IF EXISTS categories_parent_not_self;
+
+ALTER TABLE "categories" DROP COLUMN IF EXISTS "parent_id";
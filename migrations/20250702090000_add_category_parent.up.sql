@@ -0,0 +1,9 @@
+-- Add up migration script here
+-- Deleting a category lifts its children to the top level.
+ALTER TABLE "categories"
+    ADD COLUMN IF NOT EXISTS "parent_id" INT REFERENCES categories(id) ON UPDATE CASCADE ON DELETE SET NULL;
+
+ALTER TABLE "categories"
+    ADD CONSTRAINT categories_parent_not_self CHECK (parent_id <> id);
+
+CREATE INDEX IF NOT EXISTS categories_parent_idx ON categories (parent_id);
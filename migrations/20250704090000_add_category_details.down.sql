@@ -0,0 +1,12 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS posts_category_idx;
+
+DROP INDEX IF EXISTS categories_slug_idx;
+
+DROP INDEX IF EXISTS categories_name_lower_idx;
+
+ALTER TABLE "categories"
+    DROP COLUMN IF EXISTS "sort_order",
+    DROP COLUMN IF EXISTS "cover_image",
+    DROP COLUMN IF EXISTS "description",
+    DROP COLUMN IF EXISTS "slug";
@@ -0,0 +1,35 @@
+-- Add up migration script here
+ALTER TABLE "categories"
+    ADD COLUMN IF NOT EXISTS "slug" VARCHAR(220),
+    ADD COLUMN IF NOT EXISTS "description" TEXT,
+    ADD COLUMN IF NOT EXISTS "cover_image" VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS "sort_order" INT NOT NULL DEFAULT 0;
+
+-- Names must become unique regardless of case; later duplicates get their id appended.
+UPDATE categories c
+SET name = c.name || ' (' || c.id || ')'
+WHERE EXISTS (
+    SELECT 1 FROM categories other
+    WHERE LOWER(other.name) = LOWER(c.name) AND other.id < c.id
+);
+
+UPDATE categories
+SET slug = COALESCE(
+    NULLIF(TRIM(BOTH '-' FROM REGEXP_REPLACE(LOWER(name), '[^a-z0-9]+', '-', 'g')), ''),
+    'category'
+);
+
+UPDATE categories c
+SET slug = c.slug || '-' || c.id
+WHERE EXISTS (
+    SELECT 1 FROM categories other
+    WHERE other.slug = c.slug AND other.id < c.id
+);
+
+ALTER TABLE "categories" ALTER COLUMN "slug" SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS categories_name_lower_idx ON categories (LOWER(name));
+
+CREATE UNIQUE INDEX IF NOT EXISTS categories_slug_idx ON categories (slug);
+
+CREATE INDEX IF NOT EXISTS posts_category_idx ON posts (category_id);
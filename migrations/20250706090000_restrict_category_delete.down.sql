@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE "posts" DROP CONSTRAINT IF EXISTS posts_category_id_fkey;
+
+ALTER TABLE "posts"
+    ADD CONSTRAINT posts_category_id_fkey FOREIGN KEY (category_id) REFERENCES categories(id) ON UPDATE CASCADE ON DELETE CASCADE;
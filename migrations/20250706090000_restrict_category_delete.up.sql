@@ -0,0 +1,6 @@
+-- Add up migration script here
+-- Posts must be moved out before their category can go.
+ALTER TABLE "posts" DROP CONSTRAINT IF EXISTS posts_category_id_fkey;
+
+ALTER TABLE "posts"
+    ADD CONSTRAINT posts_category_id_fkey FOREIGN KEY (category_id) REFERENCES categories(id) ON UPDATE CASCADE ON DELETE RESTRICT;
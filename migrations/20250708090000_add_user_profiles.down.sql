@@ -0,0 +1,10 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS users_username_key;
+
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS users_username_format;
+
+ALTER TABLE "users"
+    DROP COLUMN IF EXISTS "website",
+    DROP COLUMN IF EXISTS "avatar",
+    DROP COLUMN IF EXISTS "bio",
+    DROP COLUMN IF EXISTS "username";
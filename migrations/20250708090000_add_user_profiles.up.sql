@@ -0,0 +1,31 @@
+-- Add up migration script here
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS "username" VARCHAR(30),
+    ADD COLUMN IF NOT EXISTS "bio" TEXT,
+    ADD COLUMN IF NOT EXISTS "avatar" VARCHAR(200),
+    ADD COLUMN IF NOT EXISTS "website" VARCHAR(200);
+
+-- Existing users get the firstname.lastname handle mentions already use.
+UPDATE "users"
+SET "username" = TRIM(BOTH '._-' FROM LEFT(
+    REGEXP_REPLACE(LOWER(firstname || '.' || lastname), '[^a-z0-9._-]+', '', 'g'),
+    30
+));
+
+UPDATE "users"
+SET "username" = 'user' || id
+WHERE LENGTH("username") < 3 OR "username" IN ('me');
+
+UPDATE "users" u
+SET "username" = TRIM(BOTH '._-' FROM LEFT(u.username, 29 - LENGTH(u.id::text))) || '-' || u.id
+WHERE EXISTS (
+    SELECT 1 FROM "users" o WHERE o.username = u.username AND o.id < u.id
+);
+
+ALTER TABLE "users" ALTER COLUMN "username" SET NOT NULL;
+
+ALTER TABLE "users"
+    ADD CONSTRAINT users_username_format
+        CHECK (username ~ '^[a-z0-9]([a-z0-9._-]*[a-z0-9])?$' AND LENGTH(username) >= 3);
+
+CREATE UNIQUE INDEX IF NOT EXISTS users_username_key ON users (username);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS posts_user_idx;
+
+DROP TABLE IF EXISTS "follows";
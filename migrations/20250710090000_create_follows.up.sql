@@ -0,0 +1,27 @@
+-- Add up migration script here
+-- Each row follows exactly one user or one category.
+CREATE TABLE
+    IF NOT EXISTS "follows" (
+        "id" SERIAL PRIMARY KEY,
+        "follower_id" INT NOT NULL,
+        "user_id" INT,
+        "category_id" INT,
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE NOT NULL DEFAULT NOW(),
+            FOREIGN KEY (follower_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            FOREIGN KEY (category_id) REFERENCES categories(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            CONSTRAINT follows_one_target CHECK (num_nonnulls(user_id, category_id) = 1),
+            CONSTRAINT follows_not_self CHECK (user_id <> follower_id)
+    );
+
+CREATE UNIQUE INDEX IF NOT EXISTS follows_user_key ON follows (follower_id, user_id) WHERE user_id IS NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS follows_category_key ON follows (follower_id, category_id) WHERE category_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS follows_user_idx ON follows (user_id, id DESC) WHERE user_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS follows_category_idx ON follows (category_id, id DESC) WHERE category_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS posts_user_idx ON posts (user_id);
@@ -0,0 +1,7 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS "user_tokens";
+
+ALTER TABLE "users"
+    DROP COLUMN IF EXISTS "password_reset_required",
+    DROP COLUMN IF EXISTS "token_version",
+    DROP COLUMN IF EXISTS "is_active";
@@ -0,0 +1,24 @@
+-- Add up migration script here
+-- Bumping token_version invalidates every token issued before the bump.
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS "is_active" BOOLEAN NOT NULL DEFAULT TRUE,
+    ADD COLUMN IF NOT EXISTS "token_version" INT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS "password_reset_required" BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- One-time secrets handed out to users; only a hash is stored.
+CREATE TABLE
+    IF NOT EXISTS "user_tokens" (
+        "id" SERIAL PRIMARY KEY,
+        "user_id" INT NOT NULL,
+        "purpose" VARCHAR(32) NOT NULL CHECK (purpose IN ('password_reset')),
+        "token_hash" VARCHAR(100) NOT NULL,
+        "expires_at" TIMESTAMP
+        WITH
+            TIME ZONE NOT NULL,
+            "created_at" TIMESTAMP
+        WITH
+            TIME ZONE NOT NULL DEFAULT NOW(),
+            FOREIGN KEY (user_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS user_tokens_user_idx ON user_tokens (user_id, purpose);
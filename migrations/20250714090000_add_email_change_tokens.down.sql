@@ -0,0 +1,7 @@
+-- Add down migration script here
+DELETE FROM "user_tokens" WHERE purpose = 'email_change';
+
+ALTER TABLE "user_tokens"
+    DROP CONSTRAINT IF EXISTS user_tokens_purpose_check,
+    ADD CONSTRAINT user_tokens_purpose_check CHECK (purpose IN ('password_reset')),
+    DROP COLUMN IF EXISTS "new_email";
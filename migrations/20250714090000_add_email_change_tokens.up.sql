@@ -0,0 +1,6 @@
+-- Add up migration script here
+-- An email change only takes effect once the token sent to the new address is redeemed.
+ALTER TABLE "user_tokens"
+    ADD COLUMN IF NOT EXISTS "new_email" VARCHAR(255),
+    DROP CONSTRAINT IF EXISTS user_tokens_purpose_check,
+    ADD CONSTRAINT user_tokens_purpose_check CHECK (purpose IN ('password_reset', 'email_change'));
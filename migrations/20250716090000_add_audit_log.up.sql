@@ -0,0 +1,13 @@
+-- Add up migration script here
+-- Ids are kept without foreign keys so entries outlive the accounts they
+-- mention; details never hold personal data.
+CREATE TABLE IF NOT EXISTS "audit_log" (
+    "id" SERIAL PRIMARY KEY,
+    "actor_id" INT,
+    "action" VARCHAR(64) NOT NULL,
+    "subject_id" INT,
+    "details" JSONB NOT NULL DEFAULT '{}'::jsonb,
+    "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_subject_idx ON "audit_log" ("subject_id");
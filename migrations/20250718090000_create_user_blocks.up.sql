@@ -0,0 +1,18 @@
+-- Add up migration script here
+-- A block also stops the blocked user from commenting on or mentioning the
+-- blocker; a mute only hides their content from the muter.
+CREATE TABLE
+    IF NOT EXISTS "user_blocks" (
+        "blocker_id" INT NOT NULL,
+        "blocked_id" INT NOT NULL,
+        "kind" VARCHAR(16) NOT NULL CHECK (kind IN ('block', 'mute')),
+        "created_at" TIMESTAMP
+        WITH
+            TIME ZONE NOT NULL DEFAULT NOW(),
+            PRIMARY KEY (blocker_id, blocked_id, kind),
+            FOREIGN KEY (blocker_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            FOREIGN KEY (blocked_id) REFERENCES users(id) ON UPDATE CASCADE ON DELETE CASCADE,
+            CONSTRAINT user_blocks_not_self CHECK (blocker_id <> blocked_id)
+    );
+
+CREATE INDEX IF NOT EXISTS user_blocks_blocked_idx ON user_blocks (blocked_id, kind);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "posts" DROP COLUMN IF EXISTS "password_hash";
+
+ALTER TABLE "posts" DROP COLUMN IF EXISTS "visibility";
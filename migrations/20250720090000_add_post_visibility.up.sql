@@ -0,0 +1,9 @@
+-- Add up migration script here
+-- Unlisted posts open by link but stay out of listings; private ones are
+-- only readable by their author and staff.
+ALTER TABLE "posts"
+    ADD COLUMN IF NOT EXISTS "visibility" VARCHAR(20) NOT NULL DEFAULT 'public'
+        CHECK (visibility IN ('public', 'unlisted', 'members', 'private'));
+
+-- Set when readers must enter a password before the post is shown.
+ALTER TABLE "posts" ADD COLUMN IF NOT EXISTS "password_hash" VARCHAR(255);
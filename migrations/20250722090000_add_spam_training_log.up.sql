@@ -0,0 +1,10 @@
+-- Add up migration script here
+-- The last verdict each comment was trained with, so a moderator changing
+-- their mind replaces the earlier verdict instead of adding to it. Tokens
+-- are kept as trained since the comment may have been edited since.
+CREATE TABLE
+    IF NOT EXISTS "spam_training" (
+        "comment_id" INT PRIMARY KEY REFERENCES comments (id) ON DELETE CASCADE,
+        "label" VARCHAR(10) NOT NULL CHECK (label IN ('spam', 'ham')),
+        "tokens" TEXT NOT NULL
+    );
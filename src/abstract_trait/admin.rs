@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    ApiResponse, ApiResponsePagination, EraseUserRequest, ErasureResponse, ErrorResponse,
+    FindUsersRequest, PasswordResetResponse, UserResponse, UserRole,
+};
+
+pub type DynAdminService = Arc<dyn AdminServiceTrait + Send + Sync>;
+
+/// User management; every call checks that `admin_id` holds the admin role.
+#[async_trait]
+pub trait AdminServiceTrait {
+    /// Fails with 403 unless `user_id` holds the admin role.
+    async fn ensure_admin(&self, user_id: i32) -> Result<(), ErrorResponse>;
+    async fn find_users(
+        &self,
+        admin_id: i32,
+        req: FindUsersRequest,
+    ) -> Result<ApiResponsePagination<Vec<UserResponse>>, ErrorResponse>;
+    /// Deactivating also revokes every token the user holds.
+    async fn set_user_active(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        active: bool,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    /// Revokes the user's tokens and blocks login until the returned token
+    /// is redeemed at `/api/auth/password-reset`.
+    async fn force_password_reset(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+    ) -> Result<ApiResponse<PasswordResetResponse>, ErrorResponse>;
+    async fn set_user_role(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        role: UserRole,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    /// Erases the account for good; admins delete their own through
+    /// `/api/users/me`.
+    async fn erase_user(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        input: &EraseUserRequest,
+    ) -> Result<ApiResponse<ErasureResponse>, ErrorResponse>;
+}
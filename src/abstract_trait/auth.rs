@@ -2,7 +2,13 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use crate::{domain::{ApiResponse, ErrorResponse, LoginRequest, RegisterRequest, UserResponse}, utils::AppError};
+use crate::{
+    domain::{
+        ApiResponse, ChangeEmailRequest, ChangePasswordRequest, ConfirmEmailRequest, ErrorResponse,
+        LoginRequest, RegisterRequest, ResetPasswordRequest, UserResponse,
+    },
+    utils::AppError,
+};
 
 
 pub type DynAuthService = Arc<dyn AuthServiceTrait + Send + Sync>;
@@ -11,5 +17,15 @@ pub type DynAuthService = Arc<dyn AuthServiceTrait + Send + Sync>;
 pub trait AuthServiceTrait {
     async fn register_user(&self, input: &RegisterRequest) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
     async fn login_user(&self, input: &LoginRequest) -> Result<ApiResponse<String>, ErrorResponse>;
-    fn verify_token(&self, token: &str) -> Result<i64, AppError>;
+    async fn reset_password(&self, input: &ResetPasswordRequest) -> Result<ApiResponse<()>, ErrorResponse>;
+    /// Requires the current password; revokes every other session and
+    /// returns a fresh token for this one.
+    async fn change_password(&self, user_id: i32, input: &ChangePasswordRequest) -> Result<ApiResponse<String>, ErrorResponse>;
+    /// Requires the current password; the address only changes once the
+    /// token mailed to it is confirmed.
+    async fn request_email_change(&self, user_id: i32, input: &ChangeEmailRequest) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn confirm_email_change(&self, input: &ConfirmEmailRequest) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    /// Verifies the token and that it still belongs to an active account
+    /// whose tokens have not been revoked since it was issued.
+    async fn authenticate(&self, token: &str) -> Result<i64, AppError>;
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, BlockKind, BlockResponse, BlockedUserResponse,
+        ErrorResponse, FindBlocksRequest,
+    },
+    model::block::UserBlock,
+    utils::AppError,
+};
+
+pub type DynBlockRepository = Arc<dyn BlockRepositoryTrait + Send + Sync>;
+pub type DynBlockService = Arc<dyn BlockServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait BlockRepositoryTrait {
+    /// `false` when it already existed. A block also drops the blocked
+    /// user's follow of the blocker.
+    async fn add(&self, blocker_id: i32, blocked_id: i32, kind: BlockKind)
+        -> Result<bool, AppError>;
+    /// `false` when there was nothing to remove.
+    async fn remove(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+        kind: BlockKind,
+    ) -> Result<bool, AppError>;
+    async fn find_kinds(&self, blocker_id: i32, blocked_id: i32)
+        -> Result<Vec<BlockKind>, AppError>;
+    /// Most recent first.
+    async fn find_by_blocker(
+        &self,
+        blocker_id: i32,
+        req: &FindBlocksRequest,
+    ) -> Result<(Vec<UserBlock>, i64), AppError>;
+    /// Users whose content `viewer_id` does not want to see, blocked or muted.
+    async fn find_hidden(&self, viewer_id: i32) -> Result<Vec<i32>, AppError>;
+    /// The ones among `user_ids` that have blocked `blocked_id`.
+    async fn find_blockers(&self, blocked_id: i32, user_ids: &[i32]) -> Result<Vec<i32>, AppError>;
+    /// The ones among `user_ids` that have blocked or muted `hidden_id`.
+    async fn find_hiders(&self, hidden_id: i32, user_ids: &[i32]) -> Result<Vec<i32>, AppError>;
+}
+
+#[async_trait]
+pub trait BlockServiceTrait {
+    async fn set_block(
+        &self,
+        user_id: i32,
+        username: &str,
+        kind: BlockKind,
+        active: bool,
+    ) -> Result<ApiResponse<BlockResponse>, ErrorResponse>;
+    async fn get_blocks(
+        &self,
+        user_id: i32,
+        req: FindBlocksRequest,
+    ) -> Result<ApiResponsePagination<Vec<BlockedUserResponse>>, ErrorResponse>;
+    async fn find_hidden(&self, viewer_id: i32) -> Result<Vec<i32>, ErrorResponse>;
+}
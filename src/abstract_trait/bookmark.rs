@@ -0,0 +1,18 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::utils::AppError;
+
+pub type DynBookmarkRepository = Arc<dyn BookmarkRepositoryTrait + Send + Sync>;
+
+#[async_trait]
+pub trait BookmarkRepositoryTrait {
+    async fn add(&self, user_id: i32, post_id: i32) -> Result<bool, AppError>;
+    async fn remove(&self, user_id: i32, post_id: i32) -> Result<bool, AppError>;
+    async fn find_bookmarked(
+        &self,
+        user_id: i32,
+        post_ids: &[i32],
+    ) -> Result<HashSet<i32>, AppError>;
+}
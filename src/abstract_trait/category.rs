@@ -4,7 +4,7 @@ use async_trait::async_trait;
 
 use crate::{
     domain::{
-        ApiResponse, ApiResponsePagination, CategoryResponse, CreateCategoryRequest, ErrorResponse, FindAllCategoryRequest,  UpdateCategoryRequest
+        ApiResponse, ApiResponsePagination, CategoryMergeResponse, CategoryResponse, CategoryTreeResponse, CreateCategoryRequest, ErrorResponse, FindAllCategoryRequest,  UpdateCategoryRequest
     }, model::category::Category, utils::AppError
     
 };
@@ -21,9 +21,28 @@ pub trait CategoryRepositoryTrait {
         search: Option<String>,
     ) -> Result<(Vec<Category>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Category>, AppError>;
-    async fn create(&self, input: &CreateCategoryRequest) -> Result<Category, AppError>;
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<Category>, AppError>;
+    /// Case-insensitive match on the name.
+    async fn find_by_name(&self, name: &str) -> Result<Option<Category>, AppError>;
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Category>, AppError>;
+    /// Every category in display order, for building the tree.
+    async fn find_every(&self) -> Result<Vec<Category>, AppError>;
+    /// The category and its ancestors, root first; empty if it does not exist.
+    async fn find_ancestors(&self, id: i32) -> Result<Vec<Category>, AppError>;
+    async fn create(&self, input: &CreateCategoryRequest, slug: &str) -> Result<Category, AppError>;
     async fn update(&self, input: &UpdateCategoryRequest) -> Result<Category, AppError>;
+    /// Refused with a conflict while posts are still filed under it.
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    /// Moves every post of `id` into `target_id` and deletes `id`, all in one
+    /// transaction. Direct children move below the target when
+    /// `adopt_children` is set, otherwise up to the removed category's parent.
+    /// Returns how many posts and subcategories were moved.
+    async fn reassign(
+        &self,
+        id: i32,
+        target_id: i32,
+        adopt_children: bool,
+    ) -> Result<(u64, u64), AppError>;
 }
 
 #[async_trait]
@@ -36,13 +55,43 @@ pub trait CategoryServiceTrait {
         &self,
         id: i32,
     ) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse>;
+    async fn get_category_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<ApiResponse<CategoryResponse>, ErrorResponse>;
+    async fn get_category_tree(
+        &self,
+    ) -> Result<ApiResponse<Vec<CategoryTreeResponse>>, ErrorResponse>;
+    async fn get_breadcrumbs(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<Vec<CategoryResponse>>, ErrorResponse>;
+    /// Changes to categories are limited to moderators and admins; `user_id`
+    /// is the caller.
     async fn create_category(
         &self,
+        user_id: i32,
         input: &CreateCategoryRequest,
     ) -> Result<ApiResponse<CategoryResponse>, ErrorResponse>;
     async fn update_category(
         &self,
+        user_id: i32,
         input: &UpdateCategoryRequest,
     ) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse>;
-    async fn delete_category(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_category(&self, user_id: i32, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    /// Moves the posts to `target_id`, then deletes the category.
+    async fn reassign_and_delete_category(
+        &self,
+        user_id: i32,
+        id: i32,
+        target_id: i32,
+    ) -> Result<ApiResponse<CategoryMergeResponse>, ErrorResponse>;
+    /// Like [`Self::reassign_and_delete_category`], but the subcategories
+    /// move below the target too.
+    async fn merge_category(
+        &self,
+        user_id: i32,
+        id: i32,
+        target_id: i32,
+    ) -> Result<ApiResponse<CategoryMergeResponse>, ErrorResponse>;
 }
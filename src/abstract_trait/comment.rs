@@ -3,7 +3,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 
 use crate::{
-    domain::{ApiResponse, CommentResponse, CreateCommentRequest, ErrorResponse,  UpdateCommentRequest}, model::comment::Comment,  
+    domain::{ApiResponse, ApiResponsePagination, CommentResponse, CommentStatus, CommentTreeResponse, CreateCommentRequest, ErrorResponse, FindCommentTreeRequest, FindCommentsRequest, UpdateCommentRequest}, model::comment::{Comment, CommentNode, RankedComment},  
     utils::AppError  
 };
 
@@ -13,21 +13,76 @@ pub type DynCommentService = Arc<dyn CommentServiceTrait + Send + Sync>;
 
 #[async_trait]
 pub trait CommentRepositoryTrait {
-    async fn find_all(&self) -> Result<Vec<Comment>, AppError>;
+    /// `after` is the `(score, id)` of the last comment on the previous page.
+    async fn find_paginated(
+        &self,
+        req: &FindCommentsRequest,
+        status: CommentStatus,
+        after: Option<(i64, i32)>,
+    ) -> Result<(Vec<RankedComment>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Comment>, AppError>;
-    async fn create(&self, input: &CreateCommentRequest) -> Result<Comment, AppError>;
-    async fn update(&self, input: &UpdateCommentRequest) -> Result<Comment, AppError>;
+    async fn find_by_post(
+        &self,
+        post_id: i32,
+        page: i32,
+        page_size: i32,
+        viewer_id: Option<i32>,
+    ) -> Result<(Vec<Comment>, i64), AppError>;
+    async fn find_tree(
+        &self,
+        post_id: i32,
+        parent_id: Option<i32>,
+        req: &FindCommentTreeRequest,
+        max_depth: i32,
+    ) -> Result<(Vec<CommentNode>, i64), AppError>;
+    async fn create(
+        &self,
+        input: &CreateCommentRequest,
+        user_name: &str,
+        depth: i32,
+        status: CommentStatus,
+    ) -> Result<Comment, AppError>;
+    /// `status`, when set, replaces the comment's status in the same write.
+    async fn update(
+        &self,
+        input: &UpdateCommentRequest,
+        status: Option<CommentStatus>,
+    ) -> Result<Comment, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
 }
 
 #[async_trait]
 pub trait CommentServiceTrait {
-    async fn get_comments(&self) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse>;
-    async fn get_comment(&self, id: i32) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> ;
+    async fn get_comments(
+        &self,
+        req: FindCommentsRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentResponse>>, ErrorResponse>;
+    async fn get_post_comments(
+        &self,
+        post_id: i32,
+        req: FindCommentsRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentResponse>>, ErrorResponse>;
+    /// Held and deleted comments are only found by their author and
+    /// moderators.
+    async fn get_comment(&self, viewer_id: i32, id: i32) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> ;
+    /// The comment with its author and reactions, or `None` unless it is
+    /// approved and not deleted.
+    async fn find_published(&self, id: i32) -> Result<Option<CommentResponse>, AppError>;
+    async fn get_comment_tree(
+        &self,
+        post_id: i32,
+        req: FindCommentTreeRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentTreeResponse>>, ErrorResponse>;
+    async fn get_replies(
+        &self,
+        comment_id: i32,
+        req: FindCommentTreeRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentTreeResponse>>, ErrorResponse>;
     async fn create_comment(&self, input: &CreateCommentRequest) -> Result<ApiResponse<CommentResponse>, ErrorResponse>;
     async fn update_comment(
         &self,
+        user_id: i32,
         input: &UpdateCommentRequest
     ) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse>;
-    async fn delete_comment(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_comment(&self, user_id: i32, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
 }
@@ -21,4 +21,6 @@ pub trait FileServiceTrait {
         upload_dir: &str,
         file_name: &str,
     ) -> Result<Json<DeleteResponse>, (StatusCode, Json<DeleteResponse>)>;
+    /// `None` when the file is missing or unreadable.
+    async fn read_image(&self, upload_dir: &str, file_name: &str) -> Option<Vec<u8>>;
 }
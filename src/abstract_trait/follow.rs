@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CategoryResponse, ErrorResponse, FindFollowsRequest,
+        FollowResponse, FollowTarget, PublicUserResponse,
+    },
+    utils::AppError,
+};
+
+pub type DynFollowRepository = Arc<dyn FollowRepositoryTrait + Send + Sync>;
+pub type DynFollowService = Arc<dyn FollowServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait FollowRepositoryTrait {
+    /// `false` when the follow already existed.
+    async fn follow(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        target_id: i32,
+    ) -> Result<bool, AppError>;
+    /// `false` when there was nothing to remove.
+    async fn unfollow(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        target_id: i32,
+    ) -> Result<bool, AppError>;
+    async fn count_followers(&self, target: FollowTarget, target_id: i32) -> Result<i64, AppError>;
+    async fn count_following(&self, follower_id: i32, target: FollowTarget)
+        -> Result<i64, AppError>;
+    /// Ids of the users following the target, most recent first.
+    async fn find_followers(
+        &self,
+        target: FollowTarget,
+        target_id: i32,
+        req: &FindFollowsRequest,
+    ) -> Result<(Vec<i32>, i64), AppError>;
+    /// Ids of the users or categories `follower_id` follows, most recent first.
+    async fn find_following(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        req: &FindFollowsRequest,
+    ) -> Result<(Vec<i32>, i64), AppError>;
+}
+
+#[async_trait]
+pub trait FollowServiceTrait {
+    async fn follow_user(
+        &self,
+        follower_id: i32,
+        username: &str,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse>;
+    async fn unfollow_user(
+        &self,
+        follower_id: i32,
+        username: &str,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse>;
+    async fn follow_category(
+        &self,
+        follower_id: i32,
+        category_id: i32,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse>;
+    async fn unfollow_category(
+        &self,
+        follower_id: i32,
+        category_id: i32,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse>;
+    async fn get_user_followers(
+        &self,
+        username: &str,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse>;
+    async fn get_user_following(
+        &self,
+        username: &str,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse>;
+    async fn get_followed_categories(
+        &self,
+        username: &str,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<CategoryResponse>>, ErrorResponse>;
+    async fn get_category_followers(
+        &self,
+        category_id: i32,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse>;
+}
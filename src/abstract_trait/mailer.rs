@@ -0,0 +1,14 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::utils::AppError;
+
+pub type DynMailer = Arc<dyn Mailer + Send + Sync>;
+
+/// Outgoing email, kept behind a trait so deployments can plug in a real
+/// transport.
+#[async_trait]
+pub trait Mailer {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError>;
+}
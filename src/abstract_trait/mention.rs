@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{domain::ReactionTarget, utils::AppError};
+
+pub type DynMentionRepository = Arc<dyn MentionRepositoryTrait + Send + Sync>;
+pub type DynMentionService = Arc<dyn MentionServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait MentionRepositoryTrait {
+    /// Ids of the users matching the given lowercase handles.
+    async fn resolve_handles(&self, handles: &[String]) -> Result<Vec<i32>, AppError>;
+    /// Makes `user_ids` the mentions of a target and returns the
+    /// `(added, removed)` user ids.
+    async fn replace(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        user_ids: &[i32],
+    ) -> Result<(Vec<i32>, Vec<i32>), AppError>;
+    /// Drops the mentions of a post and of all its comments.
+    async fn delete_for_post(&self, post_id: i32) -> Result<(), AppError>;
+}
+
+/// Keeps mention rows and their notifications in step with content. Called
+/// by other services after they write a post or comment.
+#[async_trait]
+pub trait MentionServiceTrait {
+    /// `post_id` is the post the target belongs to; users who cannot read
+    /// it are not mentioned.
+    async fn sync(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        post_id: i32,
+        author_id: Option<i32>,
+        text: &str,
+    ) -> Result<(), AppError>;
+    async fn clear(&self, target: ReactionTarget, target_id: i32) -> Result<(), AppError>;
+    async fn clear_post(&self, post_id: i32) -> Result<(), AppError>;
+}
@@ -1,9 +1,30 @@
+mod admin;
 mod auth;
+mod block;
+mod bookmark;
 mod category;
 mod comment;
 mod file;
+mod follow;
+mod mailer;
+mod mention;
+mod moderation;
+mod notification;
 mod post;
+mod privacy;
+mod reaction;
+mod report;
+mod spam;
+mod stream;
 mod user;
+mod user_token;
+mod view;
+
+pub use self::block::{
+    BlockRepositoryTrait, BlockServiceTrait, DynBlockRepository, DynBlockService,
+};
+
+pub use self::bookmark::{BookmarkRepositoryTrait, DynBookmarkRepository};
 
 pub use self::category::{
     CategoryRepositoryTrait, CategoryServiceTrait, DynCategoryRepository, DynCategoryService,
@@ -17,8 +38,52 @@ pub use self::comment::{
     CommentRepositoryTrait, CommentServiceTrait, DynCommentRepository, DynCommentService,
 };
 
+pub use self::follow::{
+    DynFollowRepository, DynFollowService, FollowRepositoryTrait, FollowServiceTrait,
+};
+
+pub use self::mention::{
+    DynMentionRepository, DynMentionService, MentionRepositoryTrait, MentionServiceTrait,
+};
+
+pub use self::moderation::{
+    DynModerationRepository, DynModerationService, ModerationRepositoryTrait,
+    ModerationServiceTrait,
+};
+
+pub use self::notification::{
+    DynNotificationRepository, DynNotificationService, NotificationRepositoryTrait,
+    NotificationServiceTrait,
+};
+
+pub use self::privacy::{
+    DynPrivacyRepository, DynPrivacyService, PrivacyRepositoryTrait, PrivacyServiceTrait,
+};
+
+pub use self::reaction::{
+    DynReactionRepository, DynReactionService, ReactionRepositoryTrait, ReactionServiceTrait,
+};
+
+pub use self::report::{
+    DynReportRepository, DynReportService, ReportRepositoryTrait, ReportServiceTrait,
+};
+
+pub use self::spam::{DynSpamFilter, DynSpamRepository, SpamFilter, SpamRepositoryTrait};
+
+pub use self::stream::{DynStreamService, StreamServiceTrait};
+
 pub use self::user::{DynUserRepository, DynUserService, UserRepositoryTrait, UserServiceTrait};
 
+pub use self::user_token::{DynUserTokenRepository, UserTokenRepositoryTrait};
+
+pub use self::admin::{AdminServiceTrait, DynAdminService};
+
 pub use self::auth::{AuthServiceTrait, DynAuthService};
 
 pub use self::file::{DynFileService, FileServiceTrait};
+
+pub use self::mailer::{DynMailer, Mailer};
+
+pub use self::view::{
+    DynPostViewRepository, DynPostViewService, PostViewRepositoryTrait, PostViewServiceTrait,
+};
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CommentPolicy, CommentResponse, CommentStatus,
+        CommentStatusUpdateResponse, ErrorResponse, FindModerationQueueRequest,
+        UpdateCommentPolicyRequest, UpdateCommentStatusRequest,
+    },
+    model::comment::Comment,
+    utils::AppError,
+};
+
+pub type DynModerationRepository = Arc<dyn ModerationRepositoryTrait + Send + Sync>;
+pub type DynModerationService = Arc<dyn ModerationServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait ModerationRepositoryTrait {
+    /// The post's own policy, or its category's when the post has none.
+    /// `None` when the post does not exist.
+    async fn find_comment_policy(&self, post_id: i32) -> Result<Option<CommentPolicy>, AppError>;
+    async fn has_approved_comment(&self, user_id: i32) -> Result<bool, AppError>;
+    /// Returns every comment whose status actually changed.
+    async fn set_comment_status(
+        &self,
+        ids: &[i32],
+        status: CommentStatus,
+    ) -> Result<Vec<Comment>, AppError>;
+    async fn set_category_policy(
+        &self,
+        category_id: i32,
+        policy: CommentPolicy,
+    ) -> Result<bool, AppError>;
+    async fn set_post_policy(
+        &self,
+        post_id: i32,
+        policy: Option<CommentPolicy>,
+    ) -> Result<bool, AppError>;
+}
+
+#[async_trait]
+pub trait ModerationServiceTrait {
+    async fn get_queue(
+        &self,
+        moderator_id: i32,
+        req: FindModerationQueueRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentResponse>>, ErrorResponse>;
+    async fn update_comment_status(
+        &self,
+        moderator_id: i32,
+        req: &UpdateCommentStatusRequest,
+    ) -> Result<ApiResponse<CommentStatusUpdateResponse>, ErrorResponse>;
+    async fn set_category_policy(
+        &self,
+        moderator_id: i32,
+        category_id: i32,
+        req: &UpdateCommentPolicyRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn set_post_policy(
+        &self,
+        moderator_id: i32,
+        post_id: i32,
+        req: &UpdateCommentPolicyRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CreateNotificationRequest, ErrorResponse,
+        FindNotificationsRequest, NotificationKind, NotificationPreferencesResponse,
+        NotificationReadResponse, NotificationResponse, ReactionTarget, UnreadCountResponse,
+        UpdateNotificationPreferencesRequest,
+    },
+    model::{
+        comment::Comment,
+        notification::{Notification, NotificationPreferences},
+    },
+    utils::AppError,
+};
+
+pub type DynNotificationRepository = Arc<dyn NotificationRepositoryTrait + Send + Sync>;
+pub type DynNotificationService = Arc<dyn NotificationServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait NotificationRepositoryTrait {
+    async fn create_many(&self, inputs: &[CreateNotificationRequest]) -> Result<u64, AppError>;
+    /// Withdraws notifications the recipients have not seen yet, e.g. when
+    /// a mention is edited out. `None` matches every recipient.
+    async fn delete_unread(
+        &self,
+        kinds: &[NotificationKind],
+        target: ReactionTarget,
+        target_id: i32,
+        user_ids: Option<&[i32]>,
+    ) -> Result<u64, AppError>;
+    /// Drops every notification pointing at a post or one of its comments.
+    async fn delete_for_post(&self, post_id: i32) -> Result<u64, AppError>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<Notification>, AppError>;
+    async fn find_paginated(
+        &self,
+        user_id: i32,
+        req: &FindNotificationsRequest,
+    ) -> Result<(Vec<Notification>, i64), AppError>;
+    async fn count_unread(&self, user_id: i32) -> Result<i64, AppError>;
+    /// `false` when the notification does not exist or belongs to someone else.
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<bool, AppError>;
+    async fn mark_all_read(&self, user_id: i32) -> Result<u64, AppError>;
+    async fn find_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<NotificationPreferences>, AppError>;
+    async fn save_preferences(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences, AppError>;
+    /// The subset of `user_ids` that has not switched `kind` off.
+    async fn recipients_accepting(
+        &self,
+        kind: NotificationKind,
+        user_ids: &[i32],
+    ) -> Result<Vec<i32>, AppError>;
+}
+
+#[async_trait]
+pub trait NotificationServiceTrait {
+    /// Stores notifications for the recipients that accept their kind.
+    /// Nobody is notified about their own actions.
+    async fn notify(&self, inputs: Vec<CreateNotificationRequest>) -> Result<(), AppError>;
+    async fn withdraw(
+        &self,
+        kinds: &[NotificationKind],
+        target: ReactionTarget,
+        target_id: i32,
+        user_ids: Option<&[i32]>,
+    ) -> Result<(), AppError>;
+    /// Tells the post author and the parent comment's author about a newly
+    /// published comment.
+    async fn comment_published(&self, comment: &Comment) -> Result<(), AppError>;
+    /// Withdraws unread comment and reply notifications for a comment that
+    /// was deleted or unpublished.
+    async fn comment_withdrawn(&self, comment_id: i32) -> Result<(), AppError>;
+    async fn clear_post(&self, post_id: i32) -> Result<(), AppError>;
+    /// A single notification with its actor, as pushed to live subscribers.
+    async fn get_notification(&self, id: i32) -> Result<Option<NotificationResponse>, AppError>;
+
+    async fn get_notifications(
+        &self,
+        user_id: i32,
+        req: FindNotificationsRequest,
+    ) -> Result<ApiResponsePagination<Vec<NotificationResponse>>, ErrorResponse>;
+    async fn get_unread_count(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<UnreadCountResponse>, ErrorResponse>;
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn mark_all_read(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<NotificationReadResponse>, ErrorResponse>;
+    async fn get_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<NotificationPreferencesResponse>, ErrorResponse>;
+    async fn update_preferences(
+        &self,
+        user_id: i32,
+        input: &UpdateNotificationPreferencesRequest,
+    ) -> Result<ApiResponse<NotificationPreferencesResponse>, ErrorResponse>;
+}
@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{net::IpAddr, sync::Arc};
 
 use async_trait::async_trait;
 
 use crate::{
     domain::{
-        ApiResponse, ApiResponsePagination, CreatePostRequest, ErrorResponse, FindAllPostRequest, PostRelationResponse, PostResponse,  UpdatePostRequest
+        ApiResponse, ApiResponsePagination, CreatePostRequest, ErrorResponse, FindAllPostRequest, FindFeedRequest, FindPostDetailRequest, PostDetailResponse, PostReader, PostResponse, UnlockPostRequest, UpdatePostRequest
     }, model::posts::Post,
     utils::AppError
 };
@@ -14,16 +14,48 @@ pub type DynPostsService = Arc<dyn PostsServiceTrait + Send + Sync>;
 
 #[async_trait]
 pub trait PostsRepositoryTrait {
+    /// With `include_descendants`, `category` also matches its subcategories.
+    /// Only posts listed to `viewer_id` are returned, minus authors they
+    /// blocked or muted.
     async fn get_all_posts(
         &self,
+        req: &FindAllPostRequest,
+        viewer_id: Option<i32>,
+    ) -> Result<(Vec<Post>, i64), AppError>;
+    /// `None` when the post does not exist or `reader` may not open it.
+    async fn get_post(&self, post_id: i32, reader: PostReader) -> Result<Option<Post>, AppError>;
+    /// Bookmarks of posts the user can no longer read are skipped.
+    async fn get_bookmarked_posts(
+        &self,
+        user_id: i32,
         page: i32,
         page_size: i32,
         search: Option<String>,
+        category: Option<i32>,
+        include_descendants: bool,
+    ) -> Result<(Vec<Post>, i64), AppError>;
+    /// Listed posts by followed authors or in followed categories, newest
+    /// first, minus authors the user blocked or muted; `after` is the last
+    /// id of the previous page and replaces `page`.
+    async fn get_feed(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        after: Option<i32>,
     ) -> Result<(Vec<Post>, i64), AppError>;
-    async fn get_post(&self, post_id: i32) -> Result<Option<Post>, AppError>;
-    async fn get_post_relation(&self, post_id: i32) -> Result<Vec<PostRelationResponse>, AppError>;
-    async fn create_post(&self, input: &CreatePostRequest) -> Result<Post, AppError>;
-    async fn update_post(&self, input: &UpdatePostRequest) -> Result<Post, AppError>;
+    async fn create_post(
+        &self,
+        input: &CreatePostRequest,
+        user_name: &str,
+        password_hash: Option<&str>,
+    ) -> Result<Post, AppError>;
+    /// `password_hash` of `Some(None)` removes the post's password.
+    async fn update_post(
+        &self,
+        input: &UpdatePostRequest,
+        password_hash: Option<Option<&str>>,
+    ) -> Result<Post, AppError>;
     async fn delete_post(&self, post_id: i32) -> Result<(), AppError>;
 }
 
@@ -32,22 +64,66 @@ pub trait PostsServiceTrait {
     async fn get_all_posts(
         &self,
         req: FindAllPostRequest,
+        viewer_id: Option<i32>,
     ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse>;
+    /// `unlock_token` comes from the cookie set by [`Self::unlock_post`].
     async fn get_post(
         &self,
         post_id: i32,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
     ) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse>;
-    async fn get_post_relation(
+    async fn get_post_detail(
+        &self,
+        post_id: i32,
+        req: FindPostDetailRequest,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
+    ) -> Result<ApiResponse<PostDetailResponse>, ErrorResponse>;
+    async fn get_bookmarked_posts(
+        &self,
+        user_id: i32,
+        req: FindAllPostRequest,
+    ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse>;
+    async fn get_feed(
+        &self,
+        user_id: i32,
+        req: FindFeedRequest,
+    ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse>;
+    async fn bookmark_post(&self, user_id: i32, post_id: i32)
+        -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn remove_bookmark(
+        &self,
+        user_id: i32,
+        post_id: i32,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    /// Fails with 404 when `viewer_id` may not see the post and with 403
+    /// while it is password protected and `unlock_token` does not open it.
+    async fn ensure_readable(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
+    ) -> Result<(), ErrorResponse>;
+    /// Checks the post's password and returns a short-lived unlock token
+    /// along with the post. Repeated wrong passwords from the same address
+    /// or account are refused for a while.
+    async fn unlock_post(
         &self,
         post_id: i32,
-    ) -> Result<ApiResponse<PostRelationResponse>, ErrorResponse>;
+        viewer_id: Option<i32>,
+        client_ip: IpAddr,
+        input: &UnlockPostRequest,
+    ) -> Result<(String, ApiResponse<PostResponse>), ErrorResponse>;
     async fn create_post(
         &self,
         input: &CreatePostRequest,
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse>;
+    /// Only the author and editors may change a post.
     async fn update_post(
         &self,
+        user_id: i32,
         input: &UpdatePostRequest,
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse>;
-    async fn delete_post(&self, post_id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn delete_post(&self, user_id: i32, post_id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
 }
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, DeleteAccountRequest, ErasureResponse, ErrorResponse, ExportArchive,
+        ExportFormat, PostErasurePolicy,
+    },
+    model::{comment::Comment, posts::Post, user::ErasedAccount},
+    utils::AppError,
+};
+
+pub type DynPrivacyRepository = Arc<dyn PrivacyRepositoryTrait + Send + Sync>;
+pub type DynPrivacyService = Arc<dyn PrivacyServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait PrivacyRepositoryTrait {
+    /// Every post of the user, hidden ones included.
+    async fn find_user_posts(&self, user_id: i32) -> Result<Vec<Post>, AppError>;
+    /// Every comment of the user, whatever its status.
+    async fn find_user_comments(&self, user_id: i32) -> Result<Vec<Comment>, AppError>;
+    /// Detaches the user's comments, deletes their posts or hands them to
+    /// `reassign_to` (id and username), records an audit entry and deletes
+    /// the account, all in one transaction.
+    async fn erase_user(
+        &self,
+        user_id: i32,
+        actor_id: i32,
+        reassign_to: Option<(i32, &str)>,
+    ) -> Result<ErasedAccount, AppError>;
+}
+
+#[async_trait]
+pub trait PrivacyServiceTrait {
+    async fn export_account(
+        &self,
+        user_id: i32,
+        format: ExportFormat,
+    ) -> Result<ExportArchive, ErrorResponse>;
+    /// Self-service erasure; the user's posts are deleted unless they ask
+    /// for them to be reassigned.
+    async fn delete_account(
+        &self,
+        user_id: i32,
+        input: &DeleteAccountRequest,
+    ) -> Result<ApiResponse<ErasureResponse>, ErrorResponse>;
+    /// Erases `user_id` on behalf of `actor_id`, who must already be
+    /// authorized to do so.
+    async fn erase_account(
+        &self,
+        actor_id: i32,
+        user_id: i32,
+        posts: PostErasurePolicy,
+        reassign_to: Option<i32>,
+    ) -> Result<ApiResponse<ErasureResponse>, ErrorResponse>;
+}
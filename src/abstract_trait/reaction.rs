@@ -0,0 +1,57 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, ErrorResponse, FindReactionsRequest, ReactionCounts,
+        ReactionKind, ReactionResponse, ReactionTarget, ToggleReactionResponse,
+    },
+    model::reaction::Reactor,
+    utils::AppError,
+};
+
+pub type DynReactionRepository = Arc<dyn ReactionRepositoryTrait + Send + Sync>;
+pub type DynReactionService = Arc<dyn ReactionServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait ReactionRepositoryTrait {
+    async fn toggle(
+        &self,
+        user_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+        kind: ReactionKind,
+    ) -> Result<bool, AppError>;
+    async fn count_by_targets(
+        &self,
+        target: ReactionTarget,
+        target_ids: &[i32],
+    ) -> Result<HashMap<i32, ReactionCounts>, AppError>;
+    async fn find_reactors(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        kind: Option<ReactionKind>,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Reactor>, i64), AppError>;
+}
+
+#[async_trait]
+pub trait ReactionServiceTrait {
+    async fn toggle_reaction(
+        &self,
+        user_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+        kind: ReactionKind,
+    ) -> Result<ApiResponse<ToggleReactionResponse>, ErrorResponse>;
+    async fn get_reactions(
+        &self,
+        viewer_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+        req: FindReactionsRequest,
+    ) -> Result<ApiResponsePagination<Vec<ReactionResponse>>, ErrorResponse>;
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CreateReportRequest, ErrorResponse,
+        FindReportsRequest, ReactionTarget, ReportResponse, ReportStatus, UpdateReportRequest,
+    },
+    model::report::{Report, ReportEntry},
+    utils::AppError,
+};
+
+pub type DynReportRepository = Arc<dyn ReportRepositoryTrait + Send + Sync>;
+pub type DynReportService = Arc<dyn ReportServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait ReportRepositoryTrait {
+    /// `None` when the reporter already reported this target.
+    async fn create(
+        &self,
+        reporter_id: i32,
+        input: &CreateReportRequest,
+    ) -> Result<Option<Report>, AppError>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<Report>, AppError>;
+    async fn find_paginated(
+        &self,
+        req: &FindReportsRequest,
+    ) -> Result<(Vec<ReportEntry>, i64), AppError>;
+    /// Open and triaged reports on a target.
+    async fn count_open(&self, target: ReactionTarget, target_id: i32) -> Result<i64, AppError>;
+    /// Takes a post off public listings or sends an approved comment back
+    /// to the moderation queue.
+    async fn hide_target(&self, target: ReactionTarget, target_id: i32) -> Result<(), AppError>;
+    /// Applies `status` and returns the updated report. Resolving or
+    /// dismissing closes every pending report on the target and hides or
+    /// restores the content in the same transaction.
+    async fn set_status(
+        &self,
+        report: &Report,
+        status: ReportStatus,
+        moderator_id: i32,
+    ) -> Result<Report, AppError>;
+}
+
+#[async_trait]
+pub trait ReportServiceTrait {
+    async fn create_report(
+        &self,
+        reporter_id: i32,
+        input: &CreateReportRequest,
+    ) -> Result<ApiResponse<ReportResponse>, ErrorResponse>;
+    async fn get_inbox(
+        &self,
+        moderator_id: i32,
+        req: FindReportsRequest,
+    ) -> Result<ApiResponsePagination<Vec<ReportResponse>>, ErrorResponse>;
+    async fn update_report(
+        &self,
+        moderator_id: i32,
+        report_id: i32,
+        input: &UpdateReportRequest,
+    ) -> Result<ApiResponse<ReportResponse>, ErrorResponse>;
+}
@@ -0,0 +1,34 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::utils::AppError;
+
+pub type DynSpamFilter = Arc<dyn SpamFilter + Send + Sync>;
+pub type DynSpamRepository = Arc<dyn SpamRepositoryTrait + Send + Sync>;
+
+/// A check run on every new comment. Filters only flag; the caller decides
+/// what happens to a flagged comment.
+#[async_trait]
+pub trait SpamFilter {
+    fn name(&self) -> &'static str;
+    async fn is_spam(&self, text: &str) -> Result<bool, AppError>;
+
+    /// Feedback from a moderator decision on a comment; a later verdict on
+    /// the same comment replaces the earlier one. Static filters ignore it.
+    async fn learn(&self, _comment_id: i32, _text: &str, _is_spam: bool) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait SpamRepositoryTrait {
+    /// Adds one comment and its distinct tokens to the spam or ham counts,
+    /// first taking back whatever the comment was trained as before.
+    async fn record(&self, comment_id: i32, tokens: &[String], is_spam: bool) -> Result<(), AppError>;
+    /// `(spam_count, ham_count)` for the tokens seen in training.
+    async fn token_counts(&self, tokens: &[String])
+        -> Result<HashMap<String, (i64, i64)>, AppError>;
+    /// `(spam_documents, ham_documents)` trained so far.
+    async fn document_counts(&self) -> Result<(i64, i64), AppError>;
+}
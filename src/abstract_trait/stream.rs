@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+use crate::{domain::StreamEvent, utils::AppError};
+
+pub type DynStreamService = Arc<dyn StreamServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait StreamServiceTrait {
+    /// Every event published on this replica; subscribers filter by topic.
+    /// A receiver that falls too far behind skips ahead and sees `Lagged`.
+    fn subscribe(&self) -> broadcast::Receiver<StreamEvent>;
+    /// Turns a `NOTIFY` from the database into an event for local subscribers.
+    async fn dispatch(&self, channel: &str, payload: &str) -> Result<(), AppError>;
+    /// Tells every subscriber to reload, after events may have been missed.
+    fn resync(&self);
+}
@@ -1,7 +1,15 @@
 use std::sync::Arc;
 use async_trait::async_trait;
 
-use crate::{domain::{ApiResponse, CreateUserRequest, ErrorResponse,  UpdateUserRequest, UserResponse},  model::user::User, utils::AppError};
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CreateUserRequest, ErrorResponse, FindAllPostRequest,
+        FindUsersRequest, PostResponse, UpdateProfileRequest, UpdateUserRequest, UserProfileResponse,
+        UserResponse, UserRole,
+    },
+    model::user::User,
+    utils::AppError,
+};
 
 
 pub type DynUserRepository = Arc<dyn UserRepositoryTrait + Send + Sync>;
@@ -12,15 +20,35 @@ pub trait UserRepositoryTrait {
     async fn find_by_email_exists(&self, email: &str) -> Result<bool, AppError>;
     async fn create_user(
         &self,
-        input: &CreateUserRequest
+        input: &CreateUserRequest,
+        username: &str,
     ) -> Result<User, AppError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
+    /// Usernames are stored lowercase; the lookup ignores case.
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, AppError>;
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<User>, AppError>;
+    async fn find_paginated(&self, req: &FindUsersRequest) -> Result<(Vec<User>, i64), AppError>;
     async fn update_user(
         &self,
         input: &UpdateUserRequest
     ) -> Result<User, AppError>;
+    async fn update_profile(
+        &self,
+        id: i32,
+        input: &UpdateProfileRequest,
+    ) -> Result<User, AppError>;
+    async fn set_avatar(&self, id: i32, avatar: Option<&str>) -> Result<User, AppError>;
     async fn delete_user(&self, email: &str) -> Result<(), AppError>;
+    /// Deactivating bumps `token_version`, revoking existing tokens.
+    async fn set_active(&self, id: i32, active: bool) -> Result<User, AppError>;
+    async fn set_role(&self, id: i32, role: UserRole) -> Result<User, AppError>;
+    /// Flags the account for a reset and revokes existing tokens.
+    async fn require_password_reset(&self, id: i32) -> Result<User, AppError>;
+    /// Stores a new password hash, clears a pending reset and revokes
+    /// existing tokens.
+    async fn set_password(&self, id: i32, password_hash: &str) -> Result<User, AppError>;
+    async fn set_email(&self, id: i32, email: &str) -> Result<User, AppError>;
 }
 
 #[async_trait]
@@ -36,5 +64,26 @@ pub trait UserServiceTrait {
         &self,
         input: &UpdateUserRequest
     ) -> Result<Option<ApiResponse<UserResponse>>, ErrorResponse>;
-    async fn delete_user(&self, email: &str) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn get_profile(
+        &self,
+        username: &str,
+        viewer_id: Option<i32>,
+    ) -> Result<ApiResponse<UserProfileResponse>, ErrorResponse>;
+    async fn get_user_posts(
+        &self,
+        username: &str,
+        req: FindAllPostRequest,
+        viewer_id: Option<i32>,
+    ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse>;
+    async fn update_profile(
+        &self,
+        user_id: i32,
+        input: &UpdateProfileRequest,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    /// Points the avatar at an uploaded file, or clears it with `None`.
+    async fn set_avatar(
+        &self,
+        user_id: i32,
+        avatar: Option<&str>,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
 }
\ No newline at end of file
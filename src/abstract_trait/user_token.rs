@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{domain::TokenPurpose, model::user_token::UserToken, utils::AppError};
+
+pub type DynUserTokenRepository = Arc<dyn UserTokenRepositoryTrait + Send + Sync>;
+
+#[async_trait]
+pub trait UserTokenRepositoryTrait {
+    /// Replaces any earlier token the user holds for the same purpose.
+    async fn create(
+        &self,
+        user_id: i32,
+        purpose: TokenPurpose,
+        token_hash: &str,
+        new_email: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<UserToken, AppError>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<UserToken>, AppError>;
+    async fn delete_for_user(&self, user_id: i32, purpose: TokenPurpose) -> Result<(), AppError>;
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::{
+    domain::{ApiResponse, ErrorResponse, FindTrendingPostRequest, TrendingPostResponse},
+    model::view::TrendingPost,
+    utils::AppError,
+};
+
+pub type DynPostViewRepository = Arc<dyn PostViewRepositoryTrait + Send + Sync>;
+pub type DynPostViewService = Arc<dyn PostViewServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait PostViewRepositoryTrait {
+    async fn increment_views(&self, batch: &[(i32, NaiveDate, i64)]) -> Result<(), AppError>;
+    async fn find_trending(
+        &self,
+        window_days: i32,
+        limit: i32,
+    ) -> Result<Vec<TrendingPost>, AppError>;
+}
+
+#[async_trait]
+pub trait PostViewServiceTrait {
+    fn record_view(&self, post_id: i32, visitor: &str);
+    async fn flush(&self) -> Result<(), AppError>;
+    async fn get_trending(
+        &self,
+        req: FindTrendingPostRequest,
+    ) -> Result<ApiResponse<Vec<TrendingPostResponse>>, ErrorResponse>;
+}
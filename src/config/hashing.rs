@@ -1,7 +1,7 @@
 use bcrypt::{hash, verify, BcryptError};
 use crate::utils::AppError;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Hashing;
 
 impl Hashing {
@@ -16,7 +16,7 @@ impl Hashing {
     pub async fn compare_password(&self, hashed_password: &str, password: &str) -> Result<(), AppError> {
         match verify(password, hashed_password) {
             Ok(true) => Ok(()), // Password matches
-            Ok(false) => Err(AppError::HashingError(BcryptError::from(std::io::Error::new(std::io::ErrorKind::Other, "Passwords do not match.")))), 
+            Ok(false) => Err(AppError::HashingError(BcryptError::from(std::io::Error::other("Passwords do not match.")))), 
             Err(e) => Err(AppError::BcryptError(e.to_string())),
         }
     }
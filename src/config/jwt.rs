@@ -13,14 +13,28 @@ pub struct Claims {
     pub user_id: i64,
     pub exp: usize,
     pub iat: usize,
+    /// The user's `token_version` when the token was issued.
+    #[serde(default)]
+    pub ver: i32,
 }
 
 impl Claims {
-    pub fn new(user_id: i64, exp: usize, iat: usize) -> Self {
-        Claims { user_id, exp, iat}
+    pub fn new(user_id: i64, exp: usize, iat: usize, ver: i32) -> Self {
+        Claims { user_id, exp, iat, ver }
     }
 }
 
+/// How long an unlocked password-protected post stays readable.
+pub const POST_UNLOCK_TTL_MINUTES: i64 = 30;
+
+/// Proves the holder entered the password of `post_id`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnlockClaims {
+    pub post_id: i32,
+    pub exp: usize,
+    pub iat: usize,
+}
+
 #[derive(Clone)]
 pub struct JwtConfig{
     pub jwt_secret: String,
@@ -33,12 +47,12 @@ impl JwtConfig {
         }
     }
 
-    pub fn generate_token(&self, user_id: i64) -> Result<String, AppError> {
+    pub fn generate_token(&self, user_id: i64, token_version: i32) -> Result<String, AppError> {
         let now = Utc::now();
         let iat = now.timestamp() as usize;
         let exp = (now + Duration::minutes(60)).timestamp() as usize;
 
-        let claims = Claims::new(user_id, exp, iat);
+        let claims = Claims::new(user_id, exp, iat, token_version);
 
         match encode(
             &Header::default(),
@@ -46,11 +60,11 @@ impl JwtConfig {
             &EncodingKey::from_secret("YOUR_SECRET_KEY".as_ref()),
         ) {
             Ok(token) => Ok(token),
-            Err(err) => Err(AppError::TokenGenerationError(err.into())),
+            Err(err) => Err(AppError::TokenGenerationError(err)),
         }
     }
 
-    pub fn verify_token(&self, token: &str) -> Result<i64, AppError> {
+    pub fn verify_token(&self, token: &str) -> Result<Claims, AppError> {
         let decoding_key = DecodingKey::from_secret("YOUR_SECRET_KEY".as_ref());
     
         match decode::<Claims>(token, &decoding_key, &Validation::default()) {
@@ -58,7 +72,7 @@ impl JwtConfig {
                 let current_time = Utc::now().timestamp() as usize;
     
                 if token_data.claims.exp >= current_time {
-                    Ok(token_data.claims.user_id)
+                    Ok(token_data.claims)
                 } else {
                     Err(AppError::TokenExpiredError)
                 }
@@ -73,5 +87,30 @@ impl JwtConfig {
             }
         }
     }
-    
-}
\ No newline at end of file
+
+    pub fn generate_unlock_token(&self, post_id: i32) -> Result<String, AppError> {
+        let now = Utc::now();
+        let claims = UnlockClaims {
+            post_id,
+            exp: (now + Duration::minutes(POST_UNLOCK_TTL_MINUTES)).timestamp() as usize,
+            iat: now.timestamp() as usize,
+        };
+
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_ref()),
+        )
+        .map_err(AppError::TokenGenerationError)
+    }
+
+    /// Expired, forged or foreign tokens all count as not unlocked.
+    pub fn verify_unlock_token(&self, token: &str, post_id: i32) -> bool {
+        decode::<UnlockClaims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+            &Validation::default(),
+        )
+        .is_ok_and(|token_data| token_data.claims.post_id == post_id)
+    }
+}
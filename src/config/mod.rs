@@ -1,9 +1,9 @@
 mod hashing;
 mod jwt;
-mod config;
+mod settings;
 mod database;
 
-pub use self::jwt::JwtConfig;
+pub use self::jwt::{JwtConfig, POST_UNLOCK_TTL_MINUTES};
 pub use self::hashing::Hashing;
-pub use self::config::Config;
+pub use self::settings::Config;
 pub use self::database::{ConnectionManager, ConnectionPool};
\ No newline at end of file
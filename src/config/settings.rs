@@ -0,0 +1,97 @@
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database_url: String,
+    pub jwt_secret: String,
+    pub run_migrations: bool,
+    pub port: u16,
+    /// Deepest reply level allowed below a top-level comment.
+    pub comment_max_depth: i32,
+    /// Hold comments from users without an approved comment for review.
+    pub comment_hold_new_users: bool,
+    /// Links allowed in a comment before it is treated as spam.
+    pub spam_max_links: usize,
+    /// Words that mark a comment as spam, matched case-insensitively.
+    pub spam_blocked_words: Vec<String>,
+    /// Domains (and their subdomains) that mark a comment as spam.
+    pub spam_blocked_domains: Vec<String>,
+    /// Probability above which the trained classifier flags a comment.
+    pub spam_bayes_threshold: f64,
+    /// Pending reports that hide a post or comment until review; 0 disables.
+    pub report_hide_threshold: i64,
+    /// Live events buffered per subscriber before a slow client must resync.
+    pub stream_buffer_size: usize,
+}
+
+impl Config {
+    pub fn init() -> Config {
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+
+        let run_migrations_str =
+            std::env::var("RUN_MIGRATIONS").expect("RUN_MIGRATIONS must be set");
+        let port_str = std::env::var("PORT").expect("PORT must be set");
+
+
+        let run_migrations = match run_migrations_str.as_str() {
+            "true" => true,
+            "false" => false,
+            _ => panic!("RUN_MIGRATIONS must be either 'true' or 'false'"),
+        };
+
+        let port = port_str.parse().expect("Invalid value for PORT");
+
+        let comment_max_depth = std::env::var("COMMENT_MAX_DEPTH")
+            .map(|value| value.parse().expect("Invalid value for COMMENT_MAX_DEPTH"))
+            .unwrap_or(5);
+
+        let comment_hold_new_users = match std::env::var("COMMENT_HOLD_NEW_USERS").as_deref() {
+            Ok("true") => true,
+            Ok("false") | Err(_) => false,
+            _ => panic!("COMMENT_HOLD_NEW_USERS must be either 'true' or 'false'"),
+        };
+
+        let spam_max_links = std::env::var("SPAM_MAX_LINKS")
+            .map(|value| value.parse().expect("Invalid value for SPAM_MAX_LINKS"))
+            .unwrap_or(3);
+
+        let spam_blocked_words = list_var("SPAM_BLOCKED_WORDS");
+        let spam_blocked_domains = list_var("SPAM_BLOCKED_DOMAINS");
+
+        let spam_bayes_threshold = std::env::var("SPAM_BAYES_THRESHOLD")
+            .map(|value| value.parse().expect("Invalid value for SPAM_BAYES_THRESHOLD"))
+            .unwrap_or(0.9);
+
+        let report_hide_threshold = std::env::var("REPORT_HIDE_THRESHOLD")
+            .map(|value| value.parse().expect("Invalid value for REPORT_HIDE_THRESHOLD"))
+            .unwrap_or(3);
+
+        let stream_buffer_size = std::env::var("STREAM_BUFFER_SIZE")
+            .map(|value| value.parse().expect("Invalid value for STREAM_BUFFER_SIZE"))
+            .unwrap_or(256);
+
+        Config {
+            database_url,
+            jwt_secret,
+            run_migrations,
+            port,
+            comment_max_depth,
+            comment_hold_new_users,
+            spam_max_links,
+            spam_blocked_words,
+            spam_blocked_domains,
+            spam_bayes_threshold,
+            report_hide_threshold,
+            stream_buffer_size,
+        }
+ 
+    }
+}
+
+fn list_var(name: &str) -> Vec<String> {
+    std::env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(|item| item.trim().to_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
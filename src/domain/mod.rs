@@ -2,13 +2,28 @@ mod request;
 mod response;
 
 pub use self::request::{
-    CreateCategoryRequest, CreateCommentRequest, CreatePostRequest, CreateUserRequest,
-    FindAllCategoryRequest, FindAllPostRequest, LoginRequest, RegisterRequest,
-    UpdateCategoryRequest, UpdateCommentRequest, UpdatePostRequest, UpdateUserRequest,
+    BlockKind, ChangeEmailRequest, ChangePasswordRequest, CommentPolicy, CommentSort, CommentStatus,
+    ConfirmEmailRequest, CreateCategoryRequest, CreateCommentRequest, CreateNotificationRequest,
+    CreatePostRequest, CreateReportRequest, CreateUserRequest, DeleteAccountRequest,
+    DeleteCategoryRequest, EraseUserRequest, ExportAccountRequest, ExportFormat,
+    FindAllCategoryRequest, FindAllPostRequest, FindBlocksRequest, FindCommentTreeRequest,
+    FindCommentsRequest, FindFeedRequest, FindFollowsRequest, FindModerationQueueRequest,
+    FindNotificationsRequest, FindPostDetailRequest, FindReactionsRequest, FindReportsRequest,
+    FindTrendingPostRequest, FindUsersRequest, FollowTarget, LoginRequest, NotificationKind,
+    PostErasurePolicy, PostReader, PostVisibility, ReactionKind, ReactionTarget, RegisterRequest,
+    ReportReason, ReportStatus, ResetPasswordRequest, ToggleReactionRequest, TokenPurpose,
+    UnlockPostRequest, UpdateCategoryRequest, UpdateCommentPolicyRequest, UpdateCommentRequest,
+    UpdateCommentStatusRequest, UpdateNotificationPreferencesRequest, UpdatePostRequest,
+    UpdateProfileRequest, UpdateReportRequest, UpdateUserRequest, UpdateUserRoleRequest, UserRole,
 };
 
 pub use self::response::{
-    ApiResponse, ApiResponsePagination, CategoryResponse, CommentResponse, DeleteResponse,
-    ErrorResponse, Pagination, PostRelationResponse, PostResponse, UploadResponse, UserResponse,
-   
+    AccountExportResponse, ApiResponse, ApiResponsePagination, BlockResponse, BlockedUserResponse,
+    CategoryMergeResponse, CategoryResponse, CategoryTreeResponse, CommentResponse,
+    CommentStatusUpdateResponse, CommentTreeResponse, DeleteResponse, ErasureResponse,
+    ErrorResponse, ExportArchive, FollowResponse, NotificationPreferencesResponse,
+    NotificationReadResponse, NotificationResponse, Pagination, PasswordResetResponse,
+    PostDetailResponse, PostResponse, PublicUserResponse, ReactionCounts,
+    ReactionResponse, ReportResponse, StreamEvent, StreamTopic, ToggleReactionResponse,
+    TrendingPostResponse, UnreadCountResponse, UploadResponse, UserProfileResponse, UserResponse,
 };
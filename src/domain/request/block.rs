@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// A block keeps the other user away from you; a mute only hides them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockKind {
+    Block,
+    Mute,
+}
+
+impl BlockKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockKind::Block => "block",
+            BlockKind::Mute => "mute",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Self {
+        match kind {
+            "mute" => BlockKind::Mute,
+            _ => BlockKind::Block,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindBlocksRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    /// Both kinds when omitted.
+    #[serde(default)]
+    #[param(inline)]
+    pub kind: Option<BlockKind>,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    20
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
+use super::nullable;
+
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllCategoryRequest {
     #[serde(default = "default_page")]
@@ -13,6 +15,14 @@ pub struct FindAllCategoryRequest {
     pub search: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct DeleteCategoryRequest {
+    /// Move the category's posts here first; without it the delete is
+    /// refused while the category still has posts.
+    #[serde(default)]
+    pub reassign_to: Option<i32>,
+}
+
 fn default_page() -> i32 {
     1
 }
@@ -24,10 +34,41 @@ fn default_page_size() -> i32 {
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct CreateCategoryRequest {
     pub name: String,
+    /// Nest the new category below this one; top level when omitted.
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    /// Generated from the name when omitted.
+    #[serde(default)]
+    pub slug: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Path or URL of an uploaded image.
+    #[serde(default)]
+    pub cover_image: Option<String>,
+    /// Lower values are listed first.
+    #[serde(default)]
+    pub sort_order: i32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
 pub struct UpdateCategoryRequest {
     pub id: Option<i32>,
     pub name: Option<String>,
+    /// Move below another category, or to the top level with `null`.
+    /// Left unchanged when omitted.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<i32>)]
+    pub parent_id: Option<Option<i32>>,
+    pub slug: Option<String>,
+    /// Cleared with `null`; left unchanged when omitted.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    /// Cleared with `null`; left unchanged when omitted.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub cover_image: Option<Option<String>>,
+    pub sort_order: Option<i32>,
+    #[serde(skip)]
+    pub version: Option<i32>,
 }
@@ -1,16 +1,101 @@
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateCommentRequest {
     pub id_post_comment: i32,
-    pub user_name_comment: String,
     pub comment: String,
+    /// Comment being replied to; omit for a top-level comment.
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    /// Filled from the authenticated user, never from the body.
+    #[serde(skip)]
+    pub user_id: i32,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateCommentRequest {
-    pub id_post_comment: Option<i32>,
-    pub user_name_comment: String,
+    pub id: Option<i32>,
     pub comment: String,
-}
\ No newline at end of file
+    #[serde(skip)]
+    pub version: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    #[default]
+    Newest,
+    Oldest,
+    /// Most reactions first.
+    Top,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindCommentsRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    /// `next_cursor` of the previous page; takes precedence over `page`.
+    #[serde(default)]
+    pub cursor: Option<String>,
+
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: CommentSort,
+
+    /// Ignored on `/api/posts/{id}/comments`, where the path decides.
+    #[serde(default)]
+    pub post_id: Option<i32>,
+
+    #[serde(default)]
+    pub author_id: Option<i32>,
+
+    /// Hides authors this user blocked or muted and posts they may not
+    /// read; set from the token.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub viewer_id: Option<i32>,
+
+    /// Set once the post in `post_id` has been opened with the reader's
+    /// unlock token; otherwise comments on password-protected posts are
+    /// left out unless the viewer wrote or edits the post.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub post_unlocked: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindCommentTreeRequest {
+    /// Page of the first level of the tree.
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    /// How many replies to load below each comment on deeper levels.
+    #[serde(default = "default_replies_page_size")]
+    pub replies_page_size: i32,
+
+    /// Hides authors this user blocked or muted, with their replies; set
+    /// from the token.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub viewer_id: Option<i32>,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    10
+}
+
+fn default_replies_page_size() -> i32 {
+    3
+}
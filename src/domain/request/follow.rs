@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+/// What a follow points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowTarget {
+    User,
+    Category,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindFollowsRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    20
+}
@@ -1,14 +1,72 @@
+use serde::{Deserialize, Deserializer};
+
 mod auth;
+mod block;
 mod category;
 mod comment;
+mod follow;
+mod moderation;
+mod notification;
 mod post;
+mod privacy;
+mod reaction;
+mod report;
 mod user;
 
-pub use self::category::{CreateCategoryRequest, FindAllCategoryRequest, UpdateCategoryRequest};
-pub use self::post::{CreatePostRequest, FindAllPostRequest, UpdatePostRequest};
+pub use self::block::{BlockKind, FindBlocksRequest};
+
+pub use self::category::{
+    CreateCategoryRequest, DeleteCategoryRequest, FindAllCategoryRequest, UpdateCategoryRequest,
+};
+pub use self::post::{
+    CreatePostRequest, FindAllPostRequest, FindFeedRequest, FindPostDetailRequest,
+    FindTrendingPostRequest, PostReader, PostVisibility, UnlockPostRequest, UpdatePostRequest,
+};
+
+pub use self::privacy::{
+    DeleteAccountRequest, EraseUserRequest, ExportAccountRequest, ExportFormat, PostErasurePolicy,
+};
+
+pub use self::follow::{FindFollowsRequest, FollowTarget};
+
+pub use self::reaction::{
+    FindReactionsRequest, ReactionKind, ReactionTarget, ToggleReactionRequest,
+};
+
+pub use self::comment::{
+    CommentSort, CreateCommentRequest, FindCommentTreeRequest, FindCommentsRequest,
+    UpdateCommentRequest,
+};
+
+pub use self::moderation::{
+    CommentPolicy, CommentStatus, FindModerationQueueRequest, UpdateCommentPolicyRequest,
+    UpdateCommentStatusRequest,
+};
+
+pub use self::notification::{
+    CreateNotificationRequest, FindNotificationsRequest, NotificationKind,
+    UpdateNotificationPreferencesRequest,
+};
+
+pub use self::report::{
+    CreateReportRequest, FindReportsRequest, ReportReason, ReportStatus, UpdateReportRequest,
+};
 
-pub use self::comment::{CreateCommentRequest, UpdateCommentRequest};
+pub use self::auth::{
+    ChangeEmailRequest, ChangePasswordRequest, ConfirmEmailRequest, LoginRequest, RegisterRequest,
+    ResetPasswordRequest, TokenPurpose,
+};
 
-pub use self::auth::{LoginRequest, RegisterRequest};
+pub use self::user::{
+    CreateUserRequest, FindUsersRequest, UpdateProfileRequest, UpdateUserRequest,
+    UpdateUserRoleRequest, UserRole,
+};
 
-pub use self::user::{CreateUserRequest, UpdateUserRequest};
+/// Tells an absent field (`None`) apart from an explicit `null` (`Some(None)`).
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
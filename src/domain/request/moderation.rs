@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Spam,
+}
+
+impl CommentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+            CommentStatus::Spam => "spam",
+        }
+    }
+}
+
+/// Who may comment on a post, set per category and optionally overridden per post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentPolicy {
+    Open,
+    Premoderated,
+    Closed,
+}
+
+impl CommentPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentPolicy::Open => "open",
+            CommentPolicy::Premoderated => "premoderated",
+            CommentPolicy::Closed => "closed",
+        }
+    }
+
+    /// Unknown values fall back to the most permissive policy.
+    pub fn parse(policy: &str) -> Self {
+        match policy {
+            "premoderated" => CommentPolicy::Premoderated,
+            "closed" => CommentPolicy::Closed,
+            _ => CommentPolicy::Open,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindModerationQueueRequest {
+    #[serde(default = "default_status")]
+    #[param(inline)]
+    pub status: CommentStatus,
+
+    #[serde(default)]
+    pub post_id: Option<i32>,
+
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+fn default_status() -> CommentStatus {
+    CommentStatus::Pending
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    20
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateCommentStatusRequest {
+    pub ids: Vec<i32>,
+    pub status: CommentStatus,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateCommentPolicyRequest {
+    /// `null` on a post falls back to the category's policy.
+    pub policy: Option<CommentPolicy>,
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::ReactionTarget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    /// Someone mentioned the user in a post or comment.
+    Mention,
+    /// Someone commented on the user's post.
+    Comment,
+    /// Someone replied to the user's comment.
+    Reply,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Mention => "mention",
+            NotificationKind::Comment => "comment",
+            NotificationKind::Reply => "reply",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateNotificationRequest {
+    pub user_id: i32,
+    /// The user whose action triggered the notification.
+    pub actor_id: Option<i32>,
+    pub kind: NotificationKind,
+    pub target_type: ReactionTarget,
+    pub target_id: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindNotificationsRequest {
+    #[serde(default)]
+    pub unread_only: bool,
+
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    20
+}
+
+/// Omitted fields keep their current value.
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub mention: Option<bool>,
+    pub comment: Option<bool>,
+    pub reply: Option<bool>,
+}
@@ -1,6 +1,58 @@
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
+/// Who can read a post and whether it shows up in listings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PostVisibility {
+    /// Listed and readable by anyone.
+    #[default]
+    Public,
+    /// Readable by anyone with the link, but never listed.
+    Unlisted,
+    /// Listed and readable for signed-in users only.
+    Members,
+    /// Readable by the author and by moderators and admins only.
+    Private,
+}
+
+impl PostVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostVisibility::Public => "public",
+            PostVisibility::Unlisted => "unlisted",
+            PostVisibility::Members => "members",
+            PostVisibility::Private => "private",
+        }
+    }
+
+    /// Unknown values fall back to the most restrictive level.
+    pub fn parse(visibility: &str) -> Self {
+        match visibility {
+            "public" => PostVisibility::Public,
+            "unlisted" => PostVisibility::Unlisted,
+            "members" => PostVisibility::Members,
+            _ => PostVisibility::Private,
+        }
+    }
+}
+
+/// On whose behalf a post is read, which decides the visibility levels
+/// that apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostReader {
+    /// Lookups made by the server itself, which see every post.
+    Internal,
+    Anonymous,
+    User(i32),
+}
+
+impl From<Option<i32>> for PostReader {
+    fn from(viewer_id: Option<i32>) -> Self {
+        viewer_id.map_or(PostReader::Anonymous, PostReader::User)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllPostRequest {
     #[serde(default = "default_page")]
@@ -11,6 +63,55 @@ pub struct FindAllPostRequest {
 
     #[serde(default)]
     pub search: String,
+
+    /// Only posts in this category.
+    pub category: Option<i32>,
+
+    /// With `category`, also include posts in its subcategories.
+    #[serde(default)]
+    pub include_descendants: bool,
+
+    /// Only posts by this user; set from the path on profile listings.
+    #[serde(skip)]
+    #[param(ignore)]
+    pub user_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindFeedRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    /// `next_cursor` of the previous page; takes precedence over `page`.
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindTrendingPostRequest {
+    /// Look-back window such as `24h`, `7d` or `30d`.
+    #[serde(default = "default_window")]
+    pub window: String,
+
+    #[serde(default = "default_page_size")]
+    pub limit: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindPostDetailRequest {
+    /// Page of the post's comments to embed.
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+fn default_window() -> String {
+    "7d".to_string()
 }
 
 fn default_page() -> i32 {
@@ -28,18 +129,31 @@ pub struct CreatePostRequest {
     #[schema(format = Binary, content_media_type = "application/octet-stream")]
     pub file: String,
     pub category_id: i32,
+    /// Filled from the authenticated user, never from the body.
+    #[serde(skip)]
     pub user_id: i32,
-    pub user_name: String,
+    #[serde(default)]
+    pub visibility: PostVisibility,
+    /// Readers must enter this password before the post is shown.
+    pub password: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, ToSchema)]
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct UpdatePostRequest {
     pub post_id: Option<i32>,
-    pub title: String,
-    pub body: String,
+    pub title: Option<String>,
+    pub body: Option<String>,
     #[schema(format = Binary, content_media_type = "application/octet-stream")]
-    pub file: String,
-    pub category_id: i32,
-    pub user_id: i32,
-    pub user_name: String,
+    pub file: Option<String>,
+    pub category_id: Option<i32>,
+    pub visibility: Option<PostVisibility>,
+    /// Sets the post's password; an empty value removes it.
+    pub password: Option<String>,
+    #[serde(skip)]
+    pub version: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UnlockPostRequest {
+    pub password: String,
 }
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// `data.json` plus every uploaded image under `files/`.
+    #[default]
+    Zip,
+    /// The JSON document alone; images are listed but not included.
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+pub struct ExportAccountRequest {
+    #[serde(default)]
+    #[param(inline)]
+    pub format: ExportFormat,
+}
+
+/// What happens to the posts of an erased account. Comments are always
+/// kept, detached from the account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PostErasurePolicy {
+    #[default]
+    Delete,
+    Reassign,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeleteAccountRequest {
+    pub current_password: String,
+    #[serde(default)]
+    pub posts: PostErasurePolicy,
+    /// The user who takes over the posts; required with `reassign`.
+    #[serde(default)]
+    pub reassign_to: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EraseUserRequest {
+    pub posts: PostErasurePolicy,
+    /// The user who takes over the posts; required with `reassign`.
+    #[serde(default)]
+    pub reassign_to: Option<i32>,
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::ReactionTarget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    Hate,
+    Violence,
+    Misinformation,
+    Other,
+}
+
+impl ReportReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportReason::Spam => "spam",
+            ReportReason::Harassment => "harassment",
+            ReportReason::Hate => "hate",
+            ReportReason::Violence => "violence",
+            ReportReason::Misinformation => "misinformation",
+            ReportReason::Other => "other",
+        }
+    }
+}
+
+/// `open` and `triaged` reports count towards auto-hiding; `resolved` keeps
+/// the content hidden and `dismissed` restores it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    Open,
+    Triaged,
+    Resolved,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Triaged => "triaged",
+            ReportStatus::Resolved => "resolved",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self, ReportStatus::Resolved | ReportStatus::Dismissed)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CreateReportRequest {
+    pub target_type: ReactionTarget,
+    pub target_id: i32,
+    pub reason: ReportReason,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindReportsRequest {
+    #[serde(default = "default_status")]
+    #[param(inline)]
+    pub status: ReportStatus,
+
+    #[serde(default)]
+    #[param(inline)]
+    pub target_type: Option<ReactionTarget>,
+
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+}
+
+fn default_status() -> ReportStatus {
+    ReportStatus::Open
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    20
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateReportRequest {
+    /// Resolving or dismissing closes every pending report on the same target.
+    pub status: ReportStatus,
+}
@@ -1,5 +1,42 @@
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use super::nullable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    User,
+    Moderator,
+    Admin,
+}
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::User => "user",
+            UserRole::Moderator => "moderator",
+            UserRole::Admin => "admin",
+        }
+    }
+
+    /// Unknown values fall back to the least privileged role.
+    pub fn parse(role: &str) -> Self {
+        match role {
+            "moderator" => UserRole::Moderator,
+            "admin" => UserRole::Admin,
+            _ => UserRole::User,
+        }
+    }
+
+    pub fn can_moderate(&self) -> bool {
+        matches!(self, UserRole::Moderator | UserRole::Admin)
+    }
+
+    pub fn is_admin(&self) -> bool {
+        matches!(self, UserRole::Admin)
+    }
+}
 
 #[derive(Debug, Clone,  Serialize, Deserialize, ToSchema)]
 pub struct CreateUserRequest {
@@ -7,15 +44,65 @@ pub struct CreateUserRequest {
     pub lastname: String,
     pub email: String,
     pub password: String,
+    /// Generated from the name when omitted.
+    #[serde(default)]
+    pub username: Option<String>,
 }
 
+/// Email and password changes go through `/api/users/me/email` and
+/// `/api/users/me/password`, which verify the current password.
 #[derive(Debug, Clone,  Serialize, Deserialize, ToSchema)]
 pub struct UpdateUserRequest {
     pub id: Option<i32>,
     pub firstname: Option<String>,
     pub lastname: Option<String>,
-   
-    pub email: Option<String>, 
-    pub password: Option<String>,
+    #[serde(skip)]
+    pub version: Option<i32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateProfileRequest {
+    pub username: Option<String>,
+    /// Cleared with `null`; left unchanged when omitted.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub bio: Option<Option<String>>,
+    /// An `http` or `https` URL. Cleared with `null`; left unchanged when omitted.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub website: Option<Option<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
+pub struct FindUsersRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+
+    /// Matches email, username, first or last name, ignoring case.
+    #[serde(default)]
+    pub search: Option<String>,
+
+    #[serde(default)]
+    #[param(inline)]
+    pub role: Option<UserRole>,
+
+    /// `false` lists deactivated accounts only.
+    #[serde(default)]
+    pub active: Option<bool>,
+}
+
+fn default_page() -> i32 {
+    1
+}
+
+fn default_page_size() -> i32 {
+    20
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateUserRoleRequest {
+    pub role: UserRole,
+}
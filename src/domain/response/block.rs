@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::{BlockKind, PublicUserResponse};
+
+/// The caller's standing towards the target after the change.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockResponse {
+    pub blocked: bool,
+    pub muted: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockedUserResponse {
+    pub user: PublicUserResponse,
+    pub kind: BlockKind,
+    pub created_at: DateTime<Utc>,
+}
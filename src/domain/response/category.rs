@@ -8,6 +8,14 @@ use crate::model::category::Category;
 pub struct CategoryResponse {
     pub id: i32,
     pub name: String,
+    pub version: i32,
+    pub parent_id: Option<i32>,
+    pub slug: String,
+    pub description: Option<String>,
+    pub cover_image: Option<String>,
+    pub sort_order: i32,
+    /// Visible posts filed directly in this category.
+    pub post_count: i64,
 }
 
 impl From<Category> for CategoryResponse {
@@ -15,6 +23,41 @@ impl From<Category> for CategoryResponse {
         CategoryResponse {
             id: category.id,
             name: category.name,
+            version: category.version,
+            parent_id: category.parent_id,
+            slug: category.slug,
+            description: category.description,
+            cover_image: category.cover_image,
+            sort_order: category.sort_order,
+            post_count: category.post_count,
         }
     }
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryTreeResponse {
+    #[serde(flatten)]
+    pub category: CategoryResponse,
+    #[schema(no_recursion)]
+    pub children: Vec<CategoryTreeResponse>,
+}
+
+impl From<Category> for CategoryTreeResponse {
+    fn from(category: Category) -> Self {
+        CategoryTreeResponse {
+            category: CategoryResponse::from(category),
+            children: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryMergeResponse {
+    /// The category that was removed.
+    pub source_id: i32,
+    /// The category that took over its posts.
+    pub target_id: i32,
+    pub moved_posts: u64,
+    /// Direct children re-attached to a new parent.
+    pub moved_categories: u64,
+}
@@ -1,7 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::model::comment::Comment;
+use crate::{
+    domain::{PublicUserResponse, ReactionCounts},
+    model::comment::{Comment, CommentNode},
+};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CommentResponse {
@@ -9,6 +13,19 @@ pub struct CommentResponse {
     pub id_post_comment: i32,
     pub user_name_comment: String,
     pub comment: String,
+    pub version: i32,
+    pub parent_id: Option<i32>,
+    pub depth: i32,
+    /// `pending`, `approved`, `rejected` or `spam`; only approved comments are listed publicly.
+    pub status: String,
+    /// Tombstoned comments keep their place in the thread but lose their content.
+    pub deleted: bool,
+    /// `None` for guest comments written before comments were tied to accounts.
+    pub user_id: Option<i32>,
+    /// `None` for guest comments; `user_name_comment` is the only name they carry.
+    pub author: Option<PublicUserResponse>,
+    pub reactions: ReactionCounts,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 impl From<Comment> for CommentResponse {
@@ -18,6 +35,35 @@ impl From<Comment> for CommentResponse {
             id_post_comment: comment.id_post_comment,
             user_name_comment: comment.user_name_comment,
             comment: comment.comment,
+            version: comment.version,
+            parent_id: comment.parent_id,
+            depth: comment.depth,
+            status: comment.status,
+            deleted: comment.is_deleted,
+            user_id: comment.user_id,
+            author: None,
+            reactions: ReactionCounts::new(),
+            created_at: comment.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentTreeResponse {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    /// Total number of direct replies, which may exceed `replies.len()`.
+    pub reply_count: i64,
+    #[schema(no_recursion)]
+    pub replies: Vec<CommentTreeResponse>,
+}
+
+impl From<CommentNode> for CommentTreeResponse {
+    fn from(node: CommentNode) -> Self {
+        CommentTreeResponse {
+            comment: CommentResponse::from(node.comment),
+            reply_count: node.reply_count,
+            replies: Vec::new(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowResponse {
+    /// Whether the caller now follows the target.
+    pub following: bool,
+    /// The target's follower count after the change.
+    pub followers: i64,
+}
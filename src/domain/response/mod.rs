@@ -1,24 +1,48 @@
 use core::fmt;
 
+use axum::http::StatusCode;
 use serde::Serialize;
 use std::fmt::Formatter;
 use utoipa::ToSchema;
 
+mod block;
 mod category;
 mod comment;
 mod file;
+mod follow;
+mod moderation;
+mod notification;
 mod pagination;
 mod post;
+mod privacy;
+mod reaction;
+mod report;
+mod stream;
 mod user;
 
 use crate::utils::AppError;
 
-pub use self::category::CategoryResponse;
-pub use self::comment::CommentResponse;
+pub use self::block::{BlockResponse, BlockedUserResponse};
+pub use self::category::{CategoryMergeResponse, CategoryResponse, CategoryTreeResponse};
+pub use self::comment::{CommentResponse, CommentTreeResponse};
 pub use self::file::{DeleteResponse, UploadResponse};
+pub use self::follow::FollowResponse;
+pub use self::moderation::CommentStatusUpdateResponse;
+pub use self::notification::{
+    NotificationPreferencesResponse, NotificationReadResponse, NotificationResponse,
+    UnreadCountResponse,
+};
 pub use self::pagination::Pagination;
-pub use self::post::{PostRelationResponse, PostResponse};
-pub use self::user::UserResponse;
+pub use self::post::{
+    PostDetailResponse, PostResponse, TrendingPostResponse,
+};
+pub use self::privacy::{AccountExportResponse, ErasureResponse, ExportArchive};
+pub use self::reaction::{ReactionCounts, ReactionResponse, ToggleReactionResponse};
+pub use self::report::ReportResponse;
+pub use self::stream::{StreamEvent, StreamTopic};
+pub use self::user::{
+    PasswordResetResponse, PublicUserResponse, UserProfileResponse, UserResponse,
+};
 
 #[derive(Debug, Serialize, Clone, ToSchema)]
 pub struct ApiResponse<T> {
@@ -57,36 +81,77 @@ impl<T: Serialize> fmt::Display for ApiResponsePagination<T> {
 pub struct ErrorResponse {
     pub status: String,
     pub message: String,
+    #[serde(skip)]
+    pub code: StatusCode,
 }
 
 impl From<AppError> for ErrorResponse {
     fn from(error: AppError) -> Self {
-        let (status, message) = match error {
-            AppError::SqlxError(_) => ("error".to_string(), "Database error occurred".to_string()),
+        let (code, status, message) = match error {
+            AppError::SqlxError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error".to_string(),
+                "Database error occurred".to_string(),
+            ),
             AppError::HashingError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
                 "error".to_string(),
                 "Error during password hashing".to_string(),
             ),
-            AppError::NotFound(ref msg) => ("error".to_string(), msg.clone()),
-            AppError::TokenExpiredError => ("error".to_string(), "Token has expired".to_string()),
-            AppError::TokenValidationError => {
-                ("error".to_string(), "Token validation failed".to_string())
-            }
-            AppError::TokenGenerationError(_) => {
-                ("error".to_string(), "Token generation failed".to_string())
+            AppError::NotFound(ref msg) => (StatusCode::NOT_FOUND, "error".to_string(), msg.clone()),
+            AppError::TokenExpiredError => (
+                StatusCode::UNAUTHORIZED,
+                "error".to_string(),
+                "Token has expired".to_string(),
+            ),
+            AppError::TokenValidationError => (
+                StatusCode::UNAUTHORIZED,
+                "error".to_string(),
+                "Token validation failed".to_string(),
+            ),
+            AppError::TokenGenerationError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error".to_string(),
+                "Token generation failed".to_string(),
+            ),
+            AppError::BcryptError(ref msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "error".to_string(),
+                format!("Bcrypt error: {}", msg),
+            ),
+            AppError::InvalidCredentials => (
+                StatusCode::UNAUTHORIZED,
+                "error".to_string(),
+                "Invalid credentials".to_string(),
+            ),
+            AppError::EmailAlreadyExists => (
+                StatusCode::CONFLICT,
+                "error".to_string(),
+                "Email already exists".to_string(),
+            ),
+            AppError::ValidationError(ref msg) => {
+                (StatusCode::BAD_REQUEST, "error".to_string(), msg.clone())
             }
-            AppError::BcryptError(ref msg) => {
-                ("error".to_string(), format!("Bcrypt error: {}", msg))
+            AppError::Forbidden(ref msg) => {
+                (StatusCode::FORBIDDEN, "fail".to_string(), msg.clone())
             }
-            AppError::InvalidCredentials => {
-                ("error".to_string(), "Invalid credentials".to_string())
+            AppError::PreconditionFailed(ref msg) => (
+                StatusCode::PRECONDITION_FAILED,
+                "fail".to_string(),
+                msg.clone(),
+            ),
+            AppError::Conflict(ref msg) => {
+                (StatusCode::CONFLICT, "fail".to_string(), msg.clone())
             }
-            AppError::EmailAlreadyExists => {
-                ("error".to_string(), "Email already exists".to_string())
+            AppError::TooManyRequests(ref msg) => {
+                (StatusCode::TOO_MANY_REQUESTS, "fail".to_string(), msg.clone())
             }
-            AppError::ValidationError(_) => ("error".to_string(), "Validation error".to_string()),
         };
-        ErrorResponse { status, message }
+        ErrorResponse {
+            status,
+            message,
+            code,
+        }
     }
 }
 
@@ -0,0 +1,9 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentStatusUpdateResponse {
+    pub status: String,
+    /// Comments whose status actually changed; unknown ids are skipped.
+    pub updated: u64,
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::model::notification::{Notification, NotificationPreferences};
+
+use super::PublicUserResponse;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationResponse {
+    pub id: i32,
+    pub kind: String,
+    pub actor_id: Option<i32>,
+    pub actor: Option<PublicUserResponse>,
+    pub target_type: String,
+    pub target_id: i32,
+    pub read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<Notification> for NotificationResponse {
+    fn from(value: Notification) -> Self {
+        NotificationResponse {
+            id: value.id,
+            kind: value.kind,
+            actor_id: value.actor_id,
+            actor: None,
+            target_type: value.target_type,
+            target_id: value.target_id,
+            read: value.read_at.is_some(),
+            read_at: value.read_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnreadCountResponse {
+    pub unread: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationReadResponse {
+    /// Notifications that were unread before this call.
+    pub updated: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreferencesResponse {
+    pub mention: bool,
+    pub comment: bool,
+    pub reply: bool,
+}
+
+impl From<NotificationPreferences> for NotificationPreferencesResponse {
+    fn from(value: NotificationPreferences) -> Self {
+        NotificationPreferencesResponse {
+            mention: value.mention,
+            comment: value.comment,
+            reply: value.reply,
+        }
+    }
+}
@@ -7,4 +7,8 @@ pub struct Pagination {
     pub page_size: i32,
     pub total_items: i64,
     pub total_pages: i32,
+    /// Pass back as `cursor` to fetch the next page; absent on the last page
+    /// and on endpoints without cursor support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
@@ -1,7 +1,14 @@
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use crate::model::posts::{PostRelationModel, Post};
+use crate::domain::{
+    CategoryResponse, CommentResponse, Pagination, PostVisibility, PublicUserResponse,
+    ReactionCounts,
+};
+use crate::model::{
+    posts::Post,
+    view::TrendingPost,
+};
 
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -13,6 +20,14 @@ pub struct PostResponse {
     pub category_id: i32,
     pub user_id: i32,
     pub user_name: String,
+    pub version: i32,
+    pub visibility: PostVisibility,
+    /// The body is left empty in listings until the reader unlocks the post.
+    pub password_protected: bool,
+    pub reactions: ReactionCounts,
+    /// Only present when the caller is authenticated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmarked: Option<bool>,
 }
 
 impl From<Post> for PostResponse {
@@ -25,29 +40,41 @@ impl From<Post> for PostResponse {
             category_id: post.category_id,
             user_id: post.user_id,
             user_name: post.user_name,
+            version: post.version,
+            visibility: PostVisibility::parse(&post.visibility),
+            password_protected: post.password_hash.is_some(),
+            reactions: ReactionCounts::new(),
+            bookmarked: None,
         }
     }
 }
 
+/// A post together with its author, category and one page of comments.
 #[derive(Debug, Serialize, ToSchema)]
-pub struct PostRelationResponse {
-    pub post_id: i32,
-    pub title: String,
-    pub comment_id: i32,
-    pub id_post_comment: i32,
-    pub user_name_comment: String,
-    pub comment: String,
+pub struct PostDetailResponse {
+    pub post: PostResponse,
+    /// `None` when the author account no longer exists.
+    pub author: Option<PublicUserResponse>,
+    /// `None` when the category no longer exists.
+    pub category: Option<CategoryResponse>,
+    pub comments: Vec<CommentResponse>,
+    pub comments_pagination: Pagination,
 }
 
-impl From<PostRelationModel> for PostRelationResponse {
-    fn from(post_relation: PostRelationModel) -> Self {
-        PostRelationResponse {
-            post_id: post_relation.post_id,
-            title: post_relation.title,
-            comment_id: post_relation.comment_id,
-            id_post_comment: post_relation.id_post_comment,
-            user_name_comment: post_relation.user_name_comment,
-            comment: post_relation.comment,
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrendingPostResponse {
+    #[serde(flatten)]
+    pub post: PostResponse,
+    pub views: i64,
+    pub score: f64,
+}
+
+impl From<TrendingPost> for TrendingPostResponse {
+    fn from(trending: TrendingPost) -> Self {
+        TrendingPostResponse {
+            post: PostResponse::from(trending.post),
+            views: trending.views,
+            score: trending.score,
         }
     }
-}
\ No newline at end of file
+}
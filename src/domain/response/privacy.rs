@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::{CommentResponse, PostResponse, UserResponse};
+
+/// The `data.json` of an account export.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountExportResponse {
+    pub exported_at: DateTime<Utc>,
+    pub profile: UserResponse,
+    /// Every post, including ones hidden by reports.
+    pub posts: Vec<PostResponse>,
+    /// Every comment, whatever its moderation status.
+    pub comments: Vec<CommentResponse>,
+    /// Uploaded images, as paths inside the ZIP archive.
+    pub files: Vec<String>,
+}
+
+/// A finished export, ready to be sent as a download.
+#[derive(Debug)]
+pub struct ExportArchive {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErasureResponse {
+    pub user_id: i32,
+    pub posts_deleted: u64,
+    pub posts_reassigned: u64,
+    pub comments_anonymized: u64,
+    pub files_removed: usize,
+}
@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::model::reaction::Reactor;
+
+/// Reaction totals keyed by kind, e.g. `{"like": 3, "love": 1}`.
+pub type ReactionCounts = BTreeMap<String, i64>;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReactionResponse {
+    pub user_id: i32,
+    pub firstname: String,
+    pub lastname: String,
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Reactor> for ReactionResponse {
+    fn from(reactor: Reactor) -> Self {
+        ReactionResponse {
+            user_id: reactor.user_id,
+            firstname: reactor.firstname,
+            lastname: reactor.lastname,
+            kind: reactor.kind,
+            created_at: reactor.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ToggleReactionResponse {
+    pub kind: String,
+    pub reacted: bool,
+    pub reactions: ReactionCounts,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::model::report::{Report, ReportEntry};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReportResponse {
+    pub id: i32,
+    pub reporter_id: i32,
+    pub target_type: String,
+    pub target_id: i32,
+    pub reason: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Open or triaged reports on the same target; only set in the inbox.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_reports: Option<i64>,
+}
+
+impl From<Report> for ReportResponse {
+    fn from(value: Report) -> Self {
+        ReportResponse {
+            id: value.id,
+            reporter_id: value.reporter_id,
+            target_type: value.target_type,
+            target_id: value.target_id,
+            reason: value.reason,
+            details: value.details,
+            status: value.status,
+            resolved_by: value.resolved_by,
+            resolved_at: value.resolved_at,
+            created_at: value.created_at,
+            open_reports: None,
+        }
+    }
+}
+
+impl From<ReportEntry> for ReportResponse {
+    fn from(value: ReportEntry) -> Self {
+        ReportResponse {
+            open_reports: Some(value.open_reports),
+            ..ReportResponse::from(value.report)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+/// Who an event is meant for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamTopic {
+    /// Everyone watching a post's comments.
+    Post(i32),
+    /// A single user's notification socket.
+    User(i32),
+    /// Every subscriber, whatever they are watching.
+    All,
+}
+
+/// An event fanned out to live subscribers. The payload is serialized once
+/// when it is published rather than once per subscriber.
+#[derive(Debug, Clone)]
+pub struct StreamEvent {
+    pub topic: StreamTopic,
+    /// `comment`, `comment_removed`, `notification` or `resync`.
+    pub event: &'static str,
+    /// JSON body of the event.
+    pub data: Arc<str>,
+    /// Who wrote the content, so subscribers can drop authors they hide.
+    pub author_id: Option<i32>,
+}
+
+impl StreamEvent {
+    /// Whether a subscriber watching `topic` should receive this event.
+    pub fn is_for(&self, topic: StreamTopic) -> bool {
+        self.topic == topic || self.topic == StreamTopic::All
+    }
+}
@@ -1,7 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::model::user::User;
+use crate::{domain::PostResponse, model::user::User};
 
 
 #[derive(Debug, Deserialize, Serialize,  Clone, ToSchema)]
@@ -10,6 +11,15 @@ pub struct UserResponse {
     pub firstname: String,
     pub lastname: String,
     pub email: String,
+    pub version: i32,
+    pub role: String,
+    pub username: String,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    pub website: Option<String>,
+    pub is_active: bool,
+    /// Login is refused until the user redeems a reset token.
+    pub password_reset_required: bool,
 }
 
 impl From<User> for UserResponse {
@@ -19,6 +29,63 @@ impl From<User> for UserResponse {
             firstname: user.firstname,
             lastname: user.lastname,
             email: user.email,
+            version: user.version,
+            role: user.role,
+            username: user.username,
+            bio: user.bio,
+            avatar: user.avatar,
+            website: user.website,
+            is_active: user.is_active,
+            password_reset_required: user.password_reset_required,
         }
     }
-}
\ No newline at end of file
+}
+
+/// The part of a user that is safe to show to other users.
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct PublicUserResponse {
+    pub id: i32,
+    pub username: String,
+    pub firstname: String,
+    pub lastname: String,
+    pub avatar: Option<String>,
+}
+
+impl From<User> for PublicUserResponse {
+    fn from(user: User) -> Self {
+        PublicUserResponse {
+            id: user.id,
+            username: user.username,
+            firstname: user.firstname,
+            lastname: user.lastname,
+            avatar: user.avatar,
+        }
+    }
+}
+
+/// Returned to the admin who forced a reset, to pass on to the user.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PasswordResetResponse {
+    pub user_id: i32,
+    pub reset_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Public profile page; never carries the email.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserProfileResponse {
+    pub id: i32,
+    pub username: String,
+    pub firstname: String,
+    pub lastname: String,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    pub website: Option<String>,
+    /// Published posts in total.
+    pub post_count: i64,
+    pub follower_count: i64,
+    /// Users this user follows; followed categories are listed separately.
+    pub following_count: i64,
+    /// The first page of published posts; the rest via `/api/users/{username}/posts`.
+    pub posts: Vec<PostResponse>,
+}
@@ -0,0 +1,231 @@
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, EraseUserRequest, ErasureResponse, FindUsersRequest,
+        PasswordResetResponse, UpdateUserRoleRequest, UserResponse,
+    },
+    middleware::jwt,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+async fn set_active(
+    data: &AppState,
+    admin_id: i64,
+    user_id: i32,
+    active: bool,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .admin_service
+        .set_user_active(admin_id as i32, user_id, active)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/users",
+    params(FindUsersRequest),
+    responses(
+        (status = 200, description = "Users matching the filters", body = ApiResponsePagination<Vec<UserResponse>>),
+        (status = 403, description = "Admin role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn get_users(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindUsersRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .admin_service
+        .find_users(user_id as i32, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/deactivate",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User deactivated and their tokens revoked", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Cannot deactivate yourself"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn deactivate_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    set_active(&data, user_id, id, false).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/reactivate",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User reactivated", body = ApiResponse<UserResponse>),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn reactivate_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    set_active(&data, user_id, id, true).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/users/{id}/password-reset",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Reset forced; the token is redeemed at /api/auth/password-reset", body = ApiResponse<PasswordResetResponse>),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn force_password_reset(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .admin_service
+        .force_password_reset(user_id as i32, id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/users/{id}/role",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    request_body = UpdateUserRoleRequest,
+    responses(
+        (status = 200, description = "Role updated", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Cannot demote yourself"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn update_user_role(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateUserRoleRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .admin_service
+        .set_user_role(user_id as i32, id, body.role)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/users/{id}",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    request_body = EraseUserRequest,
+    responses(
+        (status = 200, description = "Account erased: comments anonymized, posts deleted or reassigned, files removed", body = ApiResponse<ErasureResponse>),
+        (status = 400, description = "Cannot erase yourself, or no valid reassign target"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User or reassign target not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "admin"
+)]
+pub async fn erase_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<EraseUserRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .admin_service
+        .erase_user(user_id as i32, id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn admin_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/admin/users", get(get_users))
+        .route("/api/admin/users/{id}", delete(erase_user))
+        .route("/api/admin/users/{id}/deactivate", post(deactivate_user))
+        .route("/api/admin/users/{id}/reactivate", post(reactivate_user))
+        .route(
+            "/api/admin/users/{id}/password-reset",
+            post(force_password_reset),
+        )
+        .route("/api/admin/users/{id}/role", put(update_user_role))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state)
+}
@@ -2,11 +2,11 @@ use std::sync::Arc;
 
 
 use axum::{
-    extract::State, http::StatusCode, middleware, response::IntoResponse, routing::{get, post}, Extension, Json
+    extract::State, http::{header, HeaderMap, StatusCode}, middleware, response::IntoResponse, routing::{get, post}, Extension, Json
 };
 use serde_json::{json, Value};
 use utoipa_axum::router::OpenApiRouter;
-use crate::{domain::{ApiResponse, LoginRequest, RegisterRequest, UserResponse}, middleware::jwt, state::AppState};
+use crate::{domain::{ApiResponse, ConfirmEmailRequest, LoginRequest, RegisterRequest, ResetPasswordRequest, UserResponse}, middleware::jwt, state::AppState, utils::{etag, if_none_match}};
 
 
 
@@ -73,11 +73,65 @@ pub async fn login_user_handler(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/password-reset",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset; all existing tokens are revoked"),
+        (status = 400, description = "Invalid or expired token, or password too short")
+    ),
+    tag = "auth"
+)]
+pub async fn reset_password_handler(
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<ResetPasswordRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match data.di_container.auth_service.reset_password(&body).await {
+        Ok(response) => Ok((
+            StatusCode::OK,
+            Json(json!(response))
+        )),
+        Err(e) => Err((
+            e.code,
+            Json(json!(e))
+        ))
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/confirm-email",
+    request_body = ConfirmEmailRequest,
+    responses(
+        (status = 200, description = "Email address changed", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Invalid or expired token"),
+        (status = 409, description = "Email already exists")
+    ),
+    tag = "auth"
+)]
+pub async fn confirm_email_handler(
+    State(data): State<Arc<AppState>>,
+    Json(body): Json<ConfirmEmailRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    match data.di_container.auth_service.confirm_email_change(&body).await {
+        Ok(response) => Ok((
+            StatusCode::OK,
+            Json(json!(response))
+        )),
+        Err(e) => Err((
+            e.code,
+            Json(json!(e))
+        ))
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/users/me",
     responses(
-        (status = 200, description = "Get Me user", body = ApiResponse<UserResponse>)
+        (status = 200, description = "Get Me user", body = ApiResponse<UserResponse>),
+        (status = 304, description = "User not modified since the given ETag")
     ),
     security(
         ("bearer_auth" = [])
@@ -87,14 +141,22 @@ pub async fn login_user_handler(
 pub async fn get_me_handler(
     State(data): State<Arc<AppState>>,
     Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     
     match data.di_container.user_service.find_by_id(user_id as i32).await {
         Ok(Some(user)) => {
+            let tag = etag(user.data.version);
+
+            if if_none_match(&headers, user.data.version) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+            }
+
             Ok((
                 StatusCode::OK,
+                [(header::ETAG, tag)],
                 Json(json!(user))
-            ))
+            ).into_response())
         },
         Ok(None) => {
             Err((
@@ -120,6 +182,8 @@ pub fn auth_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/healthchecker", get(health_checker_handler))
         .route("/api/auth/register", post(register_user_handler))
         .route("/api/auth/login", post(login_user_handler))
+        .route("/api/auth/password-reset", post(reset_password_handler))
+        .route("/api/auth/confirm-email", post(confirm_email_handler))
         .route(
             "/api/users/me",
             get(get_me_handler)
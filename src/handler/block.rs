@@ -0,0 +1,174 @@
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, BlockKind, BlockResponse, BlockedUserResponse,
+        FindBlocksRequest,
+    },
+    middleware::jwt,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, put},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+async fn set_block(
+    data: &AppState,
+    user_id: i64,
+    username: &str,
+    kind: BlockKind,
+    active: bool,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .block_service
+        .set_block(user_id as i32, username, kind, active)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{username}/block",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User blocked; they can no longer comment on your posts, reply to you, mention you or follow you", body = ApiResponse<BlockResponse>),
+        (status = 400, description = "Cannot block yourself"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "blocks"
+)]
+pub async fn block_user(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    set_block(&data, user_id, &username, BlockKind::Block, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{username}/block",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User unblocked", body = ApiResponse<BlockResponse>),
+        (status = 404, description = "User not found or not blocked")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "blocks"
+)]
+pub async fn unblock_user(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    set_block(&data, user_id, &username, BlockKind::Block, false).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{username}/mute",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User muted; their posts and comments are hidden from you", body = ApiResponse<BlockResponse>),
+        (status = 400, description = "Cannot mute yourself"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "blocks"
+)]
+pub async fn mute_user(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    set_block(&data, user_id, &username, BlockKind::Mute, true).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{username}/mute",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User unmuted", body = ApiResponse<BlockResponse>),
+        (status = 404, description = "User not found or not muted")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "blocks"
+)]
+pub async fn unmute_user(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    set_block(&data, user_id, &username, BlockKind::Mute, false).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/me/blocks",
+    params(FindBlocksRequest),
+    responses(
+        (status = 200, description = "Users you blocked or muted, most recent first", body = ApiResponsePagination<Vec<BlockedUserResponse>>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "blocks"
+)]
+pub async fn get_blocks(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindBlocksRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .block_service
+        .get_blocks(user_id as i32, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn block_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route(
+            "/api/users/{username}/block",
+            put(block_user).delete(unblock_user),
+        )
+        .route(
+            "/api/users/{username}/mute",
+            put(mute_user).delete(unmute_user),
+        )
+        .route("/api/users/me/blocks", get(get_blocks))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state)
+}
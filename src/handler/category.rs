@@ -1,14 +1,16 @@
 use crate::{
     domain::{
-        ApiResponse, ApiResponsePagination, CategoryResponse, CreateCategoryRequest,
+        ApiResponse, ApiResponsePagination, CategoryMergeResponse, CategoryResponse,
+        CategoryTreeResponse, CreateCategoryRequest, DeleteCategoryRequest,
         FindAllCategoryRequest, UpdateCategoryRequest,
     },
     middleware::jwt,
     state::AppState,
+    utils::{etag, if_match_version, if_none_match},
 };
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     middleware,
     response::IntoResponse,
     routing::{delete, get, post, put},
@@ -57,6 +59,7 @@ pub async fn get_categories(
     ),
     responses(
         (status = 200, description = "Successfully retrieved category details", body = ApiResponse<CategoryResponse>),
+        (status = 304, description = "Category not modified since the given ETag"),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -64,9 +67,18 @@ pub async fn get_category(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match data.di_container.category_service.get_category(id).await {
-        Ok(Some(category)) => Ok((StatusCode::OK, Json(json!(category)))),
+        Ok(Some(category)) => {
+            let tag = etag(category.data.version);
+
+            if if_none_match(&headers, category.data.version) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(header::ETAG, tag)], Json(json!(category))).into_response())
+        }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -78,11 +90,81 @@ pub async fn get_category(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/categories/by-slug/{slug}",
+    params(
+        ("slug" = String, Path, description = "Category slug")
+    ),
+    responses(
+        (status = 200, description = "Category with the given slug", body = ApiResponse<CategoryResponse>),
+        (status = 404, description = "Category not found")
+    ),
+    tag = "category"
+)]
+pub async fn get_category_by_slug(
+    State(data): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .category_service
+        .get_category_by_slug(&slug)
+        .await
+    {
+        Ok(category) => Ok((StatusCode::OK, Json(json!(category)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/categories/tree",
+    responses(
+        (status = 200, description = "Every category nested below its parent", body = ApiResponse<Vec<CategoryTreeResponse>>)
+    ),
+    tag = "category"
+)]
+pub async fn get_category_tree(
+    State(data): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data.di_container.category_service.get_category_tree().await {
+        Ok(tree) => Ok((StatusCode::OK, Json(json!(tree)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/categories/{id}/breadcrumbs",
+    params(
+        ("id" = i32, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "The category's ancestors, root first, ending with the category itself", body = ApiResponse<Vec<CategoryResponse>>),
+        (status = 404, description = "Category not found")
+    ),
+    tag = "category"
+)]
+pub async fn get_breadcrumbs(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data.di_container.category_service.get_breadcrumbs(id).await {
+        Ok(breadcrumbs) => Ok((StatusCode::OK, Json(json!(breadcrumbs)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/categories/create",
     responses(
-        (status = 200, description = "Create category", body = ApiResponse<CategoryResponse>)
+        (status = 200, description = "Create category", body = ApiResponse<CategoryResponse>),
+        (status = 400, description = "Missing name or invalid slug"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Parent category not found"),
+        (status = 409, description = "Name or slug already in use")
     ),
     security(
         ("bearer_auth" = [])
@@ -91,16 +173,17 @@ pub async fn get_category(
 )]
 pub async fn create_category(
     State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
     Json(body): Json<CreateCategoryRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     match data
         .di_container
         .category_service
-        .create_category(&body)
+        .create_category(user_id as i32, &body)
         .await
     {
         Ok(category) => Ok((StatusCode::CREATED, Json(json!(category)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -108,10 +191,17 @@ pub async fn create_category(
     put,
     path = "/api/categories/update/{id}",
     params(
-            ("id" = i32, Path, description = "Category ID")
+            ("id" = i32, Path, description = "Category ID"),
+            ("If-Match" = String, Header, description = "ETag of the version being edited")
     ),
     responses(
-        (status = 200, description = "Delete category", body = ApiResponse<CategoryResponse>)
+        (status = 200, description = "Update category", body = ApiResponse<CategoryResponse>),
+        (status = 400, description = "The new parent is the category itself or one of its descendants"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Category or parent category not found"),
+        (status = 409, description = "Name or slug already in use"),
+        (status = 412, description = "Category was modified by someone else"),
+        (status = 428, description = "If-Match header is missing")
     ),
     security(
         ("bearer_auth" = [])
@@ -121,17 +211,24 @@ pub async fn create_category(
 pub async fn update_category(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
     Json(mut body): Json<UpdateCategoryRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     body.id = Some(id);
+    body.version = if_match_version(&headers)?;
 
     match data
         .di_container
         .category_service
-        .update_category(&body)
+        .update_category(user_id as i32, &body)
         .await
     {
-        Ok(Some(category)) => Ok((StatusCode::OK, Json(json!(category)))),
+        Ok(Some(category)) => Ok((
+            StatusCode::OK,
+            [(header::ETAG, etag(category.data.version))],
+            Json(json!(category)),
+        )),
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -139,7 +236,7 @@ pub async fn update_category(
                 "message": "Category not found"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -147,10 +244,15 @@ pub async fn update_category(
     delete,
     path = "/api/categories/delete/{id}",
     params(
-        ("id" = i32, Path, description = "Category ID")
+        ("id" = i32, Path, description = "Category ID"),
+        DeleteCategoryRequest
     ),
     responses(
-        (status = 200, description = "Delete category", body = Value)
+        (status = 200, description = "Delete category; with `reassign_to`, reports the moved posts", body = ApiResponse<CategoryMergeResponse>),
+        (status = 400, description = "Category would be reassigned to itself"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Category or reassignment target not found"),
+        (status = 409, description = "Category still has posts and no `reassign_to` was given")
     ),
     security(
         ("bearer_auth" = [])
@@ -160,17 +262,61 @@ pub async fn update_category(
 pub async fn delete_category(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
+    Query(params): Query<DeleteCategoryRequest>,
+    Extension(user_id): Extension<i64>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.category_service.delete_category(id).await {
-        Ok(_) => Ok((
-            StatusCode::OK,
-            Json(json!({
+    let service = &data.di_container.category_service;
+
+    let result = match params.reassign_to {
+        Some(target_id) => service
+            .reassign_and_delete_category(user_id as i32, id, target_id)
+            .await
+            .map(|response| json!(response)),
+        None => service.delete_category(user_id as i32, id).await.map(|_| {
+            json!({
                 "status": "success",
                 "message": "Category deleted successfully"
-            })),
-        )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+            })
+        }),
+    };
+
+    match result {
+        Ok(response) => Ok((StatusCode::OK, Json(response))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/categories/{id}/merge-into/{target}",
+    params(
+        ("id" = i32, Path, description = "Category to merge and remove"),
+        ("target" = i32, Path, description = "Category receiving its posts and subcategories")
+    ),
+    responses(
+        (status = 200, description = "Category merged", body = ApiResponse<CategoryMergeResponse>),
+        (status = 400, description = "Target is the category itself or one of its descendants"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Category or target not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "category"
+)]
+pub async fn merge_category(
+    State(data): State<Arc<AppState>>,
+    Path((id, target)): Path<(i32, i32)>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .category_service
+        .merge_category(user_id as i32, id, target)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -180,10 +326,15 @@ pub fn category_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/categories/create", post(create_category))
         .route("/api/categories/update/{id}", put(update_category))
         .route("/api/categories/delete/{id}", delete(delete_category))
+        .route("/api/categories/{id}/merge-into/{target}", post(merge_category))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone());
 
-    let public_routes = OpenApiRouter::new().route("/api/categories", get(get_categories));
+    let public_routes = OpenApiRouter::new()
+        .route("/api/categories", get(get_categories))
+        .route("/api/categories/by-slug/{slug}", get(get_category_by_slug))
+        .route("/api/categories/tree", get(get_category_tree))
+        .route("/api/categories/{id}/breadcrumbs", get(get_breadcrumbs));
 
     OpenApiRouter::new()
         .merge(protected_routes)
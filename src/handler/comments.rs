@@ -1,25 +1,45 @@
 use axum::{
-    extract::{State, Path},
-    http::StatusCode,
+    extract::{Extension, State, Path, Query},
+    http::{header, HeaderMap, StatusCode},
     middleware,
-    response::IntoResponse,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
     routing::{get, post, put, delete},
     Json
 };
+use axum_extra::extract::cookie::CookieJar;
 use serde_json::json;
+use tokio::{
+    sync::{broadcast::error::RecvError, mpsc},
+    time::{interval_at, Instant},
+};
+use tokio_stream::wrappers::ReceiverStream;
 use utoipa_axum::router::OpenApiRouter;
-use std::sync::Arc;
+use std::{collections::HashSet, convert::Infallible, sync::Arc};
 use crate::{
-    middleware::jwt,
-    domain::{ApiResponse, CommentResponse, CreateCommentRequest, UpdateCommentRequest},
+    handler::posts::ensure_post_readable,
+    middleware::jwt::{self, SessionToken, SESSION_CHECK_INTERVAL},
+    domain::{
+        ApiResponse, ApiResponsePagination, CommentResponse, CommentTreeResponse,
+        CreateCommentRequest, FindCommentTreeRequest, FindCommentsRequest, StreamTopic,
+        UpdateCommentRequest,
+    },
     state::AppState,
+    utils::{etag, if_match_version, if_none_match},
 };
 
+/// Events queued for a slow SSE client before the stream waits on it.
+const SSE_BUFFER: usize = 16;
+
 #[utoipa::path(
     get,
     path = "/api/comments",
+    params(FindCommentsRequest),
     responses(
-        (status = 200, description = "Get all comments", body = ApiResponse<Vec<CommentResponse>>)
+        (status = 200, description = "Get comments, optionally filtered by post or author", body = ApiResponsePagination<Vec<CommentResponse>>),
+        (status = 400, description = "Invalid cursor")
     ),
     security(
         ("bearer_auth" = [])
@@ -28,11 +48,15 @@ use crate::{
 )]
 pub async fn get_comments(
     State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(mut params): Query<FindCommentsRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.comment_service.get_comments().await {
+    params.viewer_id = Some(user_id as i32);
+
+    match data.di_container.comment_service.get_comments(params).await {
         Ok(comments) => Ok((StatusCode::OK, Json(json!(comments)))),
         Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            e.code,
             Json(json!({
                 "status": "error",
                 "message": "Failed to fetch comments",
@@ -47,6 +71,7 @@ pub async fn get_comments(
     path = "/api/comments/{id}",
     responses(
         (status = 200, description = "Get a comment", body = ApiResponse<CommentResponse>),
+        (status = 304, description = "Comment not modified since the given ETag"),
         (status = 404, description = "Comment not found")
     ),
     params(
@@ -57,9 +82,27 @@ pub async fn get_comments(
 pub async fn get_comment(
     State(data): State<Arc<AppState>>,
     Path(comment_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    cookie_jar: CookieJar,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.comment_service.get_comment(comment_id).await {
-        Ok(Some(comment)) => Ok((StatusCode::OK, Json(json!(comment)))),
+    match data
+        .di_container
+        .comment_service
+        .get_comment(user_id as i32, comment_id)
+        .await
+    {
+        Ok(Some(comment)) => {
+            ensure_post_readable(&data, comment.data.id_post_comment, user_id, &cookie_jar).await?;
+
+            let tag = etag(comment.data.version);
+
+            if if_none_match(&headers, comment.data.version) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(header::ETAG, tag)], Json(json!(comment))).into_response())
+        }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -67,14 +110,7 @@ pub async fn get_comment(
                 "message": "Comment not found"
             })),
         )),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "status": "error",
-                "message": "Failed to fetch comment",
-                "error": format!("{:?}", e)
-            })),
-        )),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -84,18 +120,22 @@ pub async fn get_comment(
     request_body = CreateCommentRequest,
     responses(
         (status = 201, description = "Comment created", body = ApiResponse<CommentResponse>),
-        (status = 400, description = "Invalid request body")
+        (status = 400, description = "Invalid request body or reply nested too deep"),
+        (status = 404, description = "Parent comment not found")
     ),
     tag = "comments"
 )]
 pub async fn create_comment(
     State(data): State<Arc<AppState>>,
-    Json(body): Json<CreateCommentRequest>,
+    Extension(user_id): Extension<i64>,
+    Json(mut body): Json<CreateCommentRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    body.user_id = user_id as i32;
+
     match data.di_container.comment_service.create_comment(&body).await {
         Ok(comment) => Ok((StatusCode::CREATED, Json(json!(comment)))),
         Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            e.code,
             Json(json!({
                 "status": "error",
                 "message": "Failed to create comment",
@@ -111,19 +151,33 @@ pub async fn create_comment(
     request_body = UpdateCommentRequest,
     responses(
         (status = 200, description = "Comment updated", body = ApiResponse<CommentResponse>),
-        (status = 404, description = "Comment not found")
+        (status = 403, description = "Caller is neither the author nor a moderator"),
+        (status = 404, description = "Comment not found"),
+        (status = 412, description = "Comment was modified by someone else"),
+        (status = 428, description = "If-Match header is missing")
     ),
     params(
-        ("id" = i32, Path, description = "Comment ID")
+        ("id" = i32, Path, description = "Comment ID"),
+        ("If-Match" = String, Header, description = "ETag of the version being edited")
     ),
     tag = "comments"
 )]
 pub async fn update_comment(
     State(data): State<Arc<AppState>>,
-    Json(body): Json<UpdateCommentRequest>,
+    Path(comment_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
+    Json(mut body): Json<UpdateCommentRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.comment_service.update_comment(&body).await {
-        Ok(Some(comment)) => Ok((StatusCode::OK, Json(json!(comment)))),
+    body.id = Some(comment_id);
+    body.version = if_match_version(&headers)?;
+
+    match data.di_container.comment_service.update_comment(user_id as i32, &body).await {
+        Ok(Some(comment)) => Ok((
+            StatusCode::OK,
+            [(header::ETAG, etag(comment.data.version))],
+            Json(json!(comment)),
+        )),
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -132,7 +186,7 @@ pub async fn update_comment(
             })),
         )),
         Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            e.code,
             Json(json!({
                 "status": "error",
                 "message": "Failed to update comment",
@@ -146,8 +200,9 @@ pub async fn update_comment(
     delete,
     path = "/api/comments/{id}",
     responses(
-        (status = 200, description = "Comment deleted successfully", body=Value),
-        (status = 500, description = "Failed to delete comment")
+        (status = 200, description = "Comment deleted, or tombstoned if it has replies", body=Value),
+        (status = 403, description = "Caller is neither the author nor a moderator"),
+        (status = 404, description = "Comment not found")
     ),
     params(
         ("id" = i32, Path, description = "Comment ID")
@@ -157,8 +212,9 @@ pub async fn update_comment(
 pub async fn delete_comment(
     State(data): State<Arc<AppState>>,
     Path(comment_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.comment_service.delete_comment(comment_id).await {
+    match data.di_container.comment_service.delete_comment(user_id as i32, comment_id).await {
         Ok(_) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -167,7 +223,7 @@ pub async fn delete_comment(
             })),
         )),
         Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            e.code,
             Json(json!({
                 "status": "error",
                 "message": "Failed to delete comment",
@@ -177,6 +233,221 @@ pub async fn delete_comment(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        FindCommentsRequest
+    ),
+    responses(
+        (status = 200, description = "Comments of a post", body = ApiResponsePagination<Vec<CommentResponse>>),
+        (status = 400, description = "Invalid cursor")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "comments"
+)]
+pub async fn get_post_comments(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    cookie_jar: CookieJar,
+    Query(mut params): Query<FindCommentsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_post_readable(&data, post_id, user_id, &cookie_jar).await?;
+
+    params.viewer_id = Some(user_id as i32);
+    params.post_unlocked = true;
+
+    match data
+        .di_container
+        .comment_service
+        .get_post_comments(post_id, params)
+        .await
+    {
+        Ok(comments) => Ok((StatusCode::OK, Json(json!(comments)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments/stream",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Server-sent events: `comment` carries a new or changed comment, `comment_removed` its id once it is no longer public, and `resync` tells a client that fell behind to reload the list. The stream ends once the token is revoked", content_type = "text/event-stream"),
+        (status = 403, description = "Post is password protected and was not unlocked"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "comments"
+)]
+pub async fn stream_post_comments(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Extension(token): Extension<SessionToken>,
+    cookie_jar: CookieJar,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_post_readable(&data, post_id, user_id, &cookie_jar).await?;
+
+    // Taken once; blocks added while connected apply from the next connect.
+    let hidden: HashSet<i32> = match data
+        .di_container
+        .block_service
+        .find_hidden(user_id as i32)
+        .await
+    {
+        Ok(hidden) => hidden.into_iter().collect(),
+        Err(e) => return Err((e.code, Json(json!(e)))),
+    };
+
+    let topic = StreamTopic::Post(post_id);
+
+    let mut events = data.di_container.stream_service.subscribe();
+    let auth_service = data.di_container.auth_service.clone();
+    let (sender, receiver) = mpsc::channel::<Result<Event, Infallible>>(SSE_BUFFER);
+
+    // Runs until the client goes away or its token is revoked; dropping the
+    // sender ends the response.
+    tokio::spawn(async move {
+        let mut session_check =
+            interval_at(Instant::now() + SESSION_CHECK_INTERVAL, SESSION_CHECK_INTERVAL);
+
+        loop {
+            let event = tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event)
+                        if event.is_for(topic)
+                            && !event.author_id.is_some_and(|id| hidden.contains(&id)) =>
+                    {
+                        Event::default().event(event.event).data(&*event.data)
+                    }
+                    Ok(_) => continue,
+                    // Events were dropped while this client was slow to read.
+                    Err(RecvError::Lagged(missed)) => Event::default()
+                        .event("resync")
+                        .data(json!({ "missed": missed }).to_string()),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = session_check.tick() => {
+                    if auth_service.authenticate(&token.0).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                _ = sender.closed() => break,
+            };
+
+            if sender.send(Ok(event)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(Sse::new(ReceiverStream::new(receiver)).keep_alive(KeepAlive::default()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments/tree",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        FindCommentTreeRequest
+    ),
+    responses(
+        (status = 200, description = "Threaded comments of a post", body = ApiResponsePagination<Vec<CommentTreeResponse>>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "comments"
+)]
+pub async fn get_comment_tree(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    cookie_jar: CookieJar,
+    Query(mut params): Query<FindCommentTreeRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_post_readable(&data, post_id, user_id, &cookie_jar).await?;
+
+    params.viewer_id = Some(user_id as i32);
+
+    match data
+        .di_container
+        .comment_service
+        .get_comment_tree(post_id, params)
+        .await
+    {
+        Ok(comments) => Ok((StatusCode::OK, Json(json!(comments)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/comments/{id}/replies",
+    params(
+        ("id" = i32, Path, description = "Comment ID"),
+        FindCommentTreeRequest
+    ),
+    responses(
+        (status = 200, description = "Replies below a comment", body = ApiResponsePagination<Vec<CommentTreeResponse>>),
+        (status = 404, description = "Comment not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "comments"
+)]
+pub async fn get_comment_replies(
+    State(data): State<Arc<AppState>>,
+    Path(comment_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    cookie_jar: CookieJar,
+    Query(mut params): Query<FindCommentTreeRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let post_id = match data
+        .di_container
+        .comment_service
+        .get_comment(user_id as i32, comment_id)
+        .await
+    {
+        Ok(Some(comment)) => comment.data.id_post_comment,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "status": "fail",
+                    "message": "Comment not found"
+                })),
+            ))
+        }
+        Err(e) => return Err((e.code, Json(json!(e)))),
+    };
+
+    ensure_post_readable(&data, post_id, user_id, &cookie_jar).await?;
+
+    params.viewer_id = Some(user_id as i32);
+
+    match data
+        .di_container
+        .comment_service
+        .get_replies(comment_id, params)
+        .await
+    {
+        Ok(comments) => Ok((StatusCode::OK, Json(json!(comments)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
 pub fn comment_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     let protected_routes = OpenApiRouter::new()
         .route("/api/comments", get(get_comments))
@@ -184,6 +455,10 @@ pub fn comment_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/comments", post(create_comment))
         .route("/api/comments/{id}", put(update_comment))
         .route("/api/comments/{id}", delete(delete_comment))
+        .route("/api/comments/{id}/replies", get(get_comment_replies))
+        .route("/api/posts/{id}/comments", get(get_post_comments))
+        .route("/api/posts/{id}/comments/stream", get(stream_post_comments))
+        .route("/api/posts/{id}/comments/tree", get(get_comment_tree))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone());
 
@@ -0,0 +1,289 @@
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CategoryResponse, FindFollowsRequest, FollowResponse,
+        PublicUserResponse,
+    },
+    middleware::jwt,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, put},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{username}/follow",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User followed", body = ApiResponse<FollowResponse>),
+        (status = 400, description = "Cannot follow yourself"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "follows"
+)]
+pub async fn follow_user(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .follow_user(user_id as i32, &username)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{username}/follow",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "User unfollowed", body = ApiResponse<FollowResponse>),
+        (status = 404, description = "User not found or not followed")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "follows"
+)]
+pub async fn unfollow_user(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .unfollow_user(user_id as i32, &username)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/categories/{id}/follow",
+    params(
+        ("id" = i32, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Category followed", body = ApiResponse<FollowResponse>),
+        (status = 404, description = "Category not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "follows"
+)]
+pub async fn follow_category(
+    State(data): State<Arc<AppState>>,
+    Path(category_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .follow_category(user_id as i32, category_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/categories/{id}/follow",
+    params(
+        ("id" = i32, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Category unfollowed", body = ApiResponse<FollowResponse>),
+        (status = 404, description = "Category is not followed")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "follows"
+)]
+pub async fn unfollow_category(
+    State(data): State<Arc<AppState>>,
+    Path(category_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .unfollow_category(user_id as i32, category_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{username}/followers",
+    params(
+        ("username" = String, Path, description = "Username"),
+        FindFollowsRequest
+    ),
+    responses(
+        (status = 200, description = "Users following this user, newest first", body = ApiResponsePagination<Vec<PublicUserResponse>>),
+        (status = 404, description = "User not found")
+    ),
+    tag = "follows"
+)]
+pub async fn get_user_followers(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Query(params): Query<FindFollowsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .get_user_followers(&username, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{username}/following",
+    params(
+        ("username" = String, Path, description = "Username"),
+        FindFollowsRequest
+    ),
+    responses(
+        (status = 200, description = "Users this user follows, newest first", body = ApiResponsePagination<Vec<PublicUserResponse>>),
+        (status = 404, description = "User not found")
+    ),
+    tag = "follows"
+)]
+pub async fn get_user_following(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Query(params): Query<FindFollowsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .get_user_following(&username, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{username}/following/categories",
+    params(
+        ("username" = String, Path, description = "Username"),
+        FindFollowsRequest
+    ),
+    responses(
+        (status = 200, description = "Categories this user follows, newest first", body = ApiResponsePagination<Vec<CategoryResponse>>),
+        (status = 404, description = "User not found")
+    ),
+    tag = "follows"
+)]
+pub async fn get_followed_categories(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    Query(params): Query<FindFollowsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .get_followed_categories(&username, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/categories/{id}/followers",
+    params(
+        ("id" = i32, Path, description = "Category ID"),
+        FindFollowsRequest
+    ),
+    responses(
+        (status = 200, description = "Users following this category, newest first", body = ApiResponsePagination<Vec<PublicUserResponse>>),
+        (status = 404, description = "Category not found")
+    ),
+    tag = "follows"
+)]
+pub async fn get_category_followers(
+    State(data): State<Arc<AppState>>,
+    Path(category_id): Path<i32>,
+    Query(params): Query<FindFollowsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .follow_service
+        .get_category_followers(category_id, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn follow_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let protected_routes = OpenApiRouter::new()
+        .route(
+            "/api/users/{username}/follow",
+            put(follow_user).delete(unfollow_user),
+        )
+        .route(
+            "/api/categories/{id}/follow",
+            put(follow_category).delete(unfollow_category),
+        )
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone());
+
+    let public_routes = OpenApiRouter::new()
+        .route("/api/users/{username}/followers", get(get_user_followers))
+        .route("/api/users/{username}/following", get(get_user_following))
+        .route(
+            "/api/users/{username}/following/categories",
+            get(get_followed_categories),
+        )
+        .route("/api/categories/{id}/followers", get(get_category_followers))
+        .with_state(app_state.clone());
+
+    OpenApiRouter::new()
+        .merge(protected_routes)
+        .merge(public_routes)
+        .with_state(app_state)
+}
@@ -1,13 +1,23 @@
+mod admin;
 mod auth;
+mod block;
 mod category;
 mod comments;
+mod follow;
+mod moderation;
+mod notification;
 mod posts;
+mod reaction;
+mod report;
 mod user;
 
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::extract::DefaultBodyLimit;
 use tokio::net::TcpListener;
+use tokio::sync::oneshot;
 use tower_http::limit::RequestBodyLimitLayer;
 use utoipa::openapi::security::SecurityScheme;
 use utoipa::{Modify, OpenApi};
@@ -16,10 +26,45 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::state::AppState;
 
+/// How long open connections (SSE streams in particular) may keep the
+/// server alive after a shutdown signal.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+pub use self::admin::admin_routes;
 pub use self::auth::auth_routes;
+pub use self::block::block_routes;
 pub use self::category::category_routes;
 pub use self::comments::comment_routes;
+pub use self::follow::follow_routes;
+pub use self::moderation::moderation_routes;
+pub use self::notification::notification_routes;
 pub use self::posts::post_routes;
+pub use self::reaction::reaction_routes;
+pub use self::report::report_routes;
 pub use self::user::user_routes;
 
 #[derive(OpenApi)]
@@ -28,33 +73,102 @@ pub use self::user::user_routes;
         auth::login_user_handler,
         auth::get_me_handler,
         auth::register_user_handler,
+        auth::reset_password_handler,
+        auth::confirm_email_handler,
+        admin::get_users,
+        admin::deactivate_user,
+        admin::reactivate_user,
+        admin::force_password_reset,
+        admin::update_user_role,
+        admin::erase_user,
         user::create_user,
         user::find_user_by_email,
         user::update_user,
         user::delete_user,
+        user::update_profile,
+        user::change_password,
+        user::change_email,
+        user::export_account,
+        user::delete_account,
+        user::upload_avatar,
+        user::delete_avatar,
+        user::get_profile,
+        user::get_user_posts,
         category::get_categories,
         category::get_category,
+        category::get_category_by_slug,
+        category::get_category_tree,
+        category::get_breadcrumbs,
         category::create_category,
         category::update_category,
         category::delete_category,
+        category::merge_category,
         comments::get_comments,
         comments::get_comment,
         comments::create_comment,
         comments::update_comment,
         comments::delete_comment,
+        comments::get_post_comments,
+        comments::stream_post_comments,
+        comments::get_comment_tree,
+        comments::get_comment_replies,
+        follow::follow_user,
+        follow::unfollow_user,
+        follow::follow_category,
+        follow::unfollow_category,
+        follow::get_user_followers,
+        follow::get_user_following,
+        follow::get_followed_categories,
+        follow::get_category_followers,
+        block::block_user,
+        block::unblock_user,
+        block::mute_user,
+        block::unmute_user,
+        block::get_blocks,
+        moderation::get_moderation_queue,
+        moderation::update_comment_status,
+        moderation::set_category_comment_policy,
+        moderation::set_post_comment_policy,
+        notification::get_notifications,
+        notification::get_unread_count,
+        notification::mark_notification_read,
+        notification::mark_all_notifications_read,
+        notification::get_notification_preferences,
+        notification::update_notification_preferences,
+        notification::notification_socket,
         posts::get_posts,
         posts::get_post,
-        posts::get_post_relation,
+        posts::get_trending_posts,
+        posts::get_post_detail,
+        posts::unlock_post,
         posts::create_post,
         posts::update_post,
         posts::delete_post,
+        posts::add_bookmark,
+        posts::remove_bookmark,
+        posts::get_my_bookmarks,
+        posts::get_feed,
+        reaction::toggle_post_reaction,
+        reaction::get_post_reactions,
+        reaction::toggle_comment_reaction,
+        reaction::get_comment_reactions,
+        report::create_report,
+        report::get_report_inbox,
+        report::update_report,
     ),
     modifiers(&SecurityAddon),
     tags(
         (name = "auth", description = "Authentication endpoints."),
+        (name = "admin", description = "User administration; admin role required."),
+        (name = "blocks", description = "Blocking and muting other users."),
         (name = "category", description = "Category management endpoints."),
         (name = "posts", description = "Post management endpoints."),
         (name = "comments", description = "Comments management endpoints."),
+        (name = "follows", description = "Following users and categories."),
+        (name = "reactions", description = "Reactions on posts and comments."),
+        (name = "moderation", description = "Comment moderation endpoints."),
+        (name = "notifications", description = "In-app notifications for the current user."),
+        (name = "reports", description = "Content reports and the moderator inbox."),
         (name = "users", description = "User management endpoints.")
     )
 )]
@@ -82,10 +196,17 @@ impl AppRouter {
         let shared_state = Arc::new(app_state);
 
         let (router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
+            .merge(admin_routes(shared_state.clone()))
             .merge(auth_routes(shared_state.clone()))
+            .merge(block_routes(shared_state.clone()))
             .merge(category_routes(shared_state.clone()))
             .merge(comment_routes(shared_state.clone()))
+            .merge(follow_routes(shared_state.clone()))
+            .merge(moderation_routes(shared_state.clone()))
+            .merge(notification_routes(shared_state.clone()))
             .merge(post_routes(shared_state.clone()))
+            .merge(reaction_routes(shared_state.clone()))
+            .merge(report_routes(shared_state.clone()))
             .merge(user_routes(shared_state.clone()))
             .layer(DefaultBodyLimit::disable())
             .layer(RequestBodyLimitLayer::new(250 * 1024 * 1024))
@@ -98,7 +219,33 @@ impl AppRouter {
         let listener = TcpListener::bind(addr).await?;
         println!("Server running on http://{}", listener.local_addr()?);
 
-        axum::serve(listener, router).await.unwrap();
+        let (stop_tx, stop_rx) = oneshot::channel::<()>();
+        let mut server = tokio::spawn(async move {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+                .with_graceful_shutdown(async {
+                    let _ = stop_rx.await;
+                })
+                .await
+        });
+
+        tokio::select! {
+            result = &mut server => result??,
+            _ = shutdown_signal() => {
+                println!("Shutting down");
+                let _ = stop_tx.send(());
+
+                if tokio::time::timeout(SHUTDOWN_GRACE, server).await.is_err() {
+                    println!("Dropping connections still open after {:?}", SHUTDOWN_GRACE);
+                }
+            }
+        }
+
+        // Views are buffered between flushes; write out what is left.
+        shared_state.di_container.post_view_service.flush().await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,166 @@
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CommentResponse, CommentStatusUpdateResponse,
+        FindModerationQueueRequest, UpdateCommentPolicyRequest, UpdateCommentStatusRequest,
+    },
+    middleware::jwt,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post, put},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    get,
+    path = "/api/moderation/comments",
+    params(FindModerationQueueRequest),
+    responses(
+        (status = 200, description = "Comments waiting for (or given) a moderation decision", body = ApiResponsePagination<Vec<CommentResponse>>),
+        (status = 403, description = "Moderator role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "moderation"
+)]
+pub async fn get_moderation_queue(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindModerationQueueRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .moderation_service
+        .get_queue(user_id as i32, params)
+        .await
+    {
+        Ok(comments) => Ok((StatusCode::OK, Json(json!(comments)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/moderation/comments/status",
+    request_body = UpdateCommentStatusRequest,
+    responses(
+        (status = 200, description = "Status applied to the given comments", body = ApiResponse<CommentStatusUpdateResponse>),
+        (status = 400, description = "No ids or too many ids"),
+        (status = 403, description = "Moderator role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "moderation"
+)]
+pub async fn update_comment_status(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateCommentStatusRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .moderation_service
+        .update_comment_status(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/moderation/categories/{id}/comment-policy",
+    params(
+        ("id" = i32, Path, description = "Category ID")
+    ),
+    request_body = UpdateCommentPolicyRequest,
+    responses(
+        (status = 200, description = "Comment policy updated"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Category not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "moderation"
+)]
+pub async fn set_category_comment_policy(
+    State(data): State<Arc<AppState>>,
+    Path(category_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateCommentPolicyRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .moderation_service
+        .set_category_policy(user_id as i32, category_id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/moderation/posts/{id}/comment-policy",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    request_body = UpdateCommentPolicyRequest,
+    responses(
+        (status = 200, description = "Comment policy updated"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "moderation"
+)]
+pub async fn set_post_comment_policy(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateCommentPolicyRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .moderation_service
+        .set_post_policy(user_id as i32, post_id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn moderation_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let protected_routes = OpenApiRouter::new()
+        .route("/api/moderation/comments", get(get_moderation_queue))
+        .route("/api/moderation/comments/status", post(update_comment_status))
+        .route(
+            "/api/moderation/categories/{id}/comment-policy",
+            put(set_category_comment_policy),
+        )
+        .route(
+            "/api/moderation/posts/{id}/comment-policy",
+            put(set_post_comment_policy),
+        )
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone());
+
+    OpenApiRouter::new()
+        .merge(protected_routes)
+        .with_state(app_state)
+}
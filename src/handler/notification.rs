@@ -0,0 +1,290 @@
+use crate::{
+    abstract_trait::DynAuthService,
+    domain::{
+        ApiResponse, ApiResponsePagination, FindNotificationsRequest,
+        NotificationPreferencesResponse, NotificationReadResponse, NotificationResponse,
+        StreamEvent, StreamTopic, UnreadCountResponse, UpdateNotificationPreferencesRequest,
+    },
+    middleware::jwt::{self, SessionToken, SESSION_CHECK_INTERVAL},
+    state::AppState,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Extension, Path, Query, State,
+    },
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+    Json,
+};
+use serde_json::json;
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::broadcast::{error::RecvError, Receiver},
+    time::{interval_at, Instant},
+};
+use utoipa_axum::router::OpenApiRouter;
+
+/// A client that cannot take a message within this long is disconnected.
+const SOCKET_SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    params(FindNotificationsRequest),
+    responses(
+        (status = 200, description = "The current user's notifications, newest first", body = ApiResponsePagination<Vec<NotificationResponse>>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn get_notifications(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindNotificationsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .notification_service
+        .get_notifications(user_id as i32, params)
+        .await
+    {
+        Ok(notifications) => Ok((StatusCode::OK, Json(json!(notifications)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/unread-count",
+    responses(
+        (status = 200, description = "Number of unread notifications", body = ApiResponse<UnreadCountResponse>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn get_unread_count(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .notification_service
+        .get_unread_count(user_id as i32)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{id}/read",
+    params(
+        ("id" = i32, Path, description = "Notification ID")
+    ),
+    responses(
+        (status = 200, description = "Notification marked as read"),
+        (status = 404, description = "Notification not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn mark_notification_read(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .notification_service
+        .mark_read(user_id as i32, id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read-all",
+    responses(
+        (status = 200, description = "All notifications marked as read", body = ApiResponse<NotificationReadResponse>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn mark_all_notifications_read(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .notification_service
+        .mark_all_read(user_id as i32)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/preferences",
+    responses(
+        (status = 200, description = "Which events notify the current user", body = ApiResponse<NotificationPreferencesResponse>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn get_notification_preferences(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .notification_service
+        .get_preferences(user_id as i32)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/notifications/preferences",
+    request_body = UpdateNotificationPreferencesRequest,
+    responses(
+        (status = 200, description = "Preferences saved", body = ApiResponse<NotificationPreferencesResponse>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn update_notification_preferences(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateNotificationPreferencesRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .notification_service
+        .update_preferences(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/ws",
+    responses(
+        (status = 101, description = "WebSocket pushing `{\"type\": \"notification\", \"data\": ...}` for each new notification, or `{\"type\": \"resync\"}` when the client fell behind and should reload its inbox. Closed once the token is revoked")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "notifications"
+)]
+pub async fn notification_socket(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Extension(token): Extension<SessionToken>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let events = data.di_container.stream_service.subscribe();
+    let auth_service = data.di_container.auth_service.clone();
+
+    ws.on_upgrade(move |socket| {
+        push_notifications(socket, events, user_id as i32, auth_service, token)
+    })
+}
+
+async fn push_notifications(
+    mut socket: WebSocket,
+    mut events: Receiver<StreamEvent>,
+    user_id: i32,
+    auth_service: DynAuthService,
+    token: SessionToken,
+) {
+    let topic = StreamTopic::User(user_id);
+    let mut session_check = interval_at(Instant::now() + SESSION_CHECK_INTERVAL, SESSION_CHECK_INTERVAL);
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let message = match event {
+                    Ok(event) if event.is_for(topic) => {
+                        format!(r#"{{"type":"{}","data":{}}}"#, event.event, event.data)
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(missed)) => {
+                        json!({ "type": "resync", "missed": missed }).to_string()
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                let sent = tokio::time::timeout(
+                    SOCKET_SEND_TIMEOUT,
+                    socket.send(Message::Text(message.into())),
+                )
+                .await;
+
+                if !matches!(sent, Ok(Ok(()))) {
+                    break;
+                }
+            }
+            _ = session_check.tick() => {
+                // Logging out everywhere or deactivation ends the socket too.
+                if auth_service.authenticate(&token.0).await.is_err() {
+                    let _ = socket.send(Message::Close(None)).await;
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                // Pings are answered by the socket; other client messages are ignored.
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+pub fn notification_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let protected_routes = OpenApiRouter::new()
+        .route("/api/notifications", get(get_notifications))
+        .route("/api/notifications/unread-count", get(get_unread_count))
+        .route("/api/notifications/{id}/read", post(mark_notification_read))
+        .route("/api/notifications/read-all", post(mark_all_notifications_read))
+        .route("/api/notifications/ws", get(notification_socket))
+        .route(
+            "/api/notifications/preferences",
+            get(get_notification_preferences).put(update_notification_preferences),
+        )
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone());
+
+    OpenApiRouter::new()
+        .merge(protected_routes)
+        .with_state(app_state)
+}
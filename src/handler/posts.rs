@@ -1,23 +1,64 @@
 use crate::{
+    config::POST_UNLOCK_TTL_MINUTES,
     domain::{
-        ApiResponse, ApiResponsePagination, CreatePostRequest, FindAllPostRequest,
-        PostRelationResponse, PostResponse, UpdatePostRequest,
+        ApiResponse, ApiResponsePagination, CreatePostRequest, FindAllPostRequest, FindFeedRequest,
+        FindPostDetailRequest, FindTrendingPostRequest, PostDetailResponse,
+        PostResponse, PostVisibility, TrendingPostResponse, UnlockPostRequest, UpdatePostRequest,
     },
     middleware::jwt,
     state::AppState,
+    utils::{etag, if_match_version, if_none_match},
 };
 use axum::{
-    extract::{Multipart, Path, Query, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Extension, Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     middleware,
     response::IntoResponse,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Json,
 };
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
 use serde_json::json;
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use utoipa_axum::router::OpenApiRouter;
 
+/// Unlock cookies are scoped to the post, so each post has its own.
+fn unlock_cookie_name(post_id: i32) -> String {
+    format!("post_unlock_{}", post_id)
+}
+
+/// The unlock token the reader holds for `post_id`, if any.
+pub(crate) fn unlock_token(cookie_jar: &CookieJar, post_id: i32) -> Option<&str> {
+    cookie_jar
+        .get(&unlock_cookie_name(post_id))
+        .map(|cookie| cookie.value())
+}
+
+/// Comments and reports are only as visible as their post: fails unless the
+/// caller may read `post_id`, unlocking it with their cookie when it is
+/// protected.
+pub(crate) async fn ensure_post_readable(
+    data: &AppState,
+    post_id: i32,
+    user_id: i64,
+    cookie_jar: &CookieJar,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    data.di_container
+        .post_service
+        .ensure_readable(post_id, Some(user_id as i32), unlock_token(cookie_jar, post_id))
+        .await
+        .map_err(|e| (e.code, Json(json!(e))))
+}
+
+/// Key a view is deduplicated by: the user when signed in, otherwise the
+/// client address.
+fn visitor_key(viewer_id: Option<i32>, client: SocketAddr) -> String {
+    match viewer_id {
+        Some(viewer_id) => format!("user:{}", viewer_id),
+        None => format!("ip:{}", client.ip()),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/posts",
@@ -30,9 +71,17 @@ use utoipa_axum::router::OpenApiRouter;
 )]
 pub async fn get_posts(
     State(data): State<Arc<AppState>>,
+    user_id: Option<Extension<i64>>,
     Query(params): Query<FindAllPostRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.post_service.get_all_posts(params).await {
+    let viewer_id = user_id.map(|Extension(user_id)| user_id as i32);
+
+    match data
+        .di_container
+        .post_service
+        .get_all_posts(params, viewer_id)
+        .await
+    {
         Ok(posts) => Ok((StatusCode::OK, Json(json!(posts)))),
         Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
     }
@@ -46,16 +95,44 @@ pub async fn get_posts(
     ),
     responses(
         (status = 200, description = "Get post by ID", body = ApiResponse<PostResponse>),
-        (status = 404, description = "Post not found")
+        (status = 304, description = "Post not modified since the given ETag"),
+        (status = 403, description = "Post is password protected and was not unlocked"),
+        (status = 404, description = "Post not found or not visible to the caller")
+    ),
+    security(
+        ("bearer_auth" = [])
     ),
     tag = "posts"
 )]
 pub async fn get_post(
     State(data): State<Arc<AppState>>,
     Path(post_id): Path<i32>,
+    user_id: Option<Extension<i64>>,
+    cookie_jar: CookieJar,
+    headers: HeaderMap,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.post_service.get_post(post_id).await {
-        Ok(Some(post)) => Ok((StatusCode::OK, Json(json!(post)))),
+    let viewer_id = user_id.map(|Extension(user_id)| user_id as i32);
+
+    match data
+        .di_container
+        .post_service
+        .get_post(post_id, viewer_id, unlock_token(&cookie_jar, post_id))
+        .await
+    {
+        Ok(Some(post)) => {
+            data.di_container
+                .post_view_service
+                .record_view(post_id, &visitor_key(viewer_id, client));
+
+            let tag = etag(post.data.version);
+
+            if if_none_match(&headers, post.data.version) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(header::ETAG, tag)], Json(json!(post))).into_response())
+        }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -63,151 +140,295 @@ pub async fn get_post(
                 "message": "Post not found"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/posts/{id}/relation",
+    post,
+    path = "/api/posts/{id}/unlock",
     params(
-            ("id" = i32, Path, description = "Post ID")
+        ("id" = i32, Path, description = "Post ID")
     ),
+    request_body = UnlockPostRequest,
     responses(
-        (status = 200, description = "Get related posts", body = ApiResponse<Vec<PostRelationResponse>>),
-        (status = 404, description = "Post not found")
+        (status = 200, description = "Password accepted; sets a short-lived cookie that unlocks the post", body = ApiResponse<PostResponse>),
+        (status = 400, description = "Post is not password protected"),
+        (status = 403, description = "Incorrect password"),
+        (status = 404, description = "Post not found or not visible to the caller"),
+        (status = 429, description = "Too many incorrect passwords from this address or account")
+    ),
+    security(
+        ("bearer_auth" = [])
     ),
     tag = "posts"
 )]
-pub async fn get_post_relation(
+pub async fn unlock_post(
     State(data): State<Arc<AppState>>,
     Path(post_id): Path<i32>,
+    user_id: Option<Extension<i64>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    cookie_jar: CookieJar,
+    Json(body): Json<UnlockPostRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let viewer_id = user_id.map(|Extension(user_id)| user_id as i32);
+
     match data
         .di_container
         .post_service
-        .get_post_relation(post_id)
+        .unlock_post(post_id, viewer_id, client.ip(), &body)
+        .await
+    {
+        Ok((token, post)) => {
+            let cookie = Cookie::build((unlock_cookie_name(post_id), token))
+                .path(format!("/api/posts/{}", post_id))
+                .http_only(true)
+                .same_site(SameSite::Lax)
+                .max_age(time::Duration::minutes(POST_UNLOCK_TTL_MINUTES));
+
+            Ok((StatusCode::OK, cookie_jar.add(cookie), Json(json!(post))))
+        }
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/trending",
+    params(FindTrendingPostRequest),
+    responses(
+        (status = 200, description = "Posts ranked by decayed view count", body = ApiResponse<Vec<TrendingPostResponse>>),
+        (status = 400, description = "Invalid window")
+    ),
+    tag = "posts"
+)]
+pub async fn get_trending_posts(
+    State(data): State<Arc<AppState>>,
+    Query(params): Query<FindTrendingPostRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .post_view_service
+        .get_trending(params)
         .await
     {
         Ok(posts) => Ok((StatusCode::OK, Json(json!(posts)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 #[utoipa::path(
-    post,
-    path = "/api/posts/create",
-    request_body(content = CreatePostRequest, content_type = "multipart/form-data"),
+    get,
+    path = "/api/posts/{id}/full",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        FindPostDetailRequest
+    ),
     responses(
-        (status = 201, description = "Post created successfully", body = ApiResponse<PostResponse>),
-        (status = 400, description = "Invalid request body"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Get post with author, category and comments", body = ApiResponse<PostDetailResponse>),
+        (status = 403, description = "Post is password protected and was not unlocked"),
+        (status = 404, description = "Post not found or not visible to the caller")
     ),
     security(
         ("bearer_auth" = [])
     ),
     tag = "posts"
 )]
-pub async fn create_post(
+pub async fn get_post_detail(
     State(data): State<Arc<AppState>>,
-    mut multipart: Multipart,
+    Path(post_id): Path<i32>,
+    user_id: Option<Extension<i64>>,
+    cookie_jar: CookieJar,
+    Query(params): Query<FindPostDetailRequest>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let mut title: Option<String> = None;
-    let mut body: Option<String> = None;
-    let mut category_id: Option<i32> = None;
-    let mut user_id: Option<i32> = None;
-    let mut user_name: Option<String> = None;
-    let mut file_data: Option<(String, String, Vec<u8>)> = None;
-
-    while let Some(field) = multipart.next_field().await.unwrap() {
+    let viewer_id = user_id.map(|Extension(user_id)| user_id as i32);
+
+    match data
+        .di_container
+        .post_service
+        .get_post_detail(post_id, params, viewer_id, unlock_token(&cookie_jar, post_id))
+        .await
+    {
+        Ok(post) => {
+            data.di_container
+                .post_view_service
+                .record_view(post_id, &visitor_key(viewer_id, client));
+
+            Ok((StatusCode::OK, Json(json!(post))))
+        }
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[derive(Default)]
+struct PostForm {
+    title: Option<String>,
+    body: Option<String>,
+    category_id: Option<i32>,
+    visibility: Option<PostVisibility>,
+    password: Option<String>,
+    file: Option<(String, String, Vec<u8>)>,
+}
+
+fn bad_request(message: &str) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": message
+        })),
+    )
+}
+
+async fn read_post_form(
+    mut multipart: Multipart,
+) -> Result<PostForm, (StatusCode, Json<serde_json::Value>)> {
+    let mut form = PostForm::default();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| bad_request("Malformed multipart body"))?
+    {
         match field.name() {
             Some("title") => {
-                title = Some(field.text().await.expect("should be text for title field"));
+                form.title = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|_| bad_request("title should be text"))?,
+                );
             }
             Some("body") => {
-                body = Some(field.text().await.expect("should be text for body field"));
-            }
-            Some("category_id") => {
-                category_id = Some(
+                form.body = Some(
                     field
                         .text()
                         .await
-                        .expect("should be text for category_id field")
-                        .parse()
-                        .expect("should be a number for category_id field"),
+                        .map_err(|_| bad_request("body should be text"))?,
                 );
             }
-            Some("user_id") => {
-                user_id = Some(
+            Some("category_id") => {
+                form.category_id = Some(
                     field
                         .text()
                         .await
-                        .expect("should be text for user_id field")
-                        .parse()
-                        .expect("should be a number for user_id field"),
+                        .ok()
+                        .and_then(|value| value.parse().ok())
+                        .ok_or_else(|| bad_request("category_id should be a number"))?,
+                );
+            }
+            Some("visibility") => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|_| bad_request("visibility should be text"))?;
+
+                form.visibility = Some(
+                    [
+                        PostVisibility::Public,
+                        PostVisibility::Unlisted,
+                        PostVisibility::Members,
+                        PostVisibility::Private,
+                    ]
+                    .into_iter()
+                    .find(|visibility| visibility.as_str() == value)
+                    .ok_or_else(|| {
+                        bad_request("visibility should be public, unlisted, members or private")
+                    })?,
                 );
             }
-            Some("user_name") => {
-                user_name = Some(
+            Some("password") => {
+                form.password = Some(
                     field
                         .text()
                         .await
-                        .expect("should be text for user_name field"),
+                        .map_err(|_| bad_request("password should be text"))?,
                 );
             }
             Some("file") => {
                 let file_name = field.file_name().map(ToString::to_string);
                 let content_type = field.content_type().map(ToString::to_string);
-                let bytes = field.bytes().await.expect("should be bytes for file field");
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|_| bad_request("file should be binary"))?;
 
-                if let (Some(name), Some(content_type)) = (file_name, content_type) {
-                    if !bytes.is_empty() {
-                        file_data = Some((name, content_type, bytes.to_vec()));
-                    }
+                if let (Some(name), Some(content_type)) = (file_name, content_type)
+                    && !bytes.is_empty()
+                {
+                    form.file = Some((name, content_type, bytes.to_vec()));
                 }
             }
             _ => (),
         };
     }
 
-    let uploaded_file_name = if let Some((name, content_type, file_bytes)) = file_data {
-        let upload_result = data
-            .di_container
-            .file_service
-            .upload_image("posts", name, content_type, file_bytes)
-            .await;
-
-        match upload_result {
-            Ok(response) => response.file_name.clone(),
-            Err((status, response)) => {
-                return Err((
-                    status,
-                    Json(json!({
-                        "error": response.message
-                    })),
-                ))
-            }
-        }
-    } else {
-        return Err((
-            StatusCode::BAD_REQUEST,
+    Ok(form)
+}
+
+async fn upload_post_image(
+    data: &AppState,
+    (name, content_type, file_bytes): (String, String, Vec<u8>),
+) -> Result<String, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .file_service
+        .upload_image("posts", name, content_type, file_bytes)
+        .await
+    {
+        Ok(response) => Ok(response.file_name.clone()),
+        Err((status, response)) => Err((
+            status,
             Json(json!({
-                "error": "File is required"
+                "error": response.message
             })),
-        ));
+        )),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/create",
+    request_body(content = CreatePostRequest, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Post created successfully", body = ApiResponse<PostResponse>),
+        (status = 400, description = "Invalid request body"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "posts"
+)]
+pub async fn create_post(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let form = read_post_form(multipart).await?;
+
+    let Some(file) = form.file else {
+        return Err(bad_request("File is required"));
     };
 
+    let uploaded_file_name = upload_post_image(&data, file).await?;
+
     let post_data = CreatePostRequest {
-        title: title.unwrap_or_default(),
-        body: body.unwrap_or_default(),
+        title: form.title.unwrap_or_default(),
+        body: form.body.unwrap_or_default(),
         file: uploaded_file_name,
-        category_id: category_id.unwrap_or(0),
-        user_id: user_id.unwrap_or(0),
-        user_name: user_name.unwrap_or_default(),
+        category_id: form.category_id.unwrap_or(0),
+        user_id: user_id as i32,
+        visibility: form.visibility.unwrap_or_default(),
+        password: form.password,
     };
 
     match data.di_container.post_service.create_post(&post_data).await {
-        Ok(post) => Ok((StatusCode::CREATED, Json(json!(post)))),
+        Ok(post) => Ok((
+            StatusCode::CREATED,
+            [(header::ETAG, etag(post.data.version))],
+            Json(json!(post)),
+        )),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": e.to_string()})),
@@ -216,16 +437,20 @@ pub async fn create_post(
 }
 
 #[utoipa::path(
-    put,
-    path = "/api/posts/update/{id}",
+    patch,
+    path = "/api/posts/{id}",
     params(
-        ("id" = i32, Path, description = "Post ID")
+        ("id" = i32, Path, description = "Post ID"),
+        ("If-Match" = String, Header, description = "ETag of the version being edited")
     ),
     request_body(content = UpdatePostRequest, content_type = "multipart/form-data"),
     responses(
         (status = 200, description = "Post updated successfully", body = ApiResponse<PostResponse>),
         (status = 400, description = "Invalid request body"),
-        (status = 404, description = "Post not found")
+        (status = 403, description = "Only the author or an editor can edit the post"),
+        (status = 404, description = "Post not found"),
+        (status = 412, description = "Post was modified by someone else"),
+        (status = 428, description = "If-Match header is missing")
     ),
     security(
         ("bearer_auth" = [])
@@ -235,122 +460,92 @@ pub async fn create_post(
 pub async fn update_post(
     State(data): State<Arc<AppState>>,
     Path(post_id): Path<i32>,
-    mut multipart: Multipart,
+    Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
+    multipart: Multipart,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    let mut title: Option<String> = None;
-    let mut body: Option<String> = None;
-    let mut category_id: Option<i32> = None;
-    let mut user_id: Option<i32> = None;
-    let mut user_name: Option<String> = None;
-    let mut file_data: Option<(String, String, Vec<u8>)> = None;
-
-    let old_post = match data.di_container.post_service.get_post(post_id).await {
-        Ok(post) => post.unwrap().data,
-        Err(e) => return Err((StatusCode::NOT_FOUND, Json(json!(e)))),
-    };
-
-    if !old_post.img.is_empty() {
-        let _ = data
-            .di_container
-            .file_service
-            .delete_image("posts", &old_post.img)
-            .await;
-    }
+    let version = if_match_version(&headers)?;
+    let form = read_post_form(multipart).await?;
 
-    while let Some(field) = multipart.next_field().await.unwrap() {
-        match field.name() {
-            Some("title") => {
-                title = Some(field.text().await.expect("should be text for title field"));
-            }
-            Some("body") => {
-                body = Some(field.text().await.expect("should be text for body field"));
-            }
-            Some("category_id") => {
-                category_id = Some(
-                    field
-                        .text()
-                        .await
-                        .expect("should be text for category_id field")
-                        .parse()
-                        .expect("should be a number for category_id field"),
-                );
-            }
-            Some("user_id") => {
-                user_id = Some(
-                    field
-                        .text()
-                        .await
-                        .expect("should be text for user_id field")
-                        .parse()
-                        .expect("should be a number for user_id field"),
-                );
-            }
-            Some("user_name") => {
-                user_name = Some(
-                    field
-                        .text()
-                        .await
-                        .expect("should be text for user_name field"),
-                );
-            }
-            Some("file") => {
-                let file_name = field.file_name().map(ToString::to_string);
-                let content_type = field.content_type().map(ToString::to_string);
-                let bytes = field.bytes().await.expect("should be bytes for file field");
-
-                if let (Some(name), Some(content_type)) = (file_name, content_type) {
-                    if !bytes.is_empty() {
-                        file_data = Some((name, content_type, bytes.to_vec()));
-                    }
-                }
-            }
-            _ => (),
-        };
-    }
-
-    let uploaded_file_name = if let Some((name, content_type, file_bytes)) = file_data {
-        let upload_result = data
-            .di_container
-            .file_service
-            .upload_image("posts", name, content_type, file_bytes)
-            .await;
-
-        match upload_result {
-            Ok(response) => response.file_name.clone(),
-            Err((status, response)) => {
-                return Err((
-                    status,
-                    Json(json!({
-                        "error": response.message
-                    })),
-                ))
-            }
+    let old_post = match data
+        .di_container
+        .post_service
+        .get_post(post_id, Some(user_id as i32), None)
+        .await
+    {
+        Ok(Some(post)) => post.data,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "status": "fail",
+                    "message": "Post not found"
+                })),
+            ))
         }
-    } else {
+        Err(e) => return Err((e.code, Json(json!(e)))),
+    };
+
+    if version.is_some_and(|version| version != old_post.version) {
         return Err((
-            StatusCode::BAD_REQUEST,
+            StatusCode::PRECONDITION_FAILED,
             Json(json!({
-                "error": "File is required"
+                "status": "fail",
+                "message": "Post has been modified"
             })),
         ));
+    }
+
+    // The new image is stored first; the old one is only removed once the
+    // database row points at the replacement.
+    let uploaded_file_name = match form.file {
+        Some(file) => Some(upload_post_image(&data, file).await?),
+        None => None,
     };
 
     let post_data = UpdatePostRequest {
         post_id: Some(post_id),
-        title: title.unwrap_or_default(),
-        body: body.unwrap_or_default(),
-        file: uploaded_file_name,
-        category_id: category_id.unwrap_or(0),
-        user_id: user_id.unwrap_or(0),
-        user_name: user_name.unwrap_or_default(),
+        title: form.title,
+        body: form.body,
+        file: uploaded_file_name.clone(),
+        category_id: form.category_id,
+        visibility: form.visibility,
+        password: form.password,
+        version,
     };
 
-    match data.di_container.post_service.update_post(&post_data).await {
-        Ok(post) => Ok((StatusCode::OK, Json(json!(post)))),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({"error": e.to_string()})),
-        )),
+    match data
+        .di_container
+        .post_service
+        .update_post(user_id as i32, &post_data)
+        .await
+    {
+        Ok(post) => {
+            if uploaded_file_name.is_some() && !old_post.img.is_empty() {
+                let _ = data
+                    .di_container
+                    .file_service
+                    .delete_image("posts", &old_post.img)
+                    .await;
+            }
+
+            Ok((
+                StatusCode::OK,
+                [(header::ETAG, etag(post.data.version))],
+                Json(json!(post)),
+            ))
+        }
+        Err(e) => {
+            if let Some(file_name) = &uploaded_file_name {
+                let _ = data
+                    .di_container
+                    .file_service
+                    .delete_image("posts", file_name)
+                    .await;
+            }
+
+            Err((e.code, Json(json!({"error": e.to_string()}))))
+        }
     }
 }
 
@@ -362,6 +557,7 @@ pub async fn update_post(
     ),
     responses(
         (status = 200, description = "Post deleted successfully"),
+        (status = 403, description = "Only the author or an editor can delete the post"),
         (status = 404, description = "Post not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -373,8 +569,14 @@ pub async fn update_post(
 pub async fn delete_post(
     State(data): State<Arc<AppState>>,
     Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.post_service.delete_post(post_id).await {
+    match data
+        .di_container
+        .post_service
+        .delete_post(user_id as i32, post_id)
+        .await
+    {
         Ok(_) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -382,21 +584,158 @@ pub async fn delete_post(
                 "message": "Post deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/posts/{id}/bookmark",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Post bookmarked"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "posts"
+)]
+pub async fn add_bookmark(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .post_service
+        .bookmark_post(user_id as i32, post_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/bookmark",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Bookmark removed"),
+        (status = 404, description = "Post is not bookmarked")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "posts"
+)]
+pub async fn remove_bookmark(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .post_service
+        .remove_bookmark(user_id as i32, post_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/me/bookmarks",
+    params(FindAllPostRequest),
+    responses(
+        (status = 200, description = "Posts bookmarked by the current user", body = ApiResponsePagination<Vec<PostResponse>>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "posts"
+)]
+pub async fn get_my_bookmarks(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindAllPostRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .post_service
+        .get_bookmarked_posts(user_id as i32, params)
+        .await
+    {
+        Ok(posts) => Ok((StatusCode::OK, Json(json!(posts)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/feed",
+    params(FindFeedRequest),
+    responses(
+        (status = 200, description = "Recent posts from followed users and categories", body = ApiResponsePagination<Vec<PostResponse>>),
+        (status = 400, description = "Invalid cursor")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "posts"
+)]
+pub async fn get_feed(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindFeedRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .post_service
+        .get_feed(user_id as i32, params)
+        .await
+    {
+        Ok(posts) => Ok((StatusCode::OK, Json(json!(posts)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
 pub fn post_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     let protected_routes = OpenApiRouter::new()
         .route("/api/posts/create", post(create_post))
-        .route("/api/posts/{id}", get(get_post))
+        .route("/api/posts/{id}", patch(update_post))
         .route("/api/posts/update/{id}", put(update_post))
         .route("/api/posts/delete/{id}", delete(delete_post))
-        .route("/api/posts/{id}/relation", get(get_post_relation))
+        .route(
+            "/api/posts/{id}/bookmark",
+            put(add_bookmark).delete(remove_bookmark),
+        )
+        .route("/api/users/me/bookmarks", get(get_my_bookmarks))
+        .route("/api/feed", get(get_feed))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone());
 
-    let public_routes = OpenApiRouter::new().route("/posts", get(get_posts));
+    let public_routes = OpenApiRouter::new()
+        .route("/posts", get(get_posts))
+        .route("/api/posts", get(get_posts))
+        .route("/api/posts/trending", get(get_trending_posts))
+        .route("/api/posts/{id}", get(get_post))
+        .route("/api/posts/{id}/full", get(get_post_detail))
+        // Older clients still call the relation path; it serves the detail.
+        .route("/api/posts/{id}/relation", get(get_post_detail))
+        .route("/api/posts/{id}/unlock", post(unlock_post))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            jwt::optional_auth,
+        ))
+        .with_state(app_state.clone());
 
     OpenApiRouter::new()
         .merge(protected_routes)
@@ -0,0 +1,173 @@
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, FindReactionsRequest, ReactionResponse, ReactionTarget,
+        ToggleReactionRequest, ToggleReactionResponse,
+    },
+    middleware::jwt,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+async fn toggle_reaction(
+    data: &AppState,
+    user_id: i64,
+    target: ReactionTarget,
+    target_id: i32,
+    body: ToggleReactionRequest,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .reaction_service
+        .toggle_reaction(user_id as i32, target, target_id, body.kind)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+async fn get_reactions(
+    data: &AppState,
+    user_id: i64,
+    target: ReactionTarget,
+    target_id: i32,
+    params: FindReactionsRequest,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .reaction_service
+        .get_reactions(user_id as i32, target, target_id, params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/reactions",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    request_body = ToggleReactionRequest,
+    responses(
+        (status = 200, description = "Reaction toggled", body = ApiResponse<ToggleReactionResponse>),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reactions"
+)]
+pub async fn toggle_post_reaction(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<ToggleReactionRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    toggle_reaction(&data, user_id, ReactionTarget::Post, post_id, body).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/reactions",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        FindReactionsRequest
+    ),
+    responses(
+        (status = 200, description = "Users who reacted to the post", body = ApiResponsePagination<Vec<ReactionResponse>>),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reactions"
+)]
+pub async fn get_post_reactions(
+    State(data): State<Arc<AppState>>,
+    Path(post_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindReactionsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    get_reactions(&data, user_id, ReactionTarget::Post, post_id, params).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/comments/{id}/reactions",
+    params(
+        ("id" = i32, Path, description = "Comment ID")
+    ),
+    request_body = ToggleReactionRequest,
+    responses(
+        (status = 200, description = "Reaction toggled", body = ApiResponse<ToggleReactionResponse>),
+        (status = 404, description = "Comment not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reactions"
+)]
+pub async fn toggle_comment_reaction(
+    State(data): State<Arc<AppState>>,
+    Path(comment_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<ToggleReactionRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    toggle_reaction(&data, user_id, ReactionTarget::Comment, comment_id, body).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/comments/{id}/reactions",
+    params(
+        ("id" = i32, Path, description = "Comment ID"),
+        FindReactionsRequest
+    ),
+    responses(
+        (status = 200, description = "Users who reacted to the comment", body = ApiResponsePagination<Vec<ReactionResponse>>),
+        (status = 404, description = "Comment not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reactions"
+)]
+pub async fn get_comment_reactions(
+    State(data): State<Arc<AppState>>,
+    Path(comment_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindReactionsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    get_reactions(&data, user_id, ReactionTarget::Comment, comment_id, params).await
+}
+
+pub fn reaction_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let protected_routes = OpenApiRouter::new()
+        .route(
+            "/api/posts/{id}/reactions",
+            get(get_post_reactions).post(toggle_post_reaction),
+        )
+        .route(
+            "/api/comments/{id}/reactions",
+            get(get_comment_reactions).post(toggle_comment_reaction),
+        )
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone());
+
+    OpenApiRouter::new()
+        .merge(protected_routes)
+        .with_state(app_state)
+}
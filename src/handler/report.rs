@@ -0,0 +1,156 @@
+use crate::{
+    domain::{
+        ApiResponse, ApiResponsePagination, CreateReportRequest, FindReportsRequest,
+        ReactionTarget, ReportResponse, UpdateReportRequest,
+    },
+    handler::posts::ensure_post_readable,
+    middleware::jwt,
+    state::AppState,
+};
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post, put},
+    Json,
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde_json::json;
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    post,
+    path = "/api/reports",
+    request_body = CreateReportRequest,
+    responses(
+        (status = 201, description = "Report submitted", body = ApiResponse<ReportResponse>),
+        (status = 400, description = "Invalid report"),
+        (status = 403, description = "Post is password protected"),
+        (status = 404, description = "Reported content not found"),
+        (status = 409, description = "Content already reported by this user")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn create_report(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    cookie_jar: CookieJar,
+    Json(body): Json<CreateReportRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let post_id = match body.target_type {
+        ReactionTarget::Post => body.target_id,
+        ReactionTarget::Comment => match data
+            .di_container
+            .comment_service
+            .get_comment(user_id as i32, body.target_id)
+            .await
+        {
+            Ok(Some(comment)) => comment.data.id_post_comment,
+            Ok(None) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "status": "fail",
+                        "message": "Comment not found"
+                    })),
+                ))
+            }
+            Err(e) => return Err((e.code, Json(json!(e)))),
+        },
+    };
+
+    ensure_post_readable(&data, post_id, user_id, &cookie_jar).await?;
+
+    match data
+        .di_container
+        .report_service
+        .create_report(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/moderation/reports",
+    params(FindReportsRequest),
+    responses(
+        (status = 200, description = "Reports in the moderator inbox", body = ApiResponsePagination<Vec<ReportResponse>>),
+        (status = 403, description = "Moderator role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn get_report_inbox(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<FindReportsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .report_service
+        .get_inbox(user_id as i32, params)
+        .await
+    {
+        Ok(reports) => Ok((StatusCode::OK, Json(json!(reports)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/moderation/reports/{id}",
+    params(
+        ("id" = i32, Path, description = "Report ID")
+    ),
+    request_body = UpdateReportRequest,
+    responses(
+        (status = 200, description = "Report triaged, resolved or dismissed", body = ApiResponse<ReportResponse>),
+        (status = 400, description = "Invalid status"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Report not found"),
+        (status = 409, description = "Report already closed")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "reports"
+)]
+pub async fn update_report(
+    State(data): State<Arc<AppState>>,
+    Path(report_id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateReportRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .report_service
+        .update_report(user_id as i32, report_id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+pub fn report_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    let protected_routes = OpenApiRouter::new()
+        .route("/api/reports", post(create_report))
+        .route("/api/moderation/reports", get(get_report_inbox))
+        .route("/api/moderation/reports/{id}", put(update_report))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone());
+
+    OpenApiRouter::new()
+        .merge(protected_routes)
+        .with_state(app_state)
+}
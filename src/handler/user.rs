@@ -1,11 +1,17 @@
 use crate::{
-    domain::{ApiResponse, CreateUserRequest, UpdateUserRequest, UserResponse},
+    domain::{
+        ApiResponse, ApiResponsePagination, ChangeEmailRequest, ChangePasswordRequest,
+        CreateUserRequest, DeleteAccountRequest, EraseUserRequest, ErasureResponse,
+        ExportAccountRequest, FindAllPostRequest, PostResponse, UpdateProfileRequest,
+        UpdateUserRequest, UserProfileResponse, UserResponse,
+    },
     middleware::jwt,
     state::AppState,
+    utils::{etag, if_match_version, if_none_match},
 };
 use axum::{
-    extract::{Json, Path, State},
-    http::StatusCode,
+    extract::{Extension, Json, Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     middleware,
     response::IntoResponse,
     routing::{delete, get, post, put},
@@ -14,12 +20,26 @@ use serde_json::json;
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
+/// The `/api/user/*` endpoints predate `/api/admin/users` and are limited to
+/// admins in the same way.
+async fn ensure_admin(
+    data: &AppState,
+    user_id: i64,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    data.di_container
+        .admin_service
+        .ensure_admin(user_id as i32)
+        .await
+        .map_err(|e| (e.code, Json(json!(e))))
+}
+
 #[utoipa::path(
     post,
     path = "/api/user/create",
     responses(
         (status = 200, description = "Create user", body = ApiResponse<UserResponse>),
         (status = 400, description = "Invalid request body"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -29,8 +49,11 @@ use utoipa_axum::router::OpenApiRouter;
 )]
 pub async fn create_user(
     State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
     Json(body): Json<CreateUserRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_admin(&data, user_id).await?;
+
     match data.di_container.user_service.create_user(&body).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
         Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
@@ -45,7 +68,9 @@ pub async fn create_user(
     ),
     responses(
         (status = 200, description = "Find Email user", body = ApiResponse<UserResponse>),
+        (status = 304, description = "User not modified since the given ETag"),
         (status = 400, description = "Invalid request body"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -56,14 +81,26 @@ pub async fn create_user(
 pub async fn find_user_by_email(
     State(data): State<Arc<AppState>>,
     Path(email): Path<String>,
+    Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_admin(&data, user_id).await?;
+
     match data
         .di_container
         .user_service
         .find_user_by_email(&email)
         .await
     {
-        Ok(Some(response)) => Ok((StatusCode::OK, Json(json!(response)))),
+        Ok(Some(response)) => {
+            let tag = etag(response.data.version);
+
+            if if_none_match(&headers, response.data.version) {
+                return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, tag)]).into_response());
+            }
+
+            Ok((StatusCode::OK, [(header::ETAG, tag)], Json(json!(response))).into_response())
+        }
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -79,11 +116,15 @@ pub async fn find_user_by_email(
     put,
     path = "/api/user/update/{id}",
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        ("If-Match" = String, Header, description = "ETag of the version being edited")
     ),
     responses(
         (status = 200, description = "Update user", body = ApiResponse<UserResponse>),
         (status = 400, description = "Invalid request body"),
+        (status = 403, description = "Admin role required"),
+        (status = 412, description = "User was modified by someone else"),
+        (status = 428, description = "If-Match header is missing"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -94,12 +135,21 @@ pub async fn find_user_by_email(
 pub async fn update_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
     Json(mut body): Json<UpdateUserRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_admin(&data, user_id).await?;
+
     body.id = Some(id);
+    body.version = if_match_version(&headers)?;
 
     match data.di_container.user_service.update_user(&body).await {
-        Ok(Some(response)) => Ok((StatusCode::OK, Json(json!(response)))),
+        Ok(Some(response)) => Ok((
+            StatusCode::OK,
+            [(header::ETAG, etag(response.data.version))],
+            Json(json!(response)),
+        )),
         Ok(None) => Err((
             StatusCode::NOT_FOUND,
             Json(json!({
@@ -107,7 +157,7 @@ pub async fn update_user(
                 "message": "User not found"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -117,9 +167,12 @@ pub async fn update_user(
     params(
         ("email" = String, Path, description = "Email User")
     ),
+    request_body = EraseUserRequest,
     responses(
-        (status = 200, description = "User", body = Value),
+        (status = 200, description = "User erased", body = ApiResponse<ErasureResponse>),
         (status = 400, description = "Invalid request body"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -130,16 +183,404 @@ pub async fn update_user(
 pub async fn delete_user(
     State(data): State<Arc<AppState>>,
     Path(email): Path<String>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<EraseUserRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    ensure_admin(&data, user_id).await?;
+
+    let user = match data
+        .di_container
+        .user_service
+        .find_user_by_email(&email)
+        .await
+    {
+        Ok(Some(response)) => response.data,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "status": "fail",
+                    "message": "User not found"
+                })),
+            ))
+        }
+        Err(e) => return Err((e.code, Json(json!(e)))),
+    };
+
+    // Same erasure as `/api/admin/users/{id}`, so nothing is left behind.
+    match data
+        .di_container
+        .admin_service
+        .erase_user(user_id as i32, user.id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/me/profile",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Invalid username, bio or website"),
+        (status = 409, description = "Username already taken")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn update_profile(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<UpdateProfileRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.user_service.delete_user(&email).await {
-        Ok(_) => Ok((
+    match data
+        .di_container
+        .user_service
+        .update_profile(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/me/password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed; other sessions are signed out and a fresh token is returned", body = ApiResponse<String>),
+        (status = 400, description = "Current password is incorrect or the new one is too short")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn change_password(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<ChangePasswordRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .auth_service
+        .change_password(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/me/email",
+    request_body = ChangeEmailRequest,
+    responses(
+        (status = 200, description = "Confirmation token sent to the new address"),
+        (status = 400, description = "Current password is incorrect or the address is not valid"),
+        (status = 409, description = "Email already exists")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn change_email(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<ChangeEmailRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .auth_service
+        .request_email_change(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/me/export",
+    params(ExportAccountRequest),
+    responses(
+        (status = 200, description = "Download of the profile, posts, comments and uploaded images; a JSON file with `format=json`", content_type = "application/zip")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn export_account(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<ExportAccountRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .privacy_service
+        .export_account(user_id as i32, params.format)
+        .await
+    {
+        Ok(archive) => Ok((
             StatusCode::OK,
-            Json(json!({
-                "status": "success",
-                "message": "User deleted successfully"
-            })),
+            [
+                (header::CONTENT_TYPE, archive.content_type.to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", archive.file_name),
+                ),
+                (header::CACHE_CONTROL, "no-store".to_string()),
+            ],
+            archive.data,
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/me",
+    request_body = DeleteAccountRequest,
+    responses(
+        (status = 200, description = "Account erased: comments anonymized, posts deleted or reassigned, files removed", body = ApiResponse<ErasureResponse>),
+        (status = 400, description = "Current password is incorrect or reassign_to is missing"),
+        (status = 404, description = "User to reassign the posts to not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn delete_account(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    Json(body): Json<DeleteAccountRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match data
+        .di_container
+        .privacy_service
+        .delete_account(user_id as i32, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+fn bad_request(message: &str) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "status": "fail",
+            "message": message
+        })),
+    )
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/me/avatar",
+    request_body(content = String, content_type = "multipart/form-data", description = "Image in a `file` field"),
+    responses(
+        (status = 200, description = "Avatar replaced", body = ApiResponse<UserResponse>),
+        (status = 400, description = "No image in the `file` field")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn upload_avatar(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let mut file = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| bad_request("Malformed multipart body"))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.file_name().map(ToString::to_string);
+        let content_type = field.content_type().map(ToString::to_string);
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|_| bad_request("file should be binary"))?;
+
+        if let (Some(name), Some(content_type)) = (file_name, content_type)
+            && content_type.starts_with("image/")
+            && !bytes.is_empty()
+        {
+            file = Some((name, content_type, bytes.to_vec()));
+        }
+    }
+
+    let Some((name, content_type, bytes)) = file else {
+        return Err(bad_request("An image is required in the file field"));
+    };
+
+    let user_id = user_id as i32;
+    let services = &data.di_container;
+
+    let old_avatar = match services.user_service.find_by_id(user_id).await {
+        Ok(user) => user.and_then(|user| user.data.avatar),
+        Err(e) => return Err((e.code, Json(json!(e)))),
+    };
+
+    let uploaded = services
+        .file_service
+        .upload_image("avatars", name, content_type, bytes)
+        .await
+        .map_err(|(status, response)| {
+            (
+                status,
+                Json(json!({
+                    "status": "error",
+                    "message": response.message
+                })),
+            )
+        })?
+        .file_name
+        .clone();
+
+    // As with post images, the old file goes only once the row points at
+    // the new one.
+    match services
+        .user_service
+        .set_avatar(user_id, Some(&uploaded))
+        .await
+    {
+        Ok(response) => {
+            if let Some(old_avatar) = old_avatar {
+                let _ = services
+                    .file_service
+                    .delete_image("avatars", &old_avatar)
+                    .await;
+            }
+
+            Ok((StatusCode::OK, Json(json!(response))))
+        }
+        Err(e) => {
+            let _ = services.file_service.delete_image("avatars", &uploaded).await;
+
+            Err((e.code, Json(json!(e))))
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/me/avatar",
+    responses(
+        (status = 200, description = "Avatar removed", body = ApiResponse<UserResponse>)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "users"
+)]
+pub async fn delete_avatar(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i64>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let user_id = user_id as i32;
+    let services = &data.di_container;
+
+    let old_avatar = match services.user_service.find_by_id(user_id).await {
+        Ok(user) => user.and_then(|user| user.data.avatar),
+        Err(e) => return Err((e.code, Json(json!(e)))),
+    };
+
+    match services.user_service.set_avatar(user_id, None).await {
+        Ok(response) => {
+            if let Some(old_avatar) = old_avatar {
+                let _ = services
+                    .file_service
+                    .delete_image("avatars", &old_avatar)
+                    .await;
+            }
+
+            Ok((StatusCode::OK, Json(json!(response))))
+        }
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{username}",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "Public profile with the first page of published posts", body = ApiResponse<UserProfileResponse>),
+        (status = 404, description = "User not found")
+    ),
+    tag = "users"
+)]
+pub async fn get_profile(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    user_id: Option<Extension<i64>>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let viewer_id = user_id.map(|Extension(user_id)| user_id as i32);
+
+    match data
+        .di_container
+        .user_service
+        .get_profile(&username, viewer_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{username}/posts",
+    params(
+        ("username" = String, Path, description = "Username"),
+        FindAllPostRequest
+    ),
+    responses(
+        (status = 200, description = "The user's published posts", body = ApiResponsePagination<Vec<PostResponse>>),
+        (status = 404, description = "User not found")
+    ),
+    tag = "users"
+)]
+pub async fn get_user_posts(
+    State(data): State<Arc<AppState>>,
+    Path(username): Path<String>,
+    user_id: Option<Extension<i64>>,
+    Query(params): Query<FindAllPostRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let viewer_id = user_id.map(|Extension(user_id)| user_id as i32);
+
+    match data
+        .di_container
+        .user_service
+        .get_user_posts(&username, params, viewer_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err((e.code, Json(json!(e)))),
     }
 }
 
@@ -149,10 +590,29 @@ pub fn user_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/user/email/{email}", get(find_user_by_email))
         .route("/api/user/update/{id}", put(update_user))
         .route("/api/user/delete/{email}", delete(delete_user))
+        .route("/api/users/me", delete(delete_account))
+        .route("/api/users/me/export", post(export_account))
+        .route("/api/users/me/profile", put(update_profile))
+        .route("/api/users/me/password", put(change_password))
+        .route("/api/users/me/email", put(change_email))
+        .route(
+            "/api/users/me/avatar",
+            put(upload_avatar).delete(delete_avatar),
+        )
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone());
 
+    let public_routes = OpenApiRouter::new()
+        .route("/api/users/{username}", get(get_profile))
+        .route("/api/users/{username}/posts", get(get_user_posts))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            jwt::optional_auth,
+        ))
+        .with_state(app_state.clone());
+
     OpenApiRouter::new()
         .merge(protected_routes)
+        .merge(public_routes)
         .with_state(app_state)
 }
@@ -18,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let port = config.port;
 
-    let state = AppState::new(db_pool, &config.jwt_secret);
+    let state = AppState::new(db_pool, &config);
 
     println!("🚀 Server started successfully");
 
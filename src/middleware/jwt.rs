@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use axum::{
     body::Body,
     extract::State,
@@ -9,7 +9,29 @@ use axum::{
 };
 use axum_extra::extract::cookie::CookieJar;
 
-use crate::{domain::ErrorResponse, state::AppState};
+use crate::{domain::ErrorResponse, state::AppState, utils::AppError};
+
+/// How often connections held open past the request, such as streams,
+/// check that their token has not been revoked since.
+pub const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The token a request was authenticated with, inserted next to the user id
+/// so long-lived handlers can check it again later.
+#[derive(Clone)]
+pub struct SessionToken(pub String);
+
+fn extract_token(cookie_jar: &CookieJar, req: &Request<Body>) -> Option<String> {
+    cookie_jar
+        .get("token")
+        .map(|cookie| cookie.value().to_string())
+        .or_else(|| {
+            req.headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|auth_header| auth_header.to_str().ok())
+                .and_then(|auth_value| auth_value.strip_prefix("Bearer "))
+                .map(str::to_owned)
+        })
+}
 
 pub async fn auth(
     cookie_jar: CookieJar,
@@ -18,21 +40,7 @@ pub async fn auth(
     next: Next,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
     
-    let token = cookie_jar
-        .get("token")
-        .map(|cookie| cookie.value().to_string())
-        .or_else(|| {
-            req.headers()
-                .get(header::AUTHORIZATION)
-                .and_then(|auth_header| auth_header.to_str().ok())
-                .and_then(|auth_value| {
-                    if auth_value.starts_with("Bearer ") {
-                        Some(auth_value[7..].to_owned())
-                    } else {
-                        None
-                    }
-                })
-        });
+    let token = extract_token(&cookie_jar, &req);
 
     // Check if token exists
     let token = match token {
@@ -43,20 +51,26 @@ pub async fn auth(
                 Json(ErrorResponse {
                     status: "fail".to_string(),
                     message: "You are not logged in, please provide token".to_string(),
+                    code: StatusCode::UNAUTHORIZED,
                 }),
             ));
         }
     };
 
     // Verify token and get user_id
-    let user_id = match data.jwt_config.verify_token(&token) {
+    let user_id = match data.di_container.auth_service.authenticate(&token).await {
         Ok(id) => id,
+        Err(e @ AppError::Forbidden(_)) => {
+            let error = ErrorResponse::from(e);
+            return Err((error.code, Json(error)));
+        }
         Err(_) => {
             return Err((
                 StatusCode::UNAUTHORIZED,
                 Json(ErrorResponse {
                     status: "fail".to_string(),
                     message: "Invalid token".to_string(),
+                    code: StatusCode::UNAUTHORIZED,
                 }),
             ));
         }
@@ -64,7 +78,26 @@ pub async fn auth(
 
     // Insert user_id into request extensions
     req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(SessionToken(token));
     
    
     Ok(next.run(req).await)
-}
\ No newline at end of file
+}
+
+/// Like [`auth`], but lets anonymous requests through. The user id is only
+/// inserted when a valid token is present, so handlers can take
+/// `Option<Extension<i64>>`.
+pub async fn optional_auth(
+    cookie_jar: CookieJar,
+    State(data): State<Arc<AppState>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> impl IntoResponse {
+    if let Some(token) = extract_token(&cookie_jar, &req)
+        && let Ok(user_id) = data.di_container.auth_service.authenticate(&token).await
+    {
+        req.extensions_mut().insert(user_id);
+    }
+
+    next.run(req).await
+}
@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use sqlx::prelude::FromRow;
+
+#[derive(Debug, FromRow, Clone)]
+pub struct UserBlock {
+    pub blocker_id: i32,
+    pub blocked_id: i32,
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+}
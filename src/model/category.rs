@@ -5,4 +5,13 @@ use sqlx::prelude::FromRow;
 pub struct Category {
     pub id: i32,
     pub name: String,
+    pub version: i32,
+    pub parent_id: Option<i32>,
+    pub slug: String,
+    pub description: Option<String>,
+    pub cover_image: Option<String>,
+    pub sort_order: i32,
+    /// Visible posts in this category; only selected by read queries.
+    #[sqlx(default)]
+    pub post_count: i64,
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use sqlx::prelude::FromRow;
 
 
@@ -8,4 +9,29 @@ pub struct Comment {
     pub id_post_comment: i32,
     pub user_name_comment: String,
     pub comment: String,
+    pub version: i32,
+    pub parent_id: Option<i32>,
+    pub depth: i32,
+    pub is_deleted: bool,
+    /// `None` for guest comments written before comments were tied to accounts.
+    pub user_id: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub status: String,
+}
+
+/// A row of a comment tree query; `reply_count` counts all direct replies,
+/// including the ones that fell outside the requested page.
+#[derive(Debug, FromRow, Clone)]
+pub struct CommentNode {
+    #[sqlx(flatten)]
+    pub comment: Comment,
+    pub reply_count: i64,
+}
+
+/// A comment with the reaction total used by the `top` sort.
+#[derive(Debug, FromRow, Clone)]
+pub struct RankedComment {
+    #[sqlx(flatten)]
+    pub comment: Comment,
+    pub score: i64,
 }
@@ -1,4 +1,10 @@
+pub mod block;
 pub mod category;
 pub mod comment;
+pub mod notification;
 pub mod posts;
-pub mod user;
\ No newline at end of file
+pub mod reaction;
+pub mod report;
+pub mod user;
+pub mod user_token;
+pub mod view;
\ No newline at end of file
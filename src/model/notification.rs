@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use sqlx::prelude::FromRow;
+
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Notification {
+    pub id: i32,
+    pub user_id: i32,
+    pub actor_id: Option<i32>,
+    pub kind: String,
+    pub target_type: String,
+    pub target_id: i32,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct NotificationPreferences {
+    pub user_id: i32,
+    pub mention: bool,
+    pub comment: bool,
+    pub reply: bool,
+}
+
+impl NotificationPreferences {
+    /// What a user who never saved preferences gets.
+    pub fn defaults(user_id: i32) -> Self {
+        Self {
+            user_id,
+            mention: true,
+            comment: true,
+            reply: true,
+        }
+    }
+}
@@ -11,14 +11,10 @@ pub struct Post {
     pub category_id: i32,
     pub user_id: i32,
     pub user_name: String,
-}
-
-#[derive(Debug, sqlx::FromRow, Clone)]
-pub struct PostRelationModel {
-    pub post_id: i32,
-    pub title: String,
-    pub comment_id: i32,
-    pub id_post_comment: i32,
-    pub user_name_comment: String,
-    pub comment: String,
+    pub version: i32,
+    /// Hidden by reports until a moderator reviews it.
+    pub is_hidden: bool,
+    pub visibility: String,
+    /// Set when the post is password protected.
+    pub password_hash: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use sqlx::prelude::FromRow;
+
+
+#[derive(Debug, FromRow, Clone)]
+pub struct ReactionCount {
+    pub target_id: i32,
+    pub kind: String,
+    pub count: i64,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Reactor {
+    pub user_id: i32,
+    pub firstname: String,
+    pub lastname: String,
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use sqlx::prelude::FromRow;
+
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Report {
+    pub id: i32,
+    pub reporter_id: i32,
+    pub target_type: String,
+    pub target_id: i32,
+    pub reason: String,
+    pub details: Option<String>,
+    pub status: String,
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// An inbox row; `open_reports` counts the unresolved reports on the same target.
+#[derive(Debug, FromRow, Clone)]
+pub struct ReportEntry {
+    #[sqlx(flatten)]
+    pub report: Report,
+    pub open_reports: i64,
+}
@@ -7,4 +7,24 @@ pub struct User {
     pub lastname: String,
     pub email: String,
     pub password: String,
+    pub version: i32,
+    pub role: String,
+    pub username: String,
+    pub bio: Option<String>,
+    pub avatar: Option<String>,
+    pub website: Option<String>,
+    pub is_active: bool,
+    pub token_version: i32,
+    pub password_reset_required: bool,
+}
+
+/// What an erasure removed or handed over. The files are only listed; the
+/// caller removes them once the transaction has committed.
+#[derive(Debug, Clone)]
+pub struct ErasedAccount {
+    pub avatar: Option<String>,
+    pub post_images: Vec<String>,
+    pub posts_deleted: u64,
+    pub posts_reassigned: u64,
+    pub comments_anonymized: u64,
 }
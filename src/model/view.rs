@@ -0,0 +1,12 @@
+use sqlx::prelude::FromRow;
+
+use crate::model::posts::Post;
+
+
+#[derive(Debug, FromRow, Clone)]
+pub struct TrendingPost {
+    #[sqlx(flatten)]
+    pub post: Post,
+    pub views: i64,
+    pub score: f64,
+}
@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use sea_query::{Condition, Expr, Func, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::info;
+
+use crate::abstract_trait::BlockRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::{BlockKind, FindBlocksRequest};
+use crate::model::block::UserBlock;
+use crate::schema::block::UserBlocks;
+use crate::schema::follow::Follows;
+use crate::utils::AppError;
+
+/// Rows whose `author` column `viewer_id` has neither blocked nor muted.
+/// Rows without an author are kept.
+pub(crate) fn visible_to(author: Expr, viewer_id: i32) -> Condition {
+    Condition::any().add(author.clone().is_null()).add(
+        author.not_in_subquery(
+            Query::select()
+                .column(UserBlocks::BlockedId)
+                .from(UserBlocks::Table)
+                .and_where(Expr::col(UserBlocks::BlockerId).eq(viewer_id))
+                .to_owned(),
+        ),
+    )
+}
+
+pub struct BlockRepository {
+    db_pool: ConnectionPool,
+}
+
+impl BlockRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl BlockRepositoryTrait for BlockRepository {
+    async fn add(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+        kind: BlockKind,
+    ) -> Result<bool, AppError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(UserBlocks::Table)
+            .columns([UserBlocks::BlockerId, UserBlocks::BlockedId, UserBlocks::Kind])
+            .values_panic([blocker_id.into(), blocked_id.into(), kind.as_str().into()])
+            .on_conflict(
+                OnConflict::columns([
+                    UserBlocks::BlockerId,
+                    UserBlocks::BlockedId,
+                    UserBlocks::Kind,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let added = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+            > 0;
+
+        if kind == BlockKind::Block {
+            let (sql, values) = Query::delete()
+                .from_table(Follows::Table)
+                .and_where(Expr::col(Follows::FollowerId).eq(blocked_id))
+                .and_where(Expr::col(Follows::UserId).eq(blocker_id))
+                .build_sqlx(PostgresQueryBuilder);
+
+            sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+
+        info!("User {} added a {} on user {}", blocker_id, kind.as_str(), blocked_id);
+
+        Ok(added)
+    }
+
+    async fn remove(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+        kind: BlockKind,
+    ) -> Result<bool, AppError> {
+        let (sql, values) = Query::delete()
+            .from_table(UserBlocks::Table)
+            .and_where(Expr::col(UserBlocks::BlockerId).eq(blocker_id))
+            .and_where(Expr::col(UserBlocks::BlockedId).eq(blocked_id))
+            .and_where(Expr::col(UserBlocks::Kind).eq(kind.as_str()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_kinds(
+        &self,
+        blocker_id: i32,
+        blocked_id: i32,
+    ) -> Result<Vec<BlockKind>, AppError> {
+        let (sql, values) = Query::select()
+            .column(UserBlocks::Kind)
+            .from(UserBlocks::Table)
+            .and_where(Expr::col(UserBlocks::BlockerId).eq(blocker_id))
+            .and_where(Expr::col(UserBlocks::BlockedId).eq(blocked_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let kinds: Vec<String> = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(kinds.iter().map(|kind| BlockKind::parse(kind)).collect())
+    }
+
+    async fn find_by_blocker(
+        &self,
+        blocker_id: i32,
+        req: &FindBlocksRequest,
+    ) -> Result<(Vec<UserBlock>, i64), AppError> {
+        let mut filter = Query::select();
+        filter
+            .from(UserBlocks::Table)
+            .and_where(Expr::col(UserBlocks::BlockerId).eq(blocker_id));
+
+        if let Some(kind) = req.kind {
+            filter.and_where(Expr::col(UserBlocks::Kind).eq(kind.as_str()));
+        }
+
+        let mut count_query = filter.clone();
+        count_query.expr(Func::count(Expr::col(UserBlocks::BlockedId)));
+
+        let (sql, values) = filter
+            .columns([
+                UserBlocks::BlockerId,
+                UserBlocks::BlockedId,
+                UserBlocks::Kind,
+                UserBlocks::CreatedAt,
+            ])
+            .order_by(UserBlocks::CreatedAt, Order::Desc)
+            .order_by(UserBlocks::BlockedId, Order::Desc)
+            .offset(((req.page - 1) * req.page_size) as u64)
+            .limit(req.page_size as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let blocks = sqlx::query_as_with::<_, UserBlock, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((blocks, total.0))
+    }
+
+    async fn find_hidden(&self, viewer_id: i32) -> Result<Vec<i32>, AppError> {
+        let (sql, values) = Query::select()
+            .distinct()
+            .column(UserBlocks::BlockedId)
+            .from(UserBlocks::Table)
+            .and_where(Expr::col(UserBlocks::BlockerId).eq(viewer_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(ids)
+    }
+
+    async fn find_blockers(&self, blocked_id: i32, user_ids: &[i32]) -> Result<Vec<i32>, AppError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (sql, values) = Query::select()
+            .column(UserBlocks::BlockerId)
+            .from(UserBlocks::Table)
+            .and_where(Expr::col(UserBlocks::BlockedId).eq(blocked_id))
+            .and_where(Expr::col(UserBlocks::Kind).eq(BlockKind::Block.as_str()))
+            .and_where(Expr::col(UserBlocks::BlockerId).is_in(user_ids.iter().copied()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(ids)
+    }
+
+    async fn find_hiders(&self, hidden_id: i32, user_ids: &[i32]) -> Result<Vec<i32>, AppError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (sql, values) = Query::select()
+            .distinct()
+            .column(UserBlocks::BlockerId)
+            .from(UserBlocks::Table)
+            .and_where(Expr::col(UserBlocks::BlockedId).eq(hidden_id))
+            .and_where(Expr::col(UserBlocks::BlockerId).is_in(user_ids.iter().copied()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(ids)
+    }
+}
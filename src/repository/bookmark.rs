@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use sea_query::{Expr, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::info;
+
+use crate::abstract_trait::BookmarkRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::schema::bookmark::Bookmarks;
+use crate::utils::AppError;
+
+pub struct BookmarkRepository {
+    db_pool: ConnectionPool,
+}
+
+impl BookmarkRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl BookmarkRepositoryTrait for BookmarkRepository {
+    async fn add(&self, user_id: i32, post_id: i32) -> Result<bool, AppError> {
+        let (sql, values) = Query::insert()
+            .into_table(Bookmarks::Table)
+            .columns([Bookmarks::UserId, Bookmarks::PostId])
+            .values_panic([user_id.into(), post_id.into()])
+            .on_conflict(
+                OnConflict::columns([Bookmarks::UserId, Bookmarks::PostId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        info!("User {} bookmarked post {}", user_id, post_id);
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove(&self, user_id: i32, post_id: i32) -> Result<bool, AppError> {
+        let (sql, values) = Query::delete()
+            .from_table(Bookmarks::Table)
+            .and_where(Expr::col(Bookmarks::UserId).eq(user_id))
+            .and_where(Expr::col(Bookmarks::PostId).eq(post_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_bookmarked(
+        &self,
+        user_id: i32,
+        post_ids: &[i32],
+    ) -> Result<HashSet<i32>, AppError> {
+        if post_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let (sql, values) = Query::select()
+            .column(Bookmarks::PostId)
+            .from(Bookmarks::Table)
+            .and_where(Expr::col(Bookmarks::UserId).eq(user_id))
+            .and_where(Expr::col(Bookmarks::PostId).is_in(post_ids.iter().copied()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids: Vec<i32> = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(ids.into_iter().collect())
+    }
+}
@@ -2,13 +2,136 @@ use crate::abstract_trait::CategoryRepositoryTrait;
 use crate::config::ConnectionPool;
 use crate::domain::{CreateCategoryRequest,  UpdateCategoryRequest};
 use crate::model::category::Category;
+use crate::repository::posts::listed_for;
 use crate::schema::category::Categories;
+use crate::schema::follow::Follows;
+use crate::schema::posts::Posts;
 use crate::utils::AppError;
 use async_trait::async_trait;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use sea_query::{
+    Alias, CommonTableExpression, Expr, Func, IntoIden, Order, PostgresQueryBuilder, Query,
+    QueryStatementBuilder, SelectStatement, SimpleExpr, UnionType, WithClause,
+};
 use sea_query_binder::SqlxBinder;
 use tracing::{debug, error, info};
 
+const CATEGORY_COLUMNS: [Categories; 8] = [
+    Categories::Id,
+    Categories::Name,
+    Categories::Version,
+    Categories::ParentId,
+    Categories::Slug,
+    Categories::Description,
+    Categories::CoverImage,
+    Categories::SortOrder,
+];
+
+/// Guards the recursive queries should a cycle ever reach the table.
+const MAX_TREE_DEPTH: i32 = 64;
+
+/// `WITH RECURSIVE` over `categories`, following `parent_id` up from `id`
+/// towards the root (`upwards`) or down to every descendant.
+fn walk_tree(id: i32, upwards: bool) -> WithClause {
+    let tree = Alias::new("tree");
+    let depth = Alias::new("depth");
+
+    let (child, parent) = if upwards {
+        (Categories::Id, Categories::ParentId)
+    } else {
+        (Categories::ParentId, Categories::Id)
+    };
+
+    let step = Query::select()
+        .columns(CATEGORY_COLUMNS.map(|column| (Categories::Table, column)))
+        .expr(Expr::col((tree.clone(), depth.clone())).add(1))
+        .from(Categories::Table)
+        .inner_join(
+            tree.clone(),
+            Expr::col((Categories::Table, child)).equals((tree.clone(), parent)),
+        )
+        .and_where(Expr::col((tree.clone(), depth.clone())).lt(MAX_TREE_DEPTH))
+        .to_owned();
+
+    let walk = Query::select()
+        .columns(CATEGORY_COLUMNS)
+        // A literal, so Postgres types the column as an integer.
+        .expr(Expr::cust("0"))
+        .from(Categories::Table)
+        .and_where(Expr::col(Categories::Id).eq(id))
+        .union(UnionType::All, step)
+        .to_owned();
+
+    let mut cte = CommonTableExpression::new();
+    cte.query(walk).table_name(tree);
+
+    for column in CATEGORY_COLUMNS {
+        cte.column(column);
+    }
+    cte.column(depth);
+
+    WithClause::new().recursive(true).cte(cte).to_owned()
+}
+
+fn select_tree() -> SelectStatement {
+    Query::select()
+        .columns(CATEGORY_COLUMNS)
+        .expr_as(post_count(Alias::new("tree")), Alias::new("post_count"))
+        .from(Alias::new("tree"))
+        .order_by(Alias::new("depth"), Order::Desc)
+        .to_owned()
+}
+
+/// Visible posts filed directly under the category row of `table`. Counts
+/// are the same for every caller, so only public posts are included.
+fn post_count<T: IntoIden + 'static>(table: T) -> SimpleExpr {
+    SimpleExpr::SubQuery(
+        None,
+        Box::new(
+            Query::select()
+                .expr(Func::count(Expr::col((Posts::Table, Posts::Id))))
+                .from(Posts::Table)
+                .and_where(
+                    Expr::col((Posts::Table, Posts::CategoryId)).equals((table, Categories::Id)),
+                )
+                .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false))
+                .cond_where(listed_for(None))
+                .to_owned()
+                .into_sub_query_statement(),
+        ),
+    )
+}
+
+fn map_duplicate(error: sqlx::Error) -> AppError {
+    match &error {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            AppError::Conflict("A category with this name or slug already exists".into())
+        }
+        _ => AppError::SqlxError(error),
+    }
+}
+
+/// Posts still reference the category, e.g. one filed while it was removed.
+fn map_still_in_use(error: sqlx::Error, id: i32) -> AppError {
+    match &error {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => AppError::Conflict(format!(
+            "Category with id {} still has posts; merge it or reassign them first",
+            id
+        )),
+        _ => AppError::SqlxError(error),
+    }
+}
+
+/// Ids of a category and all of its descendants, for `IN` conditions.
+pub(crate) fn category_subtree(id: i32) -> SimpleExpr {
+    let ids = Query::select()
+        .column(Categories::Id)
+        .from(Alias::new("tree"))
+        .to_owned()
+        .with(walk_tree(id, false));
+
+    SimpleExpr::SubQuery(None, Box::new(ids.into_sub_query_statement()))
+}
+
 pub struct CategoryRepository {
     db_pool: ConnectionPool,
 }
@@ -44,9 +167,11 @@ impl CategoryRepositoryTrait for CategoryRepository {
         
         let mut select_query = Query::select();
         select_query
-            .columns([Categories::Id, Categories::Name])
+            .columns(CATEGORY_COLUMNS)
+            .expr_as(post_count(Categories::Table), Alias::new("post_count"))
             .from(Categories::Table)
-            .order_by(Categories::Id, Order::Asc)
+            .order_by(Categories::SortOrder, Order::Asc)
+            .order_by(Categories::Name, Order::Asc)
             .limit(page_size as u64)
             .offset(offset as u64);
             
@@ -111,7 +236,8 @@ impl CategoryRepositoryTrait for CategoryRepository {
         info!("Finding category by id: {}", id);
 
         let query = Query::select()
-            .columns([Categories::Id, Categories::Name])
+            .columns(CATEGORY_COLUMNS)
+            .expr_as(post_count(Categories::Table), Alias::new("post_count"))
             .from(Categories::Table)
             .and_where(Expr::col(Categories::Id).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -133,14 +259,104 @@ impl CategoryRepositoryTrait for CategoryRepository {
         }
     }
 
-    async fn create(&self, input: &CreateCategoryRequest) -> Result<Category, AppError> {
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<Category>, AppError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (sql, values) = Query::select()
+            .columns(CATEGORY_COLUMNS)
+            .expr_as(post_count(Categories::Table), Alias::new("post_count"))
+            .from(Categories::Table)
+            .and_where(Expr::col(Categories::Id).is_in(ids.iter().copied()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let categories = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(categories)
+    }
+
+    async fn find_every(&self) -> Result<Vec<Category>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(CATEGORY_COLUMNS)
+            .expr_as(post_count(Categories::Table), Alias::new("post_count"))
+            .from(Categories::Table)
+            .order_by(Categories::SortOrder, Order::Asc)
+            .order_by(Categories::Name, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let categories = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(categories)
+    }
+
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<Category>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(CATEGORY_COLUMNS)
+            .expr_as(post_count(Categories::Table), Alias::new("post_count"))
+            .from(Categories::Table)
+            .and_where(Expr::col(Categories::Slug).eq(slug))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let category = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Category>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(CATEGORY_COLUMNS)
+            .from(Categories::Table)
+            .and_where(Expr::expr(Func::lower(Expr::col(Categories::Name))).eq(name.to_lowercase()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let category = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn find_ancestors(&self, id: i32) -> Result<Vec<Category>, AppError> {
+        let (sql, values) = select_tree()
+            .with(walk_tree(id, true))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ancestors = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(ancestors)
+    }
+
+    async fn create(&self, input: &CreateCategoryRequest, slug: &str) -> Result<Category, AppError> {
         info!("Creating new category: {:?}", input.name);
 
         let insert = Query::insert()
             .into_table(Categories::Table)
-            .columns([Categories::Name])
-            .values_panic([input.name.clone().into()])
-            .returning_all()  
+            .columns([
+                Categories::Name,
+                Categories::ParentId,
+                Categories::Slug,
+                Categories::Description,
+                Categories::CoverImage,
+                Categories::SortOrder,
+            ])
+            .values_panic([
+                input.name.clone().into(),
+                input.parent_id.into(),
+                slug.into(),
+                input.description.clone().into(),
+                input.cover_image.clone().into(),
+                input.sort_order.into(),
+            ])
+            .returning(Query::returning().columns(CATEGORY_COLUMNS))
             .build_sqlx(PostgresQueryBuilder);
 
         let (sql, values) = insert;
@@ -148,7 +364,7 @@ impl CategoryRepositoryTrait for CategoryRepository {
         let result = sqlx::query_as_with::<_, Category, _>(&sql, values)
             .fetch_one(&self.db_pool)
             .await
-            .map_err(AppError::SqlxError)?;
+            .map_err(map_duplicate)?;
 
         info!("New category inserted with ID: {}", result.id);
         
@@ -161,25 +377,91 @@ impl CategoryRepositoryTrait for CategoryRepository {
             .ok_or(AppError::ValidationError("ID is required".into()))?;
     
         info!("Updating category ID: {} with name: {:?}", id, input.name);
+
+        let mut tx = self.db_pool.begin().await?;
+
+        if let Some(Some(parent_id)) = input.parent_id {
+            // Serialize moves so two of them cannot close a loop together.
+            sqlx::query("LOCK TABLE categories IN SHARE ROW EXCLUSIVE MODE")
+                .execute(&mut *tx)
+                .await?;
+
+            let (sql, values) = select_tree()
+                .with(walk_tree(parent_id, true))
+                .build_sqlx(PostgresQueryBuilder);
+
+            let ancestors = sqlx::query_as_with::<_, Category, _>(&sql, values)
+                .fetch_all(&mut *tx)
+                .await?;
+
+            if ancestors.is_empty() {
+                return Err(AppError::NotFound(format!(
+                    "Parent category with id {} not found",
+                    parent_id
+                )));
+            }
+
+            if ancestors.iter().any(|ancestor| ancestor.id == id) {
+                return Err(AppError::ValidationError(
+                    "A category cannot be moved below itself or one of its descendants".into(),
+                ));
+            }
+        }
     
-        let update = Query::update()
+        let mut update = Query::update();
+        update
             .table(Categories::Table)
-            .values(vec![(
-                Categories::Name,
-                input.name.clone().unwrap_or_default().into(),
-            )])
-            .and_where(Expr::col(Categories::Id).eq(id))
-            .build_sqlx(PostgresQueryBuilder);
-    
-        let (sql, values) = update;
+            .value(Categories::Version, Expr::col(Categories::Version).add(1))
+            .and_where(Expr::col(Categories::Id).eq(id));
+
+        if let Some(name) = &input.name {
+            update.value(Categories::Name, name.clone());
+        }
+
+        if let Some(parent_id) = input.parent_id {
+            update.value(Categories::ParentId, parent_id);
+        }
+
+        if let Some(slug) = &input.slug {
+            update.value(Categories::Slug, slug.clone());
+        }
+
+        if let Some(description) = &input.description {
+            update.value(Categories::Description, description.clone());
+        }
+
+        if let Some(cover_image) = &input.cover_image {
+            update.value(Categories::CoverImage, cover_image.clone());
+        }
+
+        if let Some(sort_order) = input.sort_order {
+            update.value(Categories::SortOrder, sort_order);
+        }
+
+        if let Some(version) = input.version {
+            update.and_where(Expr::col(Categories::Version).eq(version));
+        }
+
+        let (sql, values) = update.build_sqlx(PostgresQueryBuilder);
     
         let res = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
-            .await?;
+            .execute(&mut *tx)
+            .await
+            .map_err(map_duplicate)?;
+
+        tx.commit().await?;
     
         if res.rows_affected() == 0 {
+            if self.find_by_id(id).await?.is_some() {
+                info!("Category ID: {} was modified concurrently", id);
+                return Err(AppError::PreconditionFailed(format!(
+                    "Category with id {} has been modified",
+                    id
+                )));
+            }
+
             info!("No category found to update with ID: {}", id);
-            return Err(AppError::SqlxError(sqlx::Error::RowNotFound));
+            return Err(AppError::NotFound(format!("Category with id {} not found", id)));
         }
     
         info!("Category ID: {} updated successfully", id);
@@ -200,14 +482,140 @@ impl CategoryRepositoryTrait for CategoryRepository {
 
         let result = sqlx::query_with(&sql, values)
             .execute(&self.db_pool)
-            .await?;
+            .await
+            .map_err(|e| map_still_in_use(e, id))?;
 
         if result.rows_affected() == 0 {
             info!("No category found to delete with ID: {}", id);
-            return Err(AppError::SqlxError(sqlx::Error::RowNotFound));
+            return Err(AppError::NotFound(format!("Category with id {} not found", id)));
         }
 
         info!("Category ID: {} deleted successfully", id);
         Ok(())
     }
+
+    async fn reassign(
+        &self,
+        id: i32,
+        target_id: i32,
+        adopt_children: bool,
+    ) -> Result<(u64, u64), AppError> {
+        if id == target_id {
+            return Err(AppError::ValidationError(
+                "A category cannot be merged into itself".into(),
+            ));
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        // Same lock as a move, so the tree cannot change under the re-parenting.
+        sqlx::query("LOCK TABLE categories IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await?;
+
+        let (sql, values) = select_tree()
+            .with(walk_tree(target_id, true))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let target_ancestors = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        if target_ancestors.is_empty() {
+            return Err(AppError::NotFound(format!(
+                "Category with id {} not found",
+                target_id
+            )));
+        }
+
+        let (sql, values) = Query::select()
+            .columns(CATEGORY_COLUMNS)
+            .from(Categories::Table)
+            .and_where(Expr::col(Categories::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let source = sqlx::query_as_with::<_, Category, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Category with id {} not found", id)))?;
+
+        if adopt_children && target_ancestors.iter().any(|ancestor| ancestor.id == id) {
+            return Err(AppError::ValidationError(
+                "A category cannot be merged into one of its descendants".into(),
+            ));
+        }
+
+        let (sql, values) = Query::update()
+            .table(Posts::Table)
+            .value(Posts::CategoryId, target_id)
+            .and_where(Expr::col(Posts::CategoryId).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let moved_posts = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        // Merged children follow the posts; otherwise they keep their place
+        // in the tree by moving up a level.
+        let new_parent = if adopt_children {
+            Some(target_id)
+        } else {
+            source.parent_id
+        };
+
+        let (sql, values) = Query::update()
+            .table(Categories::Table)
+            .values([
+                (Categories::ParentId, new_parent.into()),
+                (Categories::Version, Expr::col(Categories::Version).add(1)),
+            ])
+            .and_where(Expr::col(Categories::ParentId).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let moved_categories = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        // Followers of a merged category follow the target from now on;
+        // anyone already following both keeps their existing follow.
+        if adopt_children {
+            let (sql, values) = Query::update()
+                .table(Follows::Table)
+                .value(Follows::CategoryId, target_id)
+                .and_where(Expr::col(Follows::CategoryId).eq(id))
+                .and_where(
+                    Expr::col(Follows::FollowerId).not_in_subquery(
+                        Query::select()
+                            .column(Follows::FollowerId)
+                            .from(Follows::Table)
+                            .and_where(Expr::col(Follows::CategoryId).eq(target_id))
+                            .to_owned(),
+                    ),
+                )
+                .build_sqlx(PostgresQueryBuilder);
+
+            sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+        }
+
+        let (sql, values) = Query::delete()
+            .from_table(Categories::Table)
+            .and_where(Expr::col(Categories::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| map_still_in_use(e, id))?;
+
+        tx.commit().await?;
+
+        info!(
+            "Category ID: {} removed into {}: {} post(s) and {} subcategory(ies) moved",
+            id, target_id, moved_posts, moved_categories
+        );
+
+        Ok((moved_posts, moved_categories))
+    }
 }
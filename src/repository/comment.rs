@@ -1,14 +1,38 @@
 
 use async_trait::async_trait;
-use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query::{
+    Alias, CommonTableExpression, Condition, Expr, Func, JoinType, Order, PostgresQueryBuilder,
+    Query, SimpleExpr, UnionType, WithClause,
+};
 use sea_query_binder::SqlxBinder;
 
 use crate::config::ConnectionPool;
-use crate::domain::{CreateCommentRequest,  UpdateCommentRequest};
+use crate::domain::{
+    CommentSort, CommentStatus, CreateCommentRequest, FindCommentTreeRequest, FindCommentsRequest, PostReader,
+    UpdateCommentRequest,
+};
 use crate::utils::AppError;
 use crate::abstract_trait::CommentRepositoryTrait;
+use crate::repository::block::visible_to;
+use crate::repository::posts::{readable_by, unlocked_for};
 use crate::schema::comment::Comments;
-use crate::model::comment::Comment;
+use crate::schema::posts::Posts;
+use crate::schema::reaction::Reactions;
+use crate::model::comment::{Comment, CommentNode, RankedComment};
+
+pub(crate) const COMMENT_COLUMNS: [Comments; 11] = [
+    Comments::Id,
+    Comments::IdPostComment,
+    Comments::UserNameComment,
+    Comments::Comment,
+    Comments::Version,
+    Comments::ParentId,
+    Comments::Depth,
+    Comments::IsDeleted,
+    Comments::UserId,
+    Comments::CreatedAt,
+    Comments::Status,
+];
 
 pub struct CommentRepository {
     db_pool: ConnectionPool,
@@ -23,34 +47,126 @@ impl CommentRepository {
 
 #[async_trait]
 impl CommentRepositoryTrait for CommentRepository {
-    async fn find_all(&self) -> Result<Vec<Comment>, AppError> {
-        let query = Query::select()
-            .columns([
-                Comments::Id,
-                Comments::IdPostComment,
-                Comments::UserNameComment,
-                Comments::Comment,
-            ])
+    async fn find_paginated(
+        &self,
+        req: &FindCommentsRequest,
+        status: CommentStatus,
+        after: Option<(i64, i32)>,
+    ) -> Result<(Vec<RankedComment>, i64), AppError> {
+        let score = SimpleExpr::SubQuery(
+            None,
+            Box::new(
+                Query::select()
+                    .expr(Func::count(Expr::col((Reactions::Table, Reactions::Id))))
+                    .from(Reactions::Table)
+                    .and_where(Expr::col((Reactions::Table, Reactions::TargetType)).eq("comment"))
+                    .and_where(
+                        Expr::col((Reactions::Table, Reactions::TargetId))
+                            .equals((Comments::Table, Comments::Id)),
+                    )
+                    .to_owned()
+                    .into_sub_query_statement(),
+            ),
+        );
+
+        let mut filter = Query::select();
+        filter
             .from(Comments::Table)
-            .build_sqlx(PostgresQueryBuilder);
+            .and_where(Expr::col((Comments::Table, Comments::IsDeleted)).eq(false))
+            .and_where(Expr::col((Comments::Table, Comments::Status)).eq(status.as_str()));
 
-        let (sql, values) = query;
+        if let Some(post_id) = req.post_id {
+            filter.and_where(Expr::col((Comments::Table, Comments::IdPostComment)).eq(post_id));
+        }
+
+        if let Some(author_id) = req.author_id {
+            filter.and_where(Expr::col((Comments::Table, Comments::UserId)).eq(author_id));
+        }
+
+        if let Some(viewer_id) = req.viewer_id {
+            filter.cond_where(visible_to(Expr::col((Comments::Table, Comments::UserId)), viewer_id));
+
+            // Comments are only as visible as their post.
+            filter
+                .inner_join(
+                    Posts::Table,
+                    Expr::col((Posts::Table, Posts::Id))
+                        .equals((Comments::Table, Comments::IdPostComment)),
+                )
+                .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false));
+
+            if let Some(condition) = readable_by(PostReader::User(viewer_id)) {
+                filter.cond_where(condition);
+            }
+
+            if !req.post_unlocked {
+                filter.cond_where(unlocked_for(viewer_id));
+            }
+        }
+
+        let mut count_query = filter.clone();
+        count_query.expr(Func::count(Expr::col((Comments::Table, Comments::Id))));
+
+        let mut select_query = filter;
+        select_query
+            .columns(COMMENT_COLUMNS.map(|col| (Comments::Table, col)))
+            .expr_as(score.clone(), Alias::new("score"))
+            .limit(req.page_size as u64);
+
+        let id = Expr::col((Comments::Table, Comments::Id));
 
-        let results = sqlx::query_as_with::<_, Comment, _>(&sql, values)
+        match req.sort {
+            CommentSort::Newest => {
+                if let Some((_, after_id)) = after {
+                    select_query.and_where(id.clone().lt(after_id));
+                }
+                select_query.order_by((Comments::Table, Comments::Id), Order::Desc);
+            }
+            CommentSort::Oldest => {
+                if let Some((_, after_id)) = after {
+                    select_query.and_where(id.clone().gt(after_id));
+                }
+                select_query.order_by((Comments::Table, Comments::Id), Order::Asc);
+            }
+            CommentSort::Top => {
+                if let Some((after_score, after_id)) = after {
+                    select_query.cond_where(
+                        Condition::any()
+                            .add(Expr::expr(score.clone()).lt(after_score))
+                            .add(
+                                Condition::all()
+                                    .add(Expr::expr(score.clone()).eq(after_score))
+                                    .add(id.clone().lt(after_id)),
+                            ),
+                    );
+                }
+                select_query
+                    .order_by_expr(score, Order::Desc)
+                    .order_by((Comments::Table, Comments::Id), Order::Desc);
+            }
+        }
+
+        if after.is_none() {
+            select_query.offset(((req.page - 1) * req.page_size) as u64);
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+
+        let comments = sqlx::query_as_with::<_, RankedComment, _>(&sql, values)
             .fetch_all(&self.db_pool)
             .await?;
 
-        Ok(results)
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((comments, total.0))
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<Comment>, AppError> {
         let query = Query::select()
-            .columns([
-                Comments::Id,
-                Comments::IdPostComment,
-                Comments::UserNameComment,
-                Comments::Comment,
-            ])
+            .columns(COMMENT_COLUMNS)
             .from(Comments::Table)
             .and_where(Expr::col(Comments::Id).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -64,19 +180,203 @@ impl CommentRepositoryTrait for CommentRepository {
         Ok(result)
     }
 
-    async fn create(&self, input: &CreateCommentRequest) -> Result<Comment, AppError> {
+    async fn find_by_post(
+        &self,
+        post_id: i32,
+        page: i32,
+        page_size: i32,
+        viewer_id: Option<i32>,
+    ) -> Result<(Vec<Comment>, i64), AppError> {
+        let offset = (page - 1) * page_size;
+
+        let mut filter = Query::select();
+        filter
+            .from(Comments::Table)
+            .and_where(Expr::col(Comments::IdPostComment).eq(post_id))
+            .and_where(Expr::col(Comments::Status).eq(CommentStatus::Approved.as_str()));
+
+        if let Some(viewer_id) = viewer_id {
+            filter.cond_where(visible_to(Expr::col((Comments::Table, Comments::UserId)), viewer_id));
+        }
+
+        let (sql, values) = filter
+            .clone()
+            .columns(COMMENT_COLUMNS)
+            .order_by(Comments::Id, Order::Asc)
+            .offset(offset as u64)
+            .limit(page_size as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let comments = sqlx::query_as_with::<_, Comment, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = filter
+            .expr(Func::count(Expr::col(Comments::Id)))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((comments, total.0))
+    }
+
+    async fn find_tree(
+        &self,
+        post_id: i32,
+        parent_id: Option<i32>,
+        req: &FindCommentTreeRequest,
+        max_depth: i32,
+    ) -> Result<(Vec<CommentNode>, i64), AppError> {
+        let offset = (req.page - 1) * req.page_size;
+        let tree = Alias::new("comment_tree");
+        let replies = Alias::new("replies");
+
+        let mut first_level = Query::select();
+        first_level
+            .column(Comments::Id)
+            .from(Comments::Table)
+            .and_where(Expr::col(Comments::IdPostComment).eq(post_id))
+            .and_where(Expr::col(Comments::Status).eq(CommentStatus::Approved.as_str()));
+
+        match parent_id {
+            Some(parent_id) => first_level.and_where(Expr::col(Comments::ParentId).eq(parent_id)),
+            None => first_level.and_where(Expr::col(Comments::ParentId).is_null()),
+        };
+
+        // Hiding an author hides the replies below their comments too.
+        let visible = || match req.viewer_id {
+            Some(viewer_id) => visible_to(Expr::col((Comments::Table, Comments::UserId)), viewer_id),
+            None => Condition::all(),
+        };
+
+        first_level.cond_where(visible());
+
+        let mut count_query = first_level.clone();
+        count_query.clear_selects().expr(Func::count(Expr::col(Comments::Id)));
+
+        first_level
+            .order_by(Comments::Id, Order::Asc)
+            .offset(offset as u64)
+            .limit(req.page_size as u64);
+
+        // Anchor: one page of the first level. Recursive step: the first
+        // `replies_page_size` replies of every node already in the tree.
+        let anchor = Query::select()
+            .columns(COMMENT_COLUMNS.map(|col| (Comments::Table, col)))
+            .from(Comments::Table)
+            .and_where(Expr::col((Comments::Table, Comments::Id)).in_subquery(first_level))
+            .union(
+                UnionType::All,
+                Query::select()
+                    .columns(COMMENT_COLUMNS.map(|col| (replies.clone(), col)))
+                    .from(tree.clone())
+                    .join_lateral(
+                        JoinType::InnerJoin,
+                        Query::select()
+                            .columns(COMMENT_COLUMNS)
+                            .from(Comments::Table)
+                            .and_where(
+                                Expr::col((Comments::Table, Comments::ParentId))
+                                    .equals((tree.clone(), Comments::Id)),
+                            )
+                            .and_where(
+                                Expr::col((Comments::Table, Comments::Status))
+                                    .eq(CommentStatus::Approved.as_str()),
+                            )
+                            .cond_where(visible())
+                            .order_by(Comments::Id, Order::Asc)
+                            .limit(req.replies_page_size as u64)
+                            .to_owned(),
+                        replies.clone(),
+                        Expr::value(true),
+                    )
+                    .and_where(Expr::col((tree.clone(), Comments::Depth)).lt(max_depth))
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let with_clause = WithClause::new()
+            .recursive(true)
+            .cte(
+                CommonTableExpression::new()
+                    .query(anchor)
+                    .table_name(tree.clone())
+                    .to_owned(),
+            )
+            .to_owned();
+
+        let reply_count = Query::select()
+            .expr(Func::count(Expr::col((replies.clone(), Comments::Id))))
+            .from_as(Comments::Table, replies.clone())
+            .and_where(
+                Expr::col((replies.clone(), Comments::ParentId)).equals((tree.clone(), Comments::Id)),
+            )
+            .and_where(
+                Expr::col((replies.clone(), Comments::Status)).eq(CommentStatus::Approved.as_str()),
+            )
+            .cond_where(match req.viewer_id {
+                Some(viewer_id) => {
+                    visible_to(Expr::col((replies.clone(), Comments::UserId)), viewer_id)
+                }
+                None => Condition::all(),
+            })
+            .to_owned();
+
+        let (sql, values) = Query::select()
+            .columns(COMMENT_COLUMNS.map(|col| (tree.clone(), col)))
+            .expr_as(
+                SimpleExpr::SubQuery(None, Box::new(reply_count.into_sub_query_statement())),
+                Alias::new("reply_count"),
+            )
+            .from(tree.clone())
+            .order_by((tree.clone(), Comments::Depth), Order::Asc)
+            .order_by((tree, Comments::Id), Order::Asc)
+            .to_owned()
+            .with(with_clause)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let nodes = sqlx::query_as_with::<_, CommentNode, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((nodes, total.0))
+    }
+
+    async fn create(
+        &self,
+        input: &CreateCommentRequest,
+        user_name: &str,
+        depth: i32,
+        status: CommentStatus,
+    ) -> Result<Comment, AppError> {
         let insert = Query::insert()
             .into_table(Comments::Table)
             .columns([
                 Comments::IdPostComment,
                 Comments::UserNameComment,
                 Comments::Comment,
+                Comments::ParentId,
+                Comments::Depth,
+                Comments::UserId,
+                Comments::Status,
             ])
             .values_panic([
                 input.id_post_comment.into(),
-                input.user_name_comment.clone().into(),
+                user_name.into(),
                 input.comment.clone().into(),
+                input.parent_id.into(),
+                depth.into(),
+                input.user_id.into(),
+                status.as_str().into(),
             ])
+            .returning_col(Comments::Id)
             .build_sqlx(PostgresQueryBuilder);
 
         let (sql, values) = insert;
@@ -89,28 +389,52 @@ impl CommentRepositoryTrait for CommentRepository {
             .ok_or_else(|| AppError::SqlxError(sqlx::Error::RowNotFound))
     }
 
-    async fn update(&self, input: &UpdateCommentRequest) -> Result<Comment, AppError> {
+    async fn update(
+        &self,
+        input: &UpdateCommentRequest,
+        status: Option<CommentStatus>,
+    ) -> Result<Comment, AppError> {
         let id = input
-            .id_post_comment
+            .id
             .ok_or_else(|| AppError::ValidationError("ID is required".into()))?;
 
-        let update = Query::update()
+        let mut update = Query::update();
+        update
             .table(Comments::Table)
             .values(vec![
-                (Comments::UserNameComment, input.user_name_comment.clone().into()),
                 (Comments::Comment, input.comment.clone().into()),
+                (Comments::Version, Expr::col(Comments::Version).add(1)),
             ])
             .and_where(Expr::col(Comments::Id).eq(id))
-            .build_sqlx(PostgresQueryBuilder);
+            .and_where(Expr::col(Comments::IsDeleted).eq(false));
 
-        let (sql, values) = update;
+        if let Some(status) = status {
+            update.value(Comments::Status, status.as_str());
+        }
+
+        if let Some(version) = input.version {
+            update.and_where(Expr::col(Comments::Version).eq(version));
+        }
+
+        let (sql, values) = update.build_sqlx(PostgresQueryBuilder);
 
         let result = sqlx::query_with(&sql, values)
             .execute(&self.db_pool)
             .await?;
 
         if result.rows_affected() == 0 {
-            return Err(AppError::SqlxError(sqlx::Error::RowNotFound));
+            if self
+                .find_by_id(id)
+                .await?
+                .is_some_and(|comment| !comment.is_deleted)
+            {
+                return Err(AppError::PreconditionFailed(format!(
+                    "Comment with id {} has been modified",
+                    id
+                )));
+            }
+
+            return Err(AppError::NotFound(format!("Comment with id {} not found", id)));
         }
 
         self.find_by_id(id)
@@ -119,21 +443,51 @@ impl CommentRepositoryTrait for CommentRepository {
     }
 
     async fn delete(&self, id: i32) -> Result<(), AppError> {
-        let delete = Query::delete()
-            .from_table(Comments::Table)
-            .and_where(Expr::col(Comments::Id).eq(id))
-            .build_sqlx(PostgresQueryBuilder);
+        let mut tx = self.db_pool.begin().await?;
 
-        let (sql, values) = delete;
+        let (sql, values) = Query::select()
+            .expr(Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(Comments::Table)
+                    .and_where(Expr::col(Comments::ParentId).eq(id))
+                    .to_owned(),
+            ))
+            .build_sqlx(PostgresQueryBuilder);
 
-        let result = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
+        let (has_replies,): (bool,) = sqlx::query_as_with(&sql, values)
+            .fetch_one(&mut *tx)
             .await?;
 
+        // A comment with replies is tombstoned so the thread below it stays
+        // reachable; leaves are removed outright.
+        let (sql, values) = if has_replies {
+            Query::update()
+                .table(Comments::Table)
+                .values(vec![
+                    (Comments::UserNameComment, "".into()),
+                    (Comments::Comment, "".into()),
+                    (Comments::IsDeleted, true.into()),
+                    (Comments::Version, Expr::col(Comments::Version).add(1)),
+                ])
+                .and_where(Expr::col(Comments::Id).eq(id))
+                .and_where(Expr::col(Comments::IsDeleted).eq(false))
+                .build_sqlx(PostgresQueryBuilder)
+        } else {
+            Query::delete()
+                .from_table(Comments::Table)
+                .and_where(Expr::col(Comments::Id).eq(id))
+                .build_sqlx(PostgresQueryBuilder)
+        };
+
+        let result = sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
         if result.rows_affected() == 0 {
-            return Err(AppError::SqlxError(sqlx::Error::RowNotFound));
+            return Err(AppError::NotFound(format!("Comment with id {} not found", id)));
         }
 
+        tx.commit().await?;
+
         Ok(())
     }
 }
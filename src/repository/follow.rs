@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use sea_query::{Expr, Func, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::info;
+
+use crate::abstract_trait::FollowRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::{FindFollowsRequest, FollowTarget};
+use crate::schema::follow::Follows;
+use crate::utils::AppError;
+
+pub(crate) fn target_column(target: FollowTarget) -> Follows {
+    match target {
+        FollowTarget::User => Follows::UserId,
+        FollowTarget::Category => Follows::CategoryId,
+    }
+}
+
+pub struct FollowRepository {
+    db_pool: ConnectionPool,
+}
+
+impl FollowRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl FollowRepositoryTrait for FollowRepository {
+    async fn follow(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        target_id: i32,
+    ) -> Result<bool, AppError> {
+        let (sql, values) = Query::insert()
+            .into_table(Follows::Table)
+            .columns([Follows::FollowerId, target_column(target)])
+            .values_panic([follower_id.into(), target_id.into()])
+            .on_conflict(
+                OnConflict::columns([Follows::FollowerId, target_column(target)])
+                    .target_and_where(Expr::col(target_column(target)).is_not_null())
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        info!("User {} followed {:?} {}", follower_id, target, target_id);
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn unfollow(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        target_id: i32,
+    ) -> Result<bool, AppError> {
+        let (sql, values) = Query::delete()
+            .from_table(Follows::Table)
+            .and_where(Expr::col(Follows::FollowerId).eq(follower_id))
+            .and_where(Expr::col(target_column(target)).eq(target_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_followers(&self, target: FollowTarget, target_id: i32) -> Result<i64, AppError> {
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(Follows::Id)))
+            .from(Follows::Table)
+            .and_where(Expr::col(target_column(target)).eq(target_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let count: (i64,) = sqlx::query_as_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    async fn count_following(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+    ) -> Result<i64, AppError> {
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(Follows::Id)))
+            .from(Follows::Table)
+            .and_where(Expr::col(Follows::FollowerId).eq(follower_id))
+            .and_where(Expr::col(target_column(target)).is_not_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let count: (i64,) = sqlx::query_as_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    async fn find_followers(
+        &self,
+        target: FollowTarget,
+        target_id: i32,
+        req: &FindFollowsRequest,
+    ) -> Result<(Vec<i32>, i64), AppError> {
+        let (sql, values) = Query::select()
+            .column(Follows::FollowerId)
+            .from(Follows::Table)
+            .and_where(Expr::col(target_column(target)).eq(target_id))
+            .order_by(Follows::Id, Order::Desc)
+            .offset(((req.page - 1) * req.page_size) as u64)
+            .limit(req.page_size as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok((ids, self.count_followers(target, target_id).await?))
+    }
+
+    async fn find_following(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        req: &FindFollowsRequest,
+    ) -> Result<(Vec<i32>, i64), AppError> {
+        let (sql, values) = Query::select()
+            .column(target_column(target))
+            .from(Follows::Table)
+            .and_where(Expr::col(Follows::FollowerId).eq(follower_id))
+            .and_where(Expr::col(target_column(target)).is_not_null())
+            .order_by(Follows::Id, Order::Desc)
+            .offset(((req.page - 1) * req.page_size) as u64)
+            .limit(req.page_size as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok((ids, self.count_following(follower_id, target).await?))
+    }
+}
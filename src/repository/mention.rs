@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use sea_query::{Condition, Expr, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+
+use crate::abstract_trait::MentionRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::ReactionTarget;
+use crate::schema::comment::Comments;
+use crate::schema::mention::Mentions;
+use crate::schema::user::Users;
+use crate::utils::AppError;
+
+pub struct MentionRepository {
+    db_pool: ConnectionPool,
+}
+
+impl MentionRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl MentionRepositoryTrait for MentionRepository {
+    async fn resolve_handles(&self, handles: &[String]) -> Result<Vec<i32>, AppError> {
+        if handles.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (sql, values) = Query::select()
+            .column(Users::Id)
+            .from(Users::Table)
+            // `firstname.lastname` keeps mentions written before usernames
+            // working, unless that handle is now somebody's username.
+            .cond_where(
+                Condition::any()
+                    .add(Expr::col(Users::Username).is_in(handles.iter().cloned()))
+                    .add(
+                        Condition::all()
+                            .add(
+                                Expr::expr(Expr::cust(
+                                    r#"LOWER("users"."firstname" || '.' || "users"."lastname")"#,
+                                ))
+                                .is_in(handles.iter().cloned()),
+                            )
+                            .add(Expr::cust(
+                                r#"NOT EXISTS (SELECT 1 FROM "users" AS "owner" WHERE "owner"."username" = LOWER("users"."firstname" || '.' || "users"."lastname"))"#,
+                            )),
+                    ),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let ids = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(ids)
+    }
+
+    async fn replace(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        user_ids: &[i32],
+    ) -> Result<(Vec<i32>, Vec<i32>), AppError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let mut delete = Query::delete();
+        delete
+            .from_table(Mentions::Table)
+            .and_where(Expr::col(Mentions::TargetType).eq(target.as_str()))
+            .and_where(Expr::col(Mentions::TargetId).eq(target_id))
+            .returning_col(Mentions::UserId);
+
+        if !user_ids.is_empty() {
+            delete.and_where(Expr::col(Mentions::UserId).is_not_in(user_ids.iter().copied()));
+        }
+
+        let (sql, values) = delete.build_sqlx(PostgresQueryBuilder);
+        let removed: Vec<i32> = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let added: Vec<i32> = if user_ids.is_empty() {
+            Vec::new()
+        } else {
+            let mut insert = Query::insert();
+            insert
+                .into_table(Mentions::Table)
+                .columns([Mentions::TargetType, Mentions::TargetId, Mentions::UserId]);
+
+            for user_id in user_ids {
+                insert.values_panic([
+                    target.as_str().into(),
+                    target_id.into(),
+                    (*user_id).into(),
+                ]);
+            }
+
+            let (sql, values) = insert
+                .on_conflict(
+                    OnConflict::columns([Mentions::TargetType, Mentions::TargetId, Mentions::UserId])
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .returning_col(Mentions::UserId)
+                .build_sqlx(PostgresQueryBuilder);
+
+            sqlx::query_scalar_with(&sql, values)
+                .fetch_all(&mut *tx)
+                .await?
+        };
+
+        tx.commit().await?;
+
+        Ok((added, removed))
+    }
+
+    async fn delete_for_post(&self, post_id: i32) -> Result<(), AppError> {
+        let (sql, values) = Query::delete()
+            .from_table(Mentions::Table)
+            .cond_where(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(Expr::col(Mentions::TargetType).eq(ReactionTarget::Post.as_str()))
+                            .add(Expr::col(Mentions::TargetId).eq(post_id)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                Expr::col(Mentions::TargetType)
+                                    .eq(ReactionTarget::Comment.as_str()),
+                            )
+                            .add(
+                                Expr::col(Mentions::TargetId).in_subquery(
+                                    Query::select()
+                                        .column(Comments::Id)
+                                        .from(Comments::Table)
+                                        .and_where(Expr::col(Comments::IdPostComment).eq(post_id))
+                                        .to_owned(),
+                                ),
+                            ),
+                    ),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&self.db_pool).await?;
+
+        Ok(())
+    }
+}
@@ -1,9 +1,33 @@
+mod block;
+mod bookmark;
 mod category;
+mod follow;
+mod mention;
+mod moderation;
+mod notification;
 mod posts;
 mod comment;
+mod privacy;
+mod reaction;
+mod report;
+mod spam;
 mod user;
+mod user_token;
+mod view;
 
+pub use self::block::BlockRepository;
+pub use self::bookmark::BookmarkRepository;
 pub use self::category::CategoryRepository;
+pub use self::follow::FollowRepository;
+pub use self::mention::MentionRepository;
+pub use self::moderation::ModerationRepository;
+pub use self::notification::NotificationRepository;
 pub use self::posts::PostRepository;
 pub use self::comment::CommentRepository;
-pub use self::user::UserRepository;
\ No newline at end of file
+pub use self::privacy::PrivacyRepository;
+pub use self::reaction::ReactionRepository;
+pub use self::report::ReportRepository;
+pub use self::spam::SpamRepository;
+pub use self::user::UserRepository;
+pub use self::user_token::UserTokenRepository;
+pub use self::view::PostViewRepository;
\ No newline at end of file
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use sea_query::{Expr, Func, JoinType, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+
+use crate::abstract_trait::ModerationRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::{CommentPolicy, CommentStatus};
+use crate::model::comment::Comment;
+use crate::schema::category::Categories;
+use crate::schema::comment::Comments;
+use crate::schema::posts::Posts;
+use crate::utils::AppError;
+
+pub struct ModerationRepository {
+    db_pool: ConnectionPool,
+}
+
+impl ModerationRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ModerationRepositoryTrait for ModerationRepository {
+    async fn find_comment_policy(&self, post_id: i32) -> Result<Option<CommentPolicy>, AppError> {
+        let (sql, values) = Query::select()
+            .expr(Func::coalesce([
+                Expr::col((Posts::Table, Posts::CommentPolicy)).into(),
+                Expr::col((Categories::Table, Categories::CommentPolicy)).into(),
+            ]))
+            .from(Posts::Table)
+            .join(
+                JoinType::LeftJoin,
+                Categories::Table,
+                Expr::col((Categories::Table, Categories::Id))
+                    .equals((Posts::Table, Posts::CategoryId)),
+            )
+            .and_where(Expr::col((Posts::Table, Posts::Id)).eq(post_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let policy: Option<(Option<String>,)> = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(policy.map(|(policy,)| {
+            policy
+                .as_deref()
+                .map(CommentPolicy::parse)
+                .unwrap_or(CommentPolicy::Open)
+        }))
+    }
+
+    async fn has_approved_comment(&self, user_id: i32) -> Result<bool, AppError> {
+        let (sql, values) = Query::select()
+            .expr(Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(Comments::Table)
+                    .and_where(Expr::col(Comments::UserId).eq(user_id))
+                    .and_where(Expr::col(Comments::Status).eq(CommentStatus::Approved.as_str()))
+                    .to_owned(),
+            ))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (exists,): (bool,) = sqlx::query_as_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok(exists)
+    }
+
+    async fn set_comment_status(
+        &self,
+        ids: &[i32],
+        status: CommentStatus,
+    ) -> Result<Vec<Comment>, AppError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (sql, values) = Query::update()
+            .table(Comments::Table)
+            .values([
+                (Comments::Status, status.as_str().into()),
+                (Comments::Version, Expr::col(Comments::Version).add(1)),
+            ])
+            .and_where(Expr::col(Comments::Id).is_in(ids.iter().copied()))
+            .and_where(Expr::col(Comments::Status).ne(status.as_str()))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let comments = sqlx::query_as_with::<_, Comment, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(comments)
+    }
+
+    async fn set_category_policy(
+        &self,
+        category_id: i32,
+        policy: CommentPolicy,
+    ) -> Result<bool, AppError> {
+        let (sql, values) = Query::update()
+            .table(Categories::Table)
+            .value(Categories::CommentPolicy, policy.as_str())
+            .and_where(Expr::col(Categories::Id).eq(category_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_post_policy(
+        &self,
+        post_id: i32,
+        policy: Option<CommentPolicy>,
+    ) -> Result<bool, AppError> {
+        let (sql, values) = Query::update()
+            .table(Posts::Table)
+            .value(
+                Posts::CommentPolicy,
+                policy.map(|policy| policy.as_str().to_string()),
+            )
+            .and_where(Expr::col(Posts::Id).eq(post_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,338 @@
+use async_trait::async_trait;
+use sea_query::{Condition, Expr, Func, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+
+use crate::abstract_trait::NotificationRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::{
+    CreateNotificationRequest, FindNotificationsRequest, NotificationKind, ReactionTarget,
+};
+use crate::model::notification::{Notification, NotificationPreferences};
+use crate::schema::comment::Comments;
+use crate::schema::notification::{NotificationPreferences as Preferences, Notifications};
+use crate::utils::AppError;
+
+const NOTIFICATION_COLUMNS: [Notifications; 8] = [
+    Notifications::Id,
+    Notifications::UserId,
+    Notifications::ActorId,
+    Notifications::Kind,
+    Notifications::TargetType,
+    Notifications::TargetId,
+    Notifications::ReadAt,
+    Notifications::CreatedAt,
+];
+
+const PREFERENCE_COLUMNS: [Preferences; 4] = [
+    Preferences::UserId,
+    Preferences::Mention,
+    Preferences::Comment,
+    Preferences::Reply,
+];
+
+pub struct NotificationRepository {
+    db_pool: ConnectionPool,
+}
+
+impl NotificationRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepositoryTrait for NotificationRepository {
+    async fn create_many(&self, inputs: &[CreateNotificationRequest]) -> Result<u64, AppError> {
+        if inputs.is_empty() {
+            return Ok(0);
+        }
+
+        let mut query = Query::insert();
+        query.into_table(Notifications::Table).columns([
+            Notifications::UserId,
+            Notifications::ActorId,
+            Notifications::Kind,
+            Notifications::TargetType,
+            Notifications::TargetId,
+        ]);
+
+        for input in inputs {
+            query.values_panic([
+                input.user_id.into(),
+                input.actor_id.into(),
+                input.kind.as_str().into(),
+                input.target_type.as_str().into(),
+                input.target_id.into(),
+            ]);
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_unread(
+        &self,
+        kinds: &[NotificationKind],
+        target: ReactionTarget,
+        target_id: i32,
+        user_ids: Option<&[i32]>,
+    ) -> Result<u64, AppError> {
+        if kinds.is_empty() || user_ids.is_some_and(|ids| ids.is_empty()) {
+            return Ok(0);
+        }
+
+        let mut query = Query::delete();
+        query
+            .from_table(Notifications::Table)
+            .and_where(Expr::col(Notifications::Kind).is_in(kinds.iter().map(|kind| kind.as_str())))
+            .and_where(Expr::col(Notifications::TargetType).eq(target.as_str()))
+            .and_where(Expr::col(Notifications::TargetId).eq(target_id))
+            .and_where(Expr::col(Notifications::ReadAt).is_null());
+
+        if let Some(user_ids) = user_ids {
+            query.and_where(Expr::col(Notifications::UserId).is_in(user_ids.iter().copied()));
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_for_post(&self, post_id: i32) -> Result<u64, AppError> {
+        let (sql, values) = Query::delete()
+            .from_table(Notifications::Table)
+            .cond_where(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(
+                                Expr::col(Notifications::TargetType)
+                                    .eq(ReactionTarget::Post.as_str()),
+                            )
+                            .add(Expr::col(Notifications::TargetId).eq(post_id)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(
+                                Expr::col(Notifications::TargetType)
+                                    .eq(ReactionTarget::Comment.as_str()),
+                            )
+                            .add(
+                                Expr::col(Notifications::TargetId).in_subquery(
+                                    Query::select()
+                                        .column(Comments::Id)
+                                        .from(Comments::Table)
+                                        .and_where(Expr::col(Comments::IdPostComment).eq(post_id))
+                                        .to_owned(),
+                                ),
+                            ),
+                    ),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Notification>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(NOTIFICATION_COLUMNS)
+            .from(Notifications::Table)
+            .and_where(Expr::col(Notifications::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let notification = sqlx::query_as_with::<_, Notification, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(notification)
+    }
+
+    async fn find_paginated(
+        &self,
+        user_id: i32,
+        req: &FindNotificationsRequest,
+    ) -> Result<(Vec<Notification>, i64), AppError> {
+        let offset = (req.page - 1) * req.page_size;
+
+        let mut select_query = Query::select();
+        select_query
+            .columns(NOTIFICATION_COLUMNS)
+            .from(Notifications::Table)
+            .and_where(Expr::col(Notifications::UserId).eq(user_id))
+            .order_by(Notifications::Id, Order::Desc)
+            .offset(offset as u64)
+            .limit(req.page_size as u64);
+
+        let mut count_query = Query::select();
+        count_query
+            .expr(Func::count(Expr::col(Notifications::Id)))
+            .from(Notifications::Table)
+            .and_where(Expr::col(Notifications::UserId).eq(user_id));
+
+        if req.unread_only {
+            select_query.and_where(Expr::col(Notifications::ReadAt).is_null());
+            count_query.and_where(Expr::col(Notifications::ReadAt).is_null());
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+
+        let notifications = sqlx::query_as_with::<_, Notification, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((notifications, total.0))
+    }
+
+    async fn count_unread(&self, user_id: i32) -> Result<i64, AppError> {
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(Notifications::Id)))
+            .from(Notifications::Table)
+            .and_where(Expr::col(Notifications::UserId).eq(user_id))
+            .and_where(Expr::col(Notifications::ReadAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let count: (i64,) = sqlx::query_as_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<bool, AppError> {
+        // Already-read notifications keep their original read time.
+        let (sql, values) = Query::update()
+            .table(Notifications::Table)
+            .value(
+                Notifications::ReadAt,
+                Func::coalesce([
+                    Expr::col(Notifications::ReadAt).into(),
+                    Expr::current_timestamp().into(),
+                ]),
+            )
+            .and_where(Expr::col(Notifications::Id).eq(id))
+            .and_where(Expr::col(Notifications::UserId).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn mark_all_read(&self, user_id: i32) -> Result<u64, AppError> {
+        let (sql, values) = Query::update()
+            .table(Notifications::Table)
+            .value(Notifications::ReadAt, Expr::current_timestamp())
+            .and_where(Expr::col(Notifications::UserId).eq(user_id))
+            .and_where(Expr::col(Notifications::ReadAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<NotificationPreferences>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(PREFERENCE_COLUMNS)
+            .from(Preferences::Table)
+            .and_where(Expr::col(Preferences::UserId).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let preferences = sqlx::query_as_with::<_, NotificationPreferences, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(preferences)
+    }
+
+    async fn save_preferences(
+        &self,
+        preferences: &NotificationPreferences,
+    ) -> Result<NotificationPreferences, AppError> {
+        let (sql, values) = Query::insert()
+            .into_table(Preferences::Table)
+            .columns(PREFERENCE_COLUMNS)
+            .values_panic([
+                preferences.user_id.into(),
+                preferences.mention.into(),
+                preferences.comment.into(),
+                preferences.reply.into(),
+            ])
+            .on_conflict(
+                OnConflict::column(Preferences::UserId)
+                    .update_columns([
+                        Preferences::Mention,
+                        Preferences::Comment,
+                        Preferences::Reply,
+                    ])
+                    .value(Preferences::UpdatedAt, Expr::current_timestamp())
+                    .to_owned(),
+            )
+            .returning(Query::returning().columns(PREFERENCE_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let saved = sqlx::query_as_with::<_, NotificationPreferences, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok(saved)
+    }
+
+    async fn recipients_accepting(
+        &self,
+        kind: NotificationKind,
+        user_ids: &[i32],
+    ) -> Result<Vec<i32>, AppError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let column = match kind {
+            NotificationKind::Mention => Preferences::Mention,
+            NotificationKind::Comment => Preferences::Comment,
+            NotificationKind::Reply => Preferences::Reply,
+        };
+
+        let (sql, values) = Query::select()
+            .column(Preferences::UserId)
+            .from(Preferences::Table)
+            .and_where(Expr::col(Preferences::UserId).is_in(user_ids.iter().copied()))
+            .and_where(Expr::col(column).eq(false))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let opted_out: Vec<i32> = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(user_ids
+            .iter()
+            .copied()
+            .filter(|user_id| !opted_out.contains(user_id))
+            .collect())
+    }
+}
@@ -1,17 +1,133 @@
 use crate::abstract_trait::PostsRepositoryTrait;
 use crate::config::ConnectionPool;
-use crate::domain::{CreatePostRequest, PostRelationResponse, UpdatePostRequest};
-use crate::utils::{AppError, generate_slug};
-
-use crate::model::posts::{Post, PostRelationModel};
-use crate::schema::comment::Comments;
+use crate::domain::{
+    CreatePostRequest, FindAllPostRequest, FollowTarget, PostReader, PostVisibility,
+    UpdatePostRequest, UserRole,
+};
+use crate::utils::AppError;
+
+use crate::model::posts::Post;
+use crate::repository::block::visible_to;
+use crate::repository::category::category_subtree;
+use crate::repository::follow::target_column;
+use crate::schema::bookmark::Bookmarks;
+use crate::schema::follow::Follows;
 use crate::schema::posts::Posts;
+use crate::schema::user::Users;
 
 use async_trait::async_trait;
-use sea_query::{Expr, Func, JoinType, PostgresQueryBuilder, Query};
+use sea_query::{
+    BinOper, Condition, Expr, Func, JoinType, Order, PostgresQueryBuilder, Query, SimpleExpr,
+};
 use sea_query_binder::SqlxBinder;
 use tracing::info;
 
+/// Every column `Post` is read from.
+pub(crate) const POST_COLUMNS: [Posts; 11] = [
+    Posts::Id,
+    Posts::Title,
+    Posts::Img,
+    Posts::Body,
+    Posts::CategoryId,
+    Posts::UserId,
+    Posts::UserName,
+    Posts::Version,
+    Posts::IsHidden,
+    Posts::Visibility,
+    Posts::PasswordHash,
+];
+
+fn visibility_in(levels: &[PostVisibility]) -> SimpleExpr {
+    Expr::col((Posts::Table, Posts::Visibility)).is_in(levels.iter().map(PostVisibility::as_str))
+}
+
+/// Moderators and admins act as editors and can read private posts.
+fn is_editor(user_id: i32) -> SimpleExpr {
+    Expr::val(user_id).in_subquery(
+        Query::select()
+            .column(Users::Id)
+            .from(Users::Table)
+            .and_where(
+                Expr::col(Users::Role)
+                    .is_in([UserRole::Moderator.as_str(), UserRole::Admin.as_str()]),
+            )
+            .to_owned(),
+    )
+}
+
+/// Posts `reader` may open by id; `None` for internal lookups, which see
+/// every post.
+pub(crate) fn readable_by(reader: PostReader) -> Option<Condition> {
+    match reader {
+        PostReader::Internal => None,
+        PostReader::Anonymous => Some(
+            Condition::all().add(visibility_in(&[PostVisibility::Public, PostVisibility::Unlisted])),
+        ),
+        PostReader::User(user_id) => Some(
+            Condition::any()
+                .add(visibility_in(&[
+                    PostVisibility::Public,
+                    PostVisibility::Unlisted,
+                    PostVisibility::Members,
+                ]))
+                .add(Expr::col((Posts::Table, Posts::UserId)).eq(user_id))
+                .add(is_editor(user_id)),
+        ),
+    }
+}
+
+/// Posts whose body `viewer_id` sees without an unlock token.
+pub(crate) fn unlocked_for(viewer_id: i32) -> Condition {
+    Condition::any()
+        .add(Expr::col((Posts::Table, Posts::PasswordHash)).is_null())
+        .add(Expr::col((Posts::Table, Posts::UserId)).eq(viewer_id))
+        .add(is_editor(viewer_id))
+}
+
+/// Posts listed to `viewer_id`: unlisted ones only show up for their author.
+pub(crate) fn listed_for(viewer_id: Option<i32>) -> Condition {
+    match viewer_id {
+        None => Condition::all().add(visibility_in(&[PostVisibility::Public])),
+        Some(viewer_id) => Condition::any()
+            .add(visibility_in(&[PostVisibility::Public, PostVisibility::Members]))
+            .add(Expr::col((Posts::Table, Posts::UserId)).eq(viewer_id))
+            .add(
+                Condition::all()
+                    .add(visibility_in(&[PostVisibility::Private]))
+                    .add(is_editor(viewer_id)),
+            ),
+    }
+}
+
+fn category_condition(category: Option<i32>, include_descendants: bool) -> Option<SimpleExpr> {
+    let category = category?;
+    let column = Expr::col((Posts::Table, Posts::CategoryId));
+
+    Some(if include_descendants {
+        column.binary(BinOper::In, category_subtree(category))
+    } else {
+        column.eq(category)
+    })
+}
+
+/// Posts whose author or category `follower_id` follows.
+fn followed_condition(follower_id: i32) -> Condition {
+    let followed = |post_column: Posts, target: FollowTarget| {
+        Expr::col((Posts::Table, post_column)).in_subquery(
+            Query::select()
+                .column(target_column(target))
+                .from(Follows::Table)
+                .and_where(Expr::col(Follows::FollowerId).eq(follower_id))
+                .and_where(Expr::col(target_column(target)).is_not_null())
+                .to_owned(),
+        )
+    };
+
+    Condition::any()
+        .add(followed(Posts::UserId, FollowTarget::User))
+        .add(followed(Posts::CategoryId, FollowTarget::Category))
+}
+
 pub struct PostRepository {
     db_pool: ConnectionPool,
 }
@@ -26,30 +142,40 @@ impl PostRepository {
 impl PostsRepositoryTrait for PostRepository {
     async fn get_all_posts(
         &self,
-        page: i32,
-        page_size: i32,
-        search: Option<String>,
+        req: &FindAllPostRequest,
+        viewer_id: Option<i32>,
     ) -> Result<(Vec<Post>, i64), AppError> {
-        let offset = (page - 1) * page_size;
+        let offset = (req.page - 1) * req.page_size;
 
         let mut select_query = Query::select();
         select_query
-            .columns([
-                (Posts::Table, Posts::Id),
-                (Posts::Table, Posts::Title),
-                (Posts::Table, Posts::Img),
-                (Posts::Table, Posts::Body),
-                (Posts::Table, Posts::CategoryId),
-                (Posts::Table, Posts::UserId),
-                (Posts::Table, Posts::UserName),
-            ])
+            .columns(POST_COLUMNS.map(|column| (Posts::Table, column)))
             .from(Posts::Table)
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false))
+            .order_by((Posts::Table, Posts::CreatedAt), Order::Desc)
+            .order_by((Posts::Table, Posts::Id), Order::Desc)
             .offset(offset as u64)
-            .limit(page_size as u64);
+            .limit(req.page_size as u64);
 
-        if let Some(ref s) = search {
+        if !req.search.is_empty() {
             select_query
-                .and_where(Expr::col((Posts::Table, Posts::Title)).like(format!("%{}%", s)));
+                .and_where(Expr::col((Posts::Table, Posts::Title)).like(format!("%{}%", req.search)));
+        }
+
+        let category = category_condition(req.category, req.include_descendants);
+
+        if let Some(ref condition) = category {
+            select_query.and_where(condition.clone());
+        }
+
+        if let Some(user_id) = req.user_id {
+            select_query.and_where(Expr::col((Posts::Table, Posts::UserId)).eq(user_id));
+        }
+
+        select_query.cond_where(listed_for(viewer_id));
+
+        if let Some(viewer_id) = viewer_id {
+            select_query.cond_where(visible_to(Expr::col((Posts::Table, Posts::UserId)), viewer_id));
         }
 
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
@@ -61,10 +187,25 @@ impl PostsRepositoryTrait for PostRepository {
         let mut count_query = Query::select();
         count_query
             .expr(Func::count(Expr::col(Posts::Id)))
-            .from(Posts::Table);
+            .from(Posts::Table)
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false));
 
-        if let Some(ref s) = search {
-            count_query.and_where(Expr::col((Posts::Table, Posts::Title)).like(format!("%{}%", s)));
+        if !req.search.is_empty() {
+            count_query.and_where(Expr::col((Posts::Table, Posts::Title)).like(format!("%{}%", req.search)));
+        }
+
+        if let Some(condition) = category {
+            count_query.and_where(condition);
+        }
+
+        if let Some(user_id) = req.user_id {
+            count_query.and_where(Expr::col((Posts::Table, Posts::UserId)).eq(user_id));
+        }
+
+        count_query.cond_where(listed_for(viewer_id));
+
+        if let Some(viewer_id) = viewer_id {
+            count_query.cond_where(visible_to(Expr::col((Posts::Table, Posts::UserId)), viewer_id));
         }
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
@@ -75,20 +216,16 @@ impl PostsRepositoryTrait for PostRepository {
         Ok((posts, total.0))
     }
 
-    async fn get_post(&self, post_id: i32) -> Result<Option<Post>, AppError> {
-        let query = Query::select()
-            .columns([
-                Posts::Id,
-                Posts::Title,
-                Posts::Img,
-                Posts::Body,
-                Posts::CategoryId,
-                Posts::UserId,
-                Posts::UserName,
-            ])
+    async fn get_post(&self, post_id: i32, reader: PostReader) -> Result<Option<Post>, AppError> {
+        let mut query = Query::select();
+        query
+            .columns(POST_COLUMNS.map(|column| (Posts::Table, column)))
             .from(Posts::Table)
-            .and_where(Expr::col(Posts::Id).eq(post_id))
-            .to_owned();
+            .and_where(Expr::col((Posts::Table, Posts::Id)).eq(post_id));
+
+        if let Some(condition) = readable_by(reader) {
+            query.cond_where(condition);
+        }
 
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
@@ -100,40 +237,132 @@ impl PostsRepositoryTrait for PostRepository {
         Ok(result)
     }
 
-    async fn get_post_relation(
+    async fn get_bookmarked_posts(
         &self,
-        post_id: i32,
-    ) -> Result<Vec<PostRelationResponse>, AppError> {
-        let query = Query::select()
-            .column((Posts::Table, Posts::Id))
-            .column((Posts::Table, Posts::Title))
-            .column((Comments::Table, Comments::Id))
-            .column((Comments::Table, Comments::IdPostComment))
-            .column((Comments::Table, Comments::UserNameComment))
-            .column((Comments::Table, Comments::Comment))
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        search: Option<String>,
+        category: Option<i32>,
+        include_descendants: bool,
+    ) -> Result<(Vec<Post>, i64), AppError> {
+        let offset = (page - 1) * page_size;
+
+        let mut select_query = Query::select();
+        select_query
+            .columns(POST_COLUMNS.map(|column| (Posts::Table, column)))
             .from(Posts::Table)
             .join(
                 JoinType::InnerJoin,
-                Comments::Table,
-                Expr::col((Posts::Table, Posts::Id))
-                    .equals((Comments::Table, Comments::IdPostComment)),
+                Bookmarks::Table,
+                Expr::col((Bookmarks::Table, Bookmarks::PostId)).equals((Posts::Table, Posts::Id)),
             )
-            .and_where(Expr::col((Posts::Table, Posts::Id)).eq(post_id))
-            .to_owned();
+            .and_where(Expr::col((Bookmarks::Table, Bookmarks::UserId)).eq(user_id))
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false))
+            .order_by((Bookmarks::Table, Bookmarks::CreatedAt), Order::Desc)
+            .offset(offset as u64)
+            .limit(page_size as u64);
 
-        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let mut count_query = Query::select();
+        count_query
+            .expr(Func::count(Expr::col((Bookmarks::Table, Bookmarks::PostId))))
+            .from(Bookmarks::Table)
+            .join(
+                JoinType::InnerJoin,
+                Posts::Table,
+                Expr::col((Bookmarks::Table, Bookmarks::PostId)).equals((Posts::Table, Posts::Id)),
+            )
+            .and_where(Expr::col((Bookmarks::Table, Bookmarks::UserId)).eq(user_id))
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false));
+
+        if let Some(ref s) = search {
+            select_query
+                .and_where(Expr::col((Posts::Table, Posts::Title)).like(format!("%{}%", s)));
+            count_query.and_where(Expr::col((Posts::Table, Posts::Title)).like(format!("%{}%", s)));
+        }
+
+        if let Some(condition) = category_condition(category, include_descendants) {
+            select_query.and_where(condition.clone());
+            count_query.and_where(condition);
+        }
+
+        // Bookmarks are kept but skipped while the post is not readable.
+        if let Some(condition) = readable_by(PostReader::User(user_id)) {
+            select_query.cond_where(condition.clone());
+            count_query.cond_where(condition);
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
 
-        let result: Vec<PostRelationModel> = sqlx::query_as_with(&sql, values)
+        let posts = sqlx::query_as_with::<_, Post, _>(&sql, values)
             .fetch_all(&self.db_pool)
-            .await
-            .map_err(AppError::SqlxError)?;
+            .await?;
 
-        let responses = result.into_iter().map(PostRelationResponse::from).collect();
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
 
-        Ok(responses)
+        Ok((posts, total.0))
     }
 
-    async fn create_post(&self, input: &CreatePostRequest) -> Result<Post, AppError> {
+    async fn get_feed(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        after: Option<i32>,
+    ) -> Result<(Vec<Post>, i64), AppError> {
+        let mut select_query = Query::select();
+        select_query
+            .columns(POST_COLUMNS.map(|column| (Posts::Table, column)))
+            .from(Posts::Table)
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false))
+            .cond_where(followed_condition(user_id))
+            .cond_where(listed_for(Some(user_id)))
+            .cond_where(visible_to(Expr::col((Posts::Table, Posts::UserId)), user_id))
+            .order_by((Posts::Table, Posts::Id), Order::Desc)
+            .limit(page_size as u64);
+
+        match after {
+            Some(after) => {
+                select_query.and_where(Expr::col((Posts::Table, Posts::Id)).lt(after));
+            }
+            None => {
+                select_query.offset(((page - 1) * page_size) as u64);
+            }
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+
+        info!("Fetching feed for user {}", user_id);
+
+        let posts = sqlx::query_as_with::<_, Post, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col((Posts::Table, Posts::Id))))
+            .from(Posts::Table)
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false))
+            .cond_where(followed_condition(user_id))
+            .cond_where(listed_for(Some(user_id)))
+            .cond_where(visible_to(Expr::col((Posts::Table, Posts::UserId)), user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((posts, total.0))
+    }
+
+    async fn create_post(
+        &self,
+        input: &CreatePostRequest,
+        user_name: &str,
+        password_hash: Option<&str>,
+    ) -> Result<Post, AppError> {
         let query = Query::insert()
             .into_table(Posts::Table)
             .columns([
@@ -143,6 +372,8 @@ impl PostsRepositoryTrait for PostRepository {
                 Posts::CategoryId,
                 Posts::UserId,
                 Posts::UserName,
+                Posts::Visibility,
+                Posts::PasswordHash,
             ])
             .values_panic([
                 input.title.clone().into(),
@@ -150,7 +381,9 @@ impl PostsRepositoryTrait for PostRepository {
                 input.body.clone().into(),
                 input.category_id.into(),
                 input.user_id.into(),
-                input.user_name.clone().into(),
+                user_name.into(),
+                input.visibility.as_str().into(),
+                password_hash.into(),
             ])
             .returning_all()
             .to_owned();
@@ -165,34 +398,86 @@ impl PostsRepositoryTrait for PostRepository {
         Ok(post)
     }
 
-    async fn update_post(&self, input: &UpdatePostRequest) -> Result<Post, AppError> {
+    async fn update_post(
+        &self,
+        input: &UpdatePostRequest,
+        password_hash: Option<Option<&str>>,
+    ) -> Result<Post, AppError> {
         let id = input
             .post_id
             .ok_or_else(|| AppError::ValidationError("Post ID is required".into()))?;
 
-        let query = Query::update()
+        let mut values: Vec<(Posts, SimpleExpr)> = Vec::new();
+
+        if let Some(title) = &input.title {
+            values.push((Posts::Title, title.clone().into()));
+        }
+
+        if let Some(body) = &input.body {
+            values.push((Posts::Body, body.clone().into()));
+        }
+
+        if let Some(file) = &input.file {
+            values.push((Posts::Img, file.clone().into()));
+        }
+
+        if let Some(category_id) = input.category_id {
+            values.push((Posts::CategoryId, category_id.into()));
+        }
+
+        if let Some(visibility) = input.visibility {
+            values.push((Posts::Visibility, visibility.as_str().into()));
+        }
+
+        if let Some(password_hash) = password_hash {
+            values.push((Posts::PasswordHash, password_hash.into()));
+        }
+
+        if values.is_empty() {
+            let post = self
+                .get_post(id, PostReader::Internal)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Post with id {} not found", id)))?;
+
+            if input.version.is_some_and(|version| version != post.version) {
+                return Err(AppError::PreconditionFailed(format!(
+                    "Post with id {} has been modified",
+                    id
+                )));
+            }
+
+            return Ok(post);
+        }
+
+        values.push((Posts::Version, Expr::col(Posts::Version).add(1)));
+
+        let mut query = Query::update();
+        query
             .table(Posts::Table)
-            .values([
-                (Posts::Title, input.title.clone().into()),
-                (Posts::Body, input.body.clone().into()),
-                (Posts::Img, input.file.clone().into()),
-                (Posts::CategoryId, input.category_id.into()),
-                (Posts::UserId, input.user_id.into()),
-                (Posts::UserName, input.user_name.clone().into()),
-            ])
+            .values(values)
             .and_where(Expr::col(Posts::Id).eq(id))
-            .returning_all()
-            .to_owned();
+            .returning_all();
+
+        if let Some(version) = input.version {
+            query.and_where(Expr::col(Posts::Version).eq(version));
+        }
 
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
-        let post: Post = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.db_pool)
+        let post: Option<Post> = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
             .await
             .map_err(AppError::SqlxError)?;
 
-        Ok(post)
+        match post {
+            Some(post) => Ok(post),
+            None if self.get_post(id, PostReader::Internal).await?.is_some() => Err(AppError::PreconditionFailed(
+                format!("Post with id {} has been modified", id),
+            )),
+            None => Err(AppError::NotFound(format!("Post with id {} not found", id))),
+        }
     }
+
     async fn delete_post(&self, post_id: i32) -> Result<(), AppError> {
         let query = Query::delete()
             .from_table(Posts::Table)
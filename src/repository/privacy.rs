@@ -0,0 +1,247 @@
+use async_trait::async_trait;
+use sea_query::{Condition, Expr, IntoColumnRef, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use serde_json::json;
+
+use crate::abstract_trait::PrivacyRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::ReactionTarget;
+use crate::model::comment::Comment;
+use crate::model::posts::Post;
+use crate::model::user::ErasedAccount;
+use crate::repository::comment::COMMENT_COLUMNS;
+use crate::repository::posts::POST_COLUMNS;
+use crate::schema::audit::AuditLog;
+use crate::schema::comment::Comments;
+use crate::schema::mention::Mentions;
+use crate::schema::notification::Notifications;
+use crate::schema::posts::Posts;
+use crate::schema::user::Users;
+use crate::utils::AppError;
+
+/// Shown in place of the author on comments of erased accounts.
+const ERASED_AUTHOR: &str = "Deleted user";
+
+pub struct PrivacyRepository {
+    db_pool: ConnectionPool,
+}
+
+impl PrivacyRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+/// Matches rows pointing at a post of the user or at a comment on one.
+fn targets_user_posts(
+    target_type: impl IntoColumnRef,
+    target_id: impl IntoColumnRef,
+    user_id: i32,
+) -> Condition {
+    let (target_type, target_id) = (target_type.into_column_ref(), target_id.into_column_ref());
+    let user_posts = Query::select()
+        .column(Posts::Id)
+        .from(Posts::Table)
+        .and_where(Expr::col(Posts::UserId).eq(user_id))
+        .to_owned();
+
+    Condition::any()
+        .add(
+            Condition::all()
+                .add(Expr::col(target_type.clone()).eq(ReactionTarget::Post.as_str()))
+                .add(Expr::col(target_id.clone()).in_subquery(user_posts.clone())),
+        )
+        .add(
+            Condition::all()
+                .add(Expr::col(target_type).eq(ReactionTarget::Comment.as_str()))
+                .add(
+                    Expr::col(target_id).in_subquery(
+                        Query::select()
+                            .column(Comments::Id)
+                            .from(Comments::Table)
+                            .and_where(Expr::col(Comments::IdPostComment).in_subquery(user_posts))
+                            .to_owned(),
+                    ),
+                ),
+        )
+}
+
+#[async_trait]
+impl PrivacyRepositoryTrait for PrivacyRepository {
+    async fn find_user_posts(&self, user_id: i32) -> Result<Vec<Post>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(POST_COLUMNS)
+            .from(Posts::Table)
+            .and_where(Expr::col(Posts::UserId).eq(user_id))
+            .order_by(Posts::Id, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let posts = sqlx::query_as_with::<_, Post, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(posts)
+    }
+
+    async fn find_user_comments(&self, user_id: i32) -> Result<Vec<Comment>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(COMMENT_COLUMNS)
+            .from(Comments::Table)
+            .and_where(Expr::col(Comments::UserId).eq(user_id))
+            .order_by(Comments::Id, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let comments = sqlx::query_as_with::<_, Comment, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(comments)
+    }
+
+    async fn erase_user(
+        &self,
+        user_id: i32,
+        actor_id: i32,
+        reassign_to: Option<(i32, &str)>,
+    ) -> Result<ErasedAccount, AppError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let (sql, values) = Query::select()
+            .column(Users::Avatar)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::Id).eq(user_id))
+            .lock_exclusive()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (avatar,): (Option<String>,) = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User with id {} not found", user_id)))?;
+
+        // Posts go first so that comments removed along with a deleted post
+        // are not counted as anonymized.
+        let mut post_images = Vec::new();
+        let mut posts_deleted = 0;
+        let mut posts_reassigned = 0;
+
+        match reassign_to {
+            Some((target_id, target_name)) => {
+                let (sql, values) = Query::update()
+                    .table(Posts::Table)
+                    .values([
+                        (Posts::UserId, target_id.into()),
+                        (Posts::UserName, target_name.into()),
+                    ])
+                    .and_where(Expr::col(Posts::UserId).eq(user_id))
+                    .build_sqlx(PostgresQueryBuilder);
+
+                posts_reassigned = sqlx::query_with(&sql, values)
+                    .execute(&mut *tx)
+                    .await?
+                    .rows_affected();
+            }
+            None => {
+                // Mentions and notifications only refer to their target by
+                // id, so they have to go before the posts and comments do.
+                let (sql, values) = Query::delete()
+                    .from_table(Mentions::Table)
+                    .cond_where(targets_user_posts(
+                        Mentions::TargetType,
+                        Mentions::TargetId,
+                        user_id,
+                    ))
+                    .build_sqlx(PostgresQueryBuilder);
+
+                sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+                let (sql, values) = Query::delete()
+                    .from_table(Notifications::Table)
+                    .cond_where(targets_user_posts(
+                        Notifications::TargetType,
+                        Notifications::TargetId,
+                        user_id,
+                    ))
+                    .build_sqlx(PostgresQueryBuilder);
+
+                sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+                let (sql, values) = Query::delete()
+                    .from_table(Posts::Table)
+                    .and_where(Expr::col(Posts::UserId).eq(user_id))
+                    .returning_col(Posts::Img)
+                    .build_sqlx(PostgresQueryBuilder);
+
+                let images: Vec<(String,)> = sqlx::query_as_with(&sql, values)
+                    .fetch_all(&mut *tx)
+                    .await?;
+
+                posts_deleted = images.len() as u64;
+                post_images = images
+                    .into_iter()
+                    .map(|(img,)| img)
+                    .filter(|img| !img.is_empty())
+                    .collect();
+            }
+        }
+
+        let (sql, values) = Query::update()
+            .table(Comments::Table)
+            .values([
+                (Comments::UserId, Option::<i32>::None.into()),
+                (Comments::UserNameComment, ERASED_AUTHOR.into()),
+            ])
+            .and_where(Expr::col(Comments::UserId).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let comments_anonymized = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        // Counts only: the entry must not bring back the data just erased.
+        let details = json!({
+            "posts": if reassign_to.is_some() { "reassign" } else { "delete" },
+            "reassigned_to": reassign_to.map(|(target_id, _)| target_id),
+            "posts_deleted": posts_deleted,
+            "posts_reassigned": posts_reassigned,
+            "comments_anonymized": comments_anonymized,
+            "files": post_images.len() + usize::from(avatar.is_some()),
+        });
+
+        let (sql, values) = Query::insert()
+            .into_table(AuditLog::Table)
+            .columns([
+                AuditLog::ActorId,
+                AuditLog::Action,
+                AuditLog::SubjectId,
+                AuditLog::Details,
+            ])
+            .values_panic([
+                actor_id.into(),
+                "user.erased".into(),
+                user_id.into(),
+                details.into(),
+            ])
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+        // Tokens, follows, bookmarks, reactions and the rest cascade.
+        let (sql, values) = Query::delete()
+            .from_table(Users::Table)
+            .and_where(Expr::col(Users::Id).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+        tx.commit().await?;
+
+        Ok(ErasedAccount {
+            avatar,
+            post_images,
+            posts_deleted,
+            posts_reassigned,
+            comments_anonymized,
+        })
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sea_query::{Alias, Expr, Func, JoinType, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::info;
+
+use crate::abstract_trait::ReactionRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::{ReactionCounts, ReactionKind, ReactionTarget};
+use crate::model::reaction::{ReactionCount, Reactor};
+use crate::schema::reaction::Reactions;
+use crate::schema::user::Users;
+use crate::utils::AppError;
+
+pub struct ReactionRepository {
+    db_pool: ConnectionPool,
+}
+
+impl ReactionRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ReactionRepositoryTrait for ReactionRepository {
+    async fn toggle(
+        &self,
+        user_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+        kind: ReactionKind,
+    ) -> Result<bool, AppError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(Reactions::Table)
+            .and_where(Expr::col(Reactions::UserId).eq(user_id))
+            .and_where(Expr::col(Reactions::TargetType).eq(target.as_str()))
+            .and_where(Expr::col(Reactions::TargetId).eq(target_id))
+            .and_where(Expr::col(Reactions::Kind).eq(kind.as_str()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let removed = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        if removed == 0 {
+            let (sql, values) = Query::insert()
+                .into_table(Reactions::Table)
+                .columns([
+                    Reactions::UserId,
+                    Reactions::TargetType,
+                    Reactions::TargetId,
+                    Reactions::Kind,
+                ])
+                .values_panic([
+                    user_id.into(),
+                    target.as_str().into(),
+                    target_id.into(),
+                    kind.as_str().into(),
+                ])
+                .on_conflict(
+                    OnConflict::columns([
+                        Reactions::UserId,
+                        Reactions::TargetType,
+                        Reactions::TargetId,
+                        Reactions::Kind,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .build_sqlx(PostgresQueryBuilder);
+
+            sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+        }
+
+        tx.commit().await?;
+
+        info!(
+            "User {} toggled {} on {} {}",
+            user_id,
+            kind.as_str(),
+            target.as_str(),
+            target_id
+        );
+
+        Ok(removed == 0)
+    }
+
+    async fn count_by_targets(
+        &self,
+        target: ReactionTarget,
+        target_ids: &[i32],
+    ) -> Result<HashMap<i32, ReactionCounts>, AppError> {
+        if target_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let (sql, values) = Query::select()
+            .columns([Reactions::TargetId, Reactions::Kind])
+            .expr_as(Func::count(Expr::col(Reactions::Id)), Alias::new("count"))
+            .from(Reactions::Table)
+            .and_where(Expr::col(Reactions::TargetType).eq(target.as_str()))
+            .and_where(Expr::col(Reactions::TargetId).is_in(target_ids.iter().copied()))
+            .group_by_columns([Reactions::TargetId, Reactions::Kind])
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows = sqlx::query_as_with::<_, ReactionCount, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let mut counts: HashMap<i32, ReactionCounts> = HashMap::new();
+        for row in rows {
+            counts
+                .entry(row.target_id)
+                .or_default()
+                .insert(row.kind, row.count);
+        }
+
+        Ok(counts)
+    }
+
+    async fn find_reactors(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        kind: Option<ReactionKind>,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Reactor>, i64), AppError> {
+        let offset = (page - 1) * page_size;
+
+        let mut select_query = Query::select();
+        select_query
+            .expr_as(Expr::col((Users::Table, Users::Id)), Alias::new("user_id"))
+            .columns([
+                (Users::Table, Users::Firstname),
+                (Users::Table, Users::Lastname),
+            ])
+            .columns([
+                (Reactions::Table, Reactions::Kind),
+                (Reactions::Table, Reactions::CreatedAt),
+            ])
+            .from(Reactions::Table)
+            .join(
+                JoinType::InnerJoin,
+                Users::Table,
+                Expr::col((Users::Table, Users::Id)).equals((Reactions::Table, Reactions::UserId)),
+            )
+            .and_where(Expr::col((Reactions::Table, Reactions::TargetType)).eq(target.as_str()))
+            .and_where(Expr::col((Reactions::Table, Reactions::TargetId)).eq(target_id))
+            .order_by((Reactions::Table, Reactions::CreatedAt), Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64);
+
+        let mut count_query = Query::select();
+        count_query
+            .expr(Func::count(Expr::col(Reactions::Id)))
+            .from(Reactions::Table)
+            .and_where(Expr::col(Reactions::TargetType).eq(target.as_str()))
+            .and_where(Expr::col(Reactions::TargetId).eq(target_id));
+
+        if let Some(kind) = kind {
+            select_query
+                .and_where(Expr::col((Reactions::Table, Reactions::Kind)).eq(kind.as_str()));
+            count_query.and_where(Expr::col(Reactions::Kind).eq(kind.as_str()));
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+        let reactors = sqlx::query_as_with::<_, Reactor, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((reactors, total.0))
+    }
+}
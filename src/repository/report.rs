@@ -0,0 +1,287 @@
+use async_trait::async_trait;
+use sea_query::{
+    Alias, Expr, Func, OnConflict, Order, PostgresQueryBuilder, Query, SelectStatement, SimpleExpr,
+};
+use sea_query_binder::{SqlxBinder, SqlxValues};
+
+use crate::abstract_trait::ReportRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::{
+    CommentStatus, CreateReportRequest, FindReportsRequest, ReactionTarget, ReportStatus,
+};
+use crate::model::report::{Report, ReportEntry};
+use crate::schema::comment::Comments;
+use crate::schema::posts::Posts;
+use crate::schema::report::Reports;
+use crate::utils::AppError;
+
+const REPORT_COLUMNS: [Reports; 10] = [
+    Reports::Id,
+    Reports::ReporterId,
+    Reports::TargetType,
+    Reports::TargetId,
+    Reports::Reason,
+    Reports::Details,
+    Reports::Status,
+    Reports::ResolvedBy,
+    Reports::ResolvedAt,
+    Reports::CreatedAt,
+];
+
+const PENDING_STATUSES: [ReportStatus; 2] = [ReportStatus::Open, ReportStatus::Triaged];
+
+pub struct ReportRepository {
+    db_pool: ConnectionPool,
+}
+
+impl ReportRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+
+    fn pending_count(target_type: SimpleExpr, target_id: SimpleExpr) -> SelectStatement {
+        let pending = Alias::new("pending");
+
+        Query::select()
+            .expr(Func::count(Expr::col((pending.clone(), Reports::Id))))
+            .from_as(Reports::Table, pending.clone())
+            .and_where(Expr::col((pending.clone(), Reports::TargetType)).eq(target_type))
+            .and_where(Expr::col((pending.clone(), Reports::TargetId)).eq(target_id))
+            .and_where(
+                Expr::col((pending, Reports::Status))
+                    .is_in(PENDING_STATUSES.iter().map(|status| status.as_str())),
+            )
+            .to_owned()
+    }
+
+    fn visibility_update(
+        target: ReactionTarget,
+        target_id: i32,
+        hidden: bool,
+        final_decision: bool,
+    ) -> (String, SqlxValues) {
+        match target {
+            ReactionTarget::Post => Query::update()
+                .table(Posts::Table)
+                .value(Posts::IsHidden, hidden)
+                .and_where(Expr::col(Posts::Id).eq(target_id))
+                .build_sqlx(PostgresQueryBuilder),
+            ReactionTarget::Comment => {
+                // Hiding only pulls published comments; a confirmed report
+                // rejects the comment whatever state it is in.
+                let (from, to) = match (hidden, final_decision) {
+                    (true, false) => (vec![CommentStatus::Approved], CommentStatus::Pending),
+                    (true, true) => (
+                        vec![CommentStatus::Approved, CommentStatus::Pending],
+                        CommentStatus::Rejected,
+                    ),
+                    (false, _) => (vec![CommentStatus::Pending], CommentStatus::Approved),
+                };
+
+                Query::update()
+                    .table(Comments::Table)
+                    .values([
+                        (Comments::Status, to.as_str().into()),
+                        (Comments::Version, Expr::col(Comments::Version).add(1)),
+                    ])
+                    .and_where(Expr::col(Comments::Id).eq(target_id))
+                    .and_where(
+                        Expr::col(Comments::Status)
+                            .is_in(from.iter().map(|status| status.as_str())),
+                    )
+                    .build_sqlx(PostgresQueryBuilder)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ReportRepositoryTrait for ReportRepository {
+    async fn create(
+        &self,
+        reporter_id: i32,
+        input: &CreateReportRequest,
+    ) -> Result<Option<Report>, AppError> {
+        let (sql, values) = Query::insert()
+            .into_table(Reports::Table)
+            .columns([
+                Reports::ReporterId,
+                Reports::TargetType,
+                Reports::TargetId,
+                Reports::Reason,
+                Reports::Details,
+            ])
+            .values_panic([
+                reporter_id.into(),
+                input.target_type.as_str().into(),
+                input.target_id.into(),
+                input.reason.as_str().into(),
+                input.details.clone().into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([Reports::ReporterId, Reports::TargetType, Reports::TargetId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .returning(Query::returning().columns(REPORT_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let report = sqlx::query_as_with::<_, Report, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(report)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Report>, AppError> {
+        let (sql, values) = Query::select()
+            .columns(REPORT_COLUMNS)
+            .from(Reports::Table)
+            .and_where(Expr::col(Reports::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let report = sqlx::query_as_with::<_, Report, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(report)
+    }
+
+    async fn find_paginated(
+        &self,
+        req: &FindReportsRequest,
+    ) -> Result<(Vec<ReportEntry>, i64), AppError> {
+        let offset = (req.page - 1) * req.page_size;
+
+        let mut select_query = Query::select();
+        select_query
+            .columns(REPORT_COLUMNS.map(|column| (Reports::Table, column)))
+            .expr_as(
+                SimpleExpr::SubQuery(
+                    None,
+                    Box::new(
+                        Self::pending_count(
+                            Expr::col((Reports::Table, Reports::TargetType)).into(),
+                            Expr::col((Reports::Table, Reports::TargetId)).into(),
+                        )
+                        .into_sub_query_statement(),
+                    ),
+                ),
+                Alias::new("open_reports"),
+            )
+            .from(Reports::Table)
+            .and_where(Expr::col((Reports::Table, Reports::Status)).eq(req.status.as_str()))
+            // Oldest first, like the comment queue.
+            .order_by((Reports::Table, Reports::Id), Order::Asc)
+            .offset(offset as u64)
+            .limit(req.page_size as u64);
+
+        let mut count_query = Query::select();
+        count_query
+            .expr(Func::count(Expr::col(Reports::Id)))
+            .from(Reports::Table)
+            .and_where(Expr::col(Reports::Status).eq(req.status.as_str()));
+
+        if let Some(target) = req.target_type {
+            select_query
+                .and_where(Expr::col((Reports::Table, Reports::TargetType)).eq(target.as_str()));
+            count_query.and_where(Expr::col(Reports::TargetType).eq(target.as_str()));
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+
+        let reports = sqlx::query_as_with::<_, ReportEntry, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+        let total: (i64,) = sqlx::query_as_with(&count_sql, count_values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((reports, total.0))
+    }
+
+    async fn count_open(&self, target: ReactionTarget, target_id: i32) -> Result<i64, AppError> {
+        let (sql, values) = Self::pending_count(target.as_str().into(), target_id.into())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let count: (i64,) = sqlx::query_as_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    async fn hide_target(&self, target: ReactionTarget, target_id: i32) -> Result<(), AppError> {
+        let (sql, values) = Self::visibility_update(target, target_id, true, false);
+
+        sqlx::query_with(&sql, values).execute(&self.db_pool).await?;
+
+        Ok(())
+    }
+
+    async fn set_status(
+        &self,
+        report: &Report,
+        status: ReportStatus,
+        moderator_id: i32,
+    ) -> Result<Report, AppError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let mut query = Query::update();
+        query
+            .table(Reports::Table)
+            .value(Reports::Status, status.as_str())
+            .and_where(
+                Expr::col(Reports::Status)
+                    .is_in(PENDING_STATUSES.iter().map(|status| status.as_str())),
+            );
+
+        if status.is_closed() {
+            query
+                .values([
+                    (Reports::ResolvedBy, moderator_id.into()),
+                    (Reports::ResolvedAt, Expr::current_timestamp().into()),
+                ])
+                .and_where(Expr::col(Reports::TargetType).eq(report.target_type.as_str()))
+                .and_where(Expr::col(Reports::TargetId).eq(report.target_id));
+        } else {
+            query.and_where(Expr::col(Reports::Id).eq(report.id));
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+        if status.is_closed() {
+            let target = if report.target_type == ReactionTarget::Post.as_str() {
+                ReactionTarget::Post
+            } else {
+                ReactionTarget::Comment
+            };
+
+            let (sql, values) = Self::visibility_update(
+                target,
+                report.target_id,
+                status == ReportStatus::Resolved,
+                true,
+            );
+
+            sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+        }
+
+        let (sql, values) = Query::select()
+            .columns(REPORT_COLUMNS)
+            .from(Reports::Table)
+            .and_where(Expr::col(Reports::Id).eq(report.id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let updated = sqlx::query_as_with::<_, Report, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+}
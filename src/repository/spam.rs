@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sea_query::{Alias, Expr, LockType, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Postgres, Transaction};
+
+use crate::abstract_trait::SpamRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::schema::spam::{SpamDocuments, SpamTokens, SpamTraining};
+use crate::utils::AppError;
+
+pub struct SpamRepository {
+    db_pool: ConnectionPool,
+}
+
+impl SpamRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+/// Moves one document and its tokens into (`delta` 1) or out of (`delta`
+/// -1) the counts of a label.
+async fn adjust(
+    tx: &mut Transaction<'_, Postgres>,
+    tokens: &[String],
+    is_spam: bool,
+    delta: i64,
+) -> Result<(), AppError> {
+    let label = if is_spam { "spam" } else { "ham" };
+
+    if !tokens.is_empty() {
+        let mut insert = Query::insert();
+        insert
+            .into_table(SpamTokens::Table)
+            .columns([SpamTokens::Token, SpamTokens::SpamCount, SpamTokens::HamCount]);
+
+        for token in tokens {
+            insert.values_panic([
+                token.clone().into(),
+                if is_spam { delta } else { 0 }.into(),
+                if is_spam { 0 } else { delta }.into(),
+            ]);
+        }
+
+        let (sql, values) = insert
+            .on_conflict(
+                OnConflict::column(SpamTokens::Token)
+                    .value(
+                        SpamTokens::SpamCount,
+                        Expr::col((SpamTokens::Table, SpamTokens::SpamCount))
+                            .add(Expr::col((Alias::new("excluded"), SpamTokens::SpamCount))),
+                    )
+                    .value(
+                        SpamTokens::HamCount,
+                        Expr::col((SpamTokens::Table, SpamTokens::HamCount))
+                            .add(Expr::col((Alias::new("excluded"), SpamTokens::HamCount))),
+                    )
+                    .to_owned(),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&mut **tx).await?;
+    }
+
+    let (sql, values) = Query::update()
+        .table(SpamDocuments::Table)
+        .value(SpamDocuments::Count, Expr::col(SpamDocuments::Count).add(delta))
+        .and_where(Expr::col(SpamDocuments::Label).eq(label))
+        .build_sqlx(PostgresQueryBuilder);
+
+    sqlx::query_with(&sql, values).execute(&mut **tx).await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl SpamRepositoryTrait for SpamRepository {
+    async fn record(&self, comment_id: i32, tokens: &[String], is_spam: bool) -> Result<(), AppError> {
+        let label = if is_spam { "spam" } else { "ham" };
+
+        let mut tx = self.db_pool.begin().await?;
+
+        let (sql, values) = Query::select()
+            .columns([SpamTraining::Label, SpamTraining::Tokens])
+            .from(SpamTraining::Table)
+            .and_where(Expr::col(SpamTraining::CommentId).eq(comment_id))
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let previous: Option<(String, String)> = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if let Some((previous_label, previous_tokens)) = previous {
+            if previous_label == label {
+                return Ok(());
+            }
+
+            let previous_tokens: Vec<String> =
+                previous_tokens.split_whitespace().map(str::to_string).collect();
+
+            adjust(&mut tx, &previous_tokens, previous_label == "spam", -1).await?;
+        }
+
+        adjust(&mut tx, tokens, is_spam, 1).await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(SpamTraining::Table)
+            .columns([SpamTraining::CommentId, SpamTraining::Label, SpamTraining::Tokens])
+            .values_panic([comment_id.into(), label.into(), tokens.join(" ").into()])
+            .on_conflict(
+                OnConflict::column(SpamTraining::CommentId)
+                    .update_columns([SpamTraining::Label, SpamTraining::Tokens])
+                    .to_owned(),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn token_counts(
+        &self,
+        tokens: &[String],
+    ) -> Result<HashMap<String, (i64, i64)>, AppError> {
+        if tokens.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let (sql, values) = Query::select()
+            .columns([SpamTokens::Token, SpamTokens::SpamCount, SpamTokens::HamCount])
+            .from(SpamTokens::Table)
+            .and_where(Expr::col(SpamTokens::Token).is_in(tokens.iter().cloned()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(token, spam, ham)| (token, (spam, ham)))
+            .collect())
+    }
+
+    async fn document_counts(&self) -> Result<(i64, i64), AppError> {
+        let (sql, values) = Query::select()
+            .columns([SpamDocuments::Label, SpamDocuments::Count])
+            .from(SpamDocuments::Table)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows: Vec<(String, i64)> = sqlx::query_as_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let count = |label: &str| {
+            rows.iter()
+                .find(|(row_label, _)| row_label == label)
+                .map(|(_, count)| *count)
+                .unwrap_or(0)
+        };
+
+        Ok((count("spam"), count("ham")))
+    }
+}
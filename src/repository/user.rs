@@ -1,14 +1,70 @@
 use async_trait::async_trait;
-use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query::extension::postgres::PgExpr;
+use sea_query::{Condition, Expr, Func, Order, PostgresQueryBuilder, Query, SimpleExpr};
 use sea_query_binder::SqlxBinder;
 
 use crate::abstract_trait::UserRepositoryTrait;
 use crate::config::ConnectionPool;
-use crate::domain::{CreateUserRequest,  UpdateUserRequest};
+use crate::domain::{
+    CreateUserRequest, FindUsersRequest, UpdateProfileRequest, UpdateUserRequest, UserRole,
+};
 use crate::model::user::User;
 use crate::schema::user::Users;
 use crate::utils::AppError;
 
+const USER_COLUMNS: [Users; 14] = [
+    Users::Id,
+    Users::Firstname,
+    Users::Lastname,
+    Users::Email,
+    Users::Password,
+    Users::Version,
+    Users::Role,
+    Users::Username,
+    Users::Bio,
+    Users::Avatar,
+    Users::Website,
+    Users::IsActive,
+    Users::TokenVersion,
+    Users::PasswordResetRequired,
+];
+
+fn map_duplicate(error: sqlx::Error) -> AppError {
+    match &error {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            AppError::Conflict("This username is already taken".into())
+        }
+        _ => AppError::SqlxError(error),
+    }
+}
+
+/// The admin list filters, shared by the page and its count.
+fn user_filter(req: &FindUsersRequest) -> Condition {
+    let mut condition = Condition::all();
+
+    if let Some(search) = req.search.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        let pattern = format!("%{}%", search);
+
+        condition = condition.add(
+            Condition::any()
+                .add(Expr::col(Users::Email).ilike(pattern.clone()))
+                .add(Expr::col(Users::Username).ilike(pattern.clone()))
+                .add(Expr::col(Users::Firstname).ilike(pattern.clone()))
+                .add(Expr::col(Users::Lastname).ilike(pattern)),
+        );
+    }
+
+    if let Some(role) = req.role {
+        condition = condition.add(Expr::col(Users::Role).eq(role.as_str()));
+    }
+
+    if let Some(active) = req.active {
+        condition = condition.add(Expr::col(Users::IsActive).eq(active));
+    }
+
+    condition
+}
+
 pub struct UserRepository {
     db_pool: ConnectionPool,
 }
@@ -17,6 +73,39 @@ impl UserRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
         Self { db_pool }
     }
+
+    /// Applies account-level changes and bumps the record version.
+    async fn update_account(
+        &self,
+        id: i32,
+        mut values: Vec<(Users, SimpleExpr)>,
+    ) -> Result<User, AppError> {
+        values.push((Users::Version, bump(Users::Version)));
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values(values)
+            .and_where(Expr::col(Users::Id).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            // Email is the only unique column changed here.
+            .map_err(|error| match &error {
+                sqlx::Error::Database(e) if e.is_unique_violation() => {
+                    AppError::EmailAlreadyExists
+                }
+                _ => AppError::SqlxError(error),
+            })?;
+
+        user.ok_or_else(|| AppError::NotFound(format!("User with id {} not found", id)))
+    }
+}
+
+fn bump(column: Users) -> SimpleExpr {
+    Expr::col(column).add(1)
 }
 
 #[async_trait]
@@ -36,7 +125,11 @@ impl UserRepositoryTrait for UserRepository {
         Ok(count > 0)
     }
 
-    async fn create_user(&self, input: &CreateUserRequest) -> Result<User, AppError> {
+    async fn create_user(
+        &self,
+        input: &CreateUserRequest,
+        username: &str,
+    ) -> Result<User, AppError> {
         let query = Query::insert()
             .into_table(Users::Table)
             .columns([
@@ -44,12 +137,14 @@ impl UserRepositoryTrait for UserRepository {
                 Users::Lastname,
                 Users::Email,
                 Users::Password,
+                Users::Username,
             ])
             .values_panic([
                 input.firstname.clone().into(),
                 input.lastname.clone().into(),
                 input.email.clone().into(),
                 input.password.clone().into(),
+                username.into(),
             ])
             .returning_all()
             .to_owned();
@@ -57,20 +152,15 @@ impl UserRepositoryTrait for UserRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
         let user: User = sqlx::query_as_with(&sql, values)
             .fetch_one(&self.db_pool)
-            .await?;
+            .await
+            .map_err(map_duplicate)?;
 
         Ok(user)
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
         let query = Query::select()
-            .columns([
-                Users::Id,
-                Users::Firstname,
-                Users::Lastname,
-                Users::Email,
-                Users::Password,
-            ])
+            .columns(USER_COLUMNS)
             .from(Users::Table)
             .and_where(Expr::col(Users::Email).eq(email))
             .to_owned();
@@ -83,15 +173,24 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError> {
+        let query = Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::Username).eq(username.to_lowercase()))
+            .to_owned();
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let user = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(user)
+    }
+
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, AppError> {
         let query = Query::select()
-            .columns([
-                Users::Id,
-                Users::Firstname,
-                Users::Lastname,
-                Users::Email,
-                Users::Password,
-            ])
+            .columns(USER_COLUMNS)
             .from(Users::Table)
             .and_where(Expr::col(Users::Id).eq(id))
             .to_owned();
@@ -104,6 +203,52 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<User>, AppError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::Id).is_in(ids.iter().copied()))
+            .to_owned();
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+        let users = sqlx::query_as_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(users)
+    }
+
+    async fn find_paginated(&self, req: &FindUsersRequest) -> Result<(Vec<User>, i64), AppError> {
+        let (sql, values) = Query::select()
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .cond_where(user_filter(req))
+            .order_by(Users::Id, Order::Asc)
+            .offset(((req.page - 1) * req.page_size) as u64)
+            .limit(req.page_size as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let users = sqlx::query_as_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(Users::Id)))
+            .from(Users::Table)
+            .cond_where(user_filter(req))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let total: i64 = sqlx::query_scalar_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await?;
+
+        Ok((users, total))
+    }
+
     async fn update_user(&self, input: &UpdateUserRequest) -> Result<User, AppError> {
         let id = input
             .id
@@ -112,6 +257,7 @@ impl UserRepositoryTrait for UserRepository {
         let mut update_query = Query::update();
         let mut query = update_query
             .table(Users::Table)
+            .value(Users::Version, Expr::col(Users::Version).add(1))
             .and_where(Expr::col(Users::Id).eq(id));
 
         if let Some(firstname) = &input.firstname {
@@ -122,18 +268,74 @@ impl UserRepositoryTrait for UserRepository {
             query = query.value(Users::Lastname, lastname.clone());
         }
 
-        if let Some(email) = &input.email {
-            query = query.value(Users::Email, email.clone());
+        if let Some(version) = input.version {
+            query = query.and_where(Expr::col(Users::Version).eq(version));
         }
 
         query = query.returning_all();
 
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
         let user = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_optional(&self.db_pool)
             .await?;
 
-        Ok(user)
+        match user {
+            Some(user) => Ok(user),
+            None if self.find_by_id(id).await?.is_some() => Err(AppError::PreconditionFailed(
+                format!("User with id {} has been modified", id),
+            )),
+            None => Err(AppError::NotFound(format!("User with id {} not found", id))),
+        }
+    }
+
+    async fn update_profile(
+        &self,
+        id: i32,
+        input: &UpdateProfileRequest,
+    ) -> Result<User, AppError> {
+        let mut query = Query::update();
+        query
+            .table(Users::Table)
+            .value(Users::Version, Expr::col(Users::Version).add(1))
+            .and_where(Expr::col(Users::Id).eq(id));
+
+        if let Some(username) = &input.username {
+            query.value(Users::Username, username.clone());
+        }
+
+        if let Some(bio) = &input.bio {
+            query.value(Users::Bio, bio.clone());
+        }
+
+        if let Some(website) = &input.website {
+            query.value(Users::Website, website.clone());
+        }
+
+        let (sql, values) = query.returning_all().build_sqlx(PostgresQueryBuilder);
+        let user = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(map_duplicate)?;
+
+        user.ok_or_else(|| AppError::NotFound(format!("User with id {} not found", id)))
+    }
+
+    async fn set_avatar(&self, id: i32, avatar: Option<&str>) -> Result<User, AppError> {
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .values([
+                (Users::Avatar, avatar.into()),
+                (Users::Version, Expr::col(Users::Version).add(1)),
+            ])
+            .and_where(Expr::col(Users::Id).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        user.ok_or_else(|| AppError::NotFound(format!("User with id {} not found", id)))
     }
 
     async fn delete_user(&self, email: &str) -> Result<(), AppError> {
@@ -149,4 +351,47 @@ impl UserRepositoryTrait for UserRepository {
 
         Ok(())
     }
+
+    async fn set_active(&self, id: i32, active: bool) -> Result<User, AppError> {
+        let mut values = vec![(Users::IsActive, active.into())];
+
+        if !active {
+            values.push((Users::TokenVersion, bump(Users::TokenVersion)));
+        }
+
+        self.update_account(id, values).await
+    }
+
+    async fn set_role(&self, id: i32, role: UserRole) -> Result<User, AppError> {
+        self.update_account(id, vec![(Users::Role, role.as_str().into())])
+            .await
+    }
+
+    async fn require_password_reset(&self, id: i32) -> Result<User, AppError> {
+        self.update_account(
+            id,
+            vec![
+                (Users::PasswordResetRequired, true.into()),
+                (Users::TokenVersion, bump(Users::TokenVersion)),
+            ],
+        )
+        .await
+    }
+
+    async fn set_password(&self, id: i32, password_hash: &str) -> Result<User, AppError> {
+        self.update_account(
+            id,
+            vec![
+                (Users::Password, password_hash.into()),
+                (Users::PasswordResetRequired, false.into()),
+                (Users::TokenVersion, bump(Users::TokenVersion)),
+            ],
+        )
+        .await
+    }
+
+    async fn set_email(&self, id: i32, email: &str) -> Result<User, AppError> {
+        self.update_account(id, vec![(Users::Email, email.into())])
+            .await
+    }
 }
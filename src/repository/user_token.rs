@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_query::{Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+
+use crate::abstract_trait::UserTokenRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::domain::TokenPurpose;
+use crate::model::user_token::UserToken;
+use crate::schema::user_token::UserTokens;
+use crate::utils::AppError;
+
+pub struct UserTokenRepository {
+    db_pool: ConnectionPool,
+}
+
+impl UserTokenRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl UserTokenRepositoryTrait for UserTokenRepository {
+    async fn create(
+        &self,
+        user_id: i32,
+        purpose: TokenPurpose,
+        token_hash: &str,
+        new_email: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<UserToken, AppError> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(UserTokens::Table)
+            .and_where(Expr::col(UserTokens::UserId).eq(user_id))
+            .and_where(Expr::col(UserTokens::Purpose).eq(purpose.as_str()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&mut *tx).await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(UserTokens::Table)
+            .columns([
+                UserTokens::UserId,
+                UserTokens::Purpose,
+                UserTokens::TokenHash,
+                UserTokens::NewEmail,
+                UserTokens::ExpiresAt,
+            ])
+            .values_panic([
+                user_id.into(),
+                purpose.as_str().into(),
+                token_hash.into(),
+                new_email.into(),
+                expires_at.into(),
+            ])
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let token = sqlx::query_as_with::<_, UserToken, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(token)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<UserToken>, AppError> {
+        let (sql, values) = Query::select()
+            .columns([
+                UserTokens::Id,
+                UserTokens::UserId,
+                UserTokens::Purpose,
+                UserTokens::TokenHash,
+                UserTokens::NewEmail,
+                UserTokens::ExpiresAt,
+                UserTokens::CreatedAt,
+            ])
+            .from(UserTokens::Table)
+            .and_where(Expr::col(UserTokens::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let token = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn delete_for_user(&self, user_id: i32, purpose: TokenPurpose) -> Result<(), AppError> {
+        let (sql, values) = Query::delete()
+            .from_table(UserTokens::Table)
+            .and_where(Expr::col(UserTokens::UserId).eq(user_id))
+            .and_where(Expr::col(UserTokens::Purpose).eq(purpose.as_str()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values).execute(&self.db_pool).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sea_query::{Alias, Expr, JoinType, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::info;
+
+use crate::abstract_trait::PostViewRepositoryTrait;
+use crate::config::ConnectionPool;
+use crate::model::view::TrendingPost;
+use crate::repository::posts::{listed_for, POST_COLUMNS};
+use crate::schema::posts::Posts;
+use crate::schema::view::PostViewsDaily;
+use crate::utils::AppError;
+
+pub struct PostViewRepository {
+    db_pool: ConnectionPool,
+}
+
+impl PostViewRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl PostViewRepositoryTrait for PostViewRepository {
+    async fn increment_views(&self, batch: &[(i32, NaiveDate, i64)]) -> Result<(), AppError> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        // Posts deleted since the views were buffered would fail the whole
+        // insert on the foreign key, so only keep ids that still exist.
+        let post_ids: Vec<i32> = batch.iter().map(|(post_id, _, _)| *post_id).collect();
+
+        let (sql, values) = Query::select()
+            .column(Posts::Id)
+            .from(Posts::Table)
+            .and_where(Expr::col(Posts::Id).is_in(post_ids))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let existing: Vec<i32> = sqlx::query_scalar_with(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        let mut insert = Query::insert();
+        insert
+            .into_table(PostViewsDaily::Table)
+            .columns([
+                PostViewsDaily::PostId,
+                PostViewsDaily::Day,
+                PostViewsDaily::Views,
+            ])
+            .on_conflict(
+                OnConflict::columns([PostViewsDaily::PostId, PostViewsDaily::Day])
+                    .value(
+                        PostViewsDaily::Views,
+                        Expr::col((PostViewsDaily::Table, PostViewsDaily::Views))
+                            .add(Expr::col((Alias::new("excluded"), PostViewsDaily::Views))),
+                    )
+                    .to_owned(),
+            );
+
+        let mut rows = 0;
+        for (post_id, day, views) in batch {
+            if existing.contains(post_id) {
+                insert.values_panic([(*post_id).into(), (*day).into(), (*views).into()]);
+                rows += 1;
+            }
+        }
+
+        if rows == 0 {
+            return Ok(());
+        }
+
+        let (sql, values) = insert.build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await?;
+
+        info!("Flushed {} post view counters", rows);
+
+        Ok(())
+    }
+
+    async fn find_trending(
+        &self,
+        window_days: i32,
+        limit: i32,
+    ) -> Result<Vec<TrendingPost>, AppError> {
+        // Each day's views lose half their weight every `half_life` days.
+        let half_life = (window_days as f64 / 2.0).max(1.0);
+        let views = Alias::new("v");
+
+        let daily = Query::select()
+            .column(PostViewsDaily::PostId)
+            .expr_as(Expr::cust(r#"SUM("views")::BIGINT"#), Alias::new("views"))
+            .expr_as(
+                Expr::cust_with_values(
+                    r#"SUM("views" * POWER(0.5::FLOAT8, (CURRENT_DATE - "day") / $1::FLOAT8))"#,
+                    [half_life],
+                ),
+                Alias::new("score"),
+            )
+            .from(PostViewsDaily::Table)
+            .and_where(Expr::col(PostViewsDaily::Day).gt(Expr::cust_with_values(
+                "CURRENT_DATE - $1::INT",
+                [window_days],
+            )))
+            .group_by_col(PostViewsDaily::PostId)
+            .to_owned();
+
+        let (sql, values) = Query::select()
+            .columns(POST_COLUMNS.map(|column| (Posts::Table, column)))
+            .column((views.clone(), Alias::new("views")))
+            .column((views.clone(), Alias::new("score")))
+            .from(Posts::Table)
+            .join_subquery(
+                JoinType::InnerJoin,
+                daily,
+                views.clone(),
+                Expr::col((views.clone(), PostViewsDaily::PostId))
+                    .equals((Posts::Table, Posts::Id)),
+            )
+            .and_where(Expr::col((Posts::Table, Posts::IsHidden)).eq(false))
+            // The ranking is public, so only posts listed to everyone count.
+            .cond_where(listed_for(None))
+            .order_by((views.clone(), Alias::new("score")), Order::Desc)
+            .order_by((Posts::Table, Posts::Id), Order::Desc)
+            .limit(limit as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let posts = sqlx::query_as_with::<_, TrendingPost, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await?;
+
+        Ok(posts)
+    }
+}
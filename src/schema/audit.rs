@@ -0,0 +1,12 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum AuditLog {
+    Table,
+    Id,
+    ActorId,
+    Action,
+    SubjectId,
+    Details,
+    CreatedAt,
+}
@@ -6,4 +6,11 @@ pub enum Categories {
     Table,
     Id,
     Name,
+    Version,
+    CommentPolicy,
+    ParentId,
+    Slug,
+    Description,
+    CoverImage,
+    SortOrder,
 }
\ No newline at end of file
@@ -9,4 +9,11 @@ pub enum Comments {
     IdPostComment,
     UserNameComment,
     Comment,
+    Version,
+    ParentId,
+    Depth,
+    IsDeleted,
+    UserId,
+    CreatedAt,
+    Status,
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum Follows {
+    Table,
+    Id,
+    FollowerId,
+    UserId,
+    CategoryId,
+    CreatedAt,
+}
@@ -0,0 +1,11 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum Mentions {
+    Table,
+    TargetType,
+    TargetId,
+    UserId,
+    CreatedAt,
+}
@@ -1,4 +1,15 @@
 pub mod user;
+pub mod audit;
+pub mod block;
+pub mod bookmark;
 pub mod category;
 pub mod comment;
-pub mod posts;
\ No newline at end of file
+pub mod follow;
+pub mod mention;
+pub mod notification;
+pub mod posts;
+pub mod reaction;
+pub mod report;
+pub mod spam;
+pub mod user_token;
+pub mod view;
\ No newline at end of file
@@ -0,0 +1,25 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum Notifications {
+    Table,
+    Id,
+    UserId,
+    ActorId,
+    Kind,
+    TargetType,
+    TargetId,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(Debug, Iden)]
+pub enum NotificationPreferences {
+    Table,
+    UserId,
+    Mention,
+    Comment,
+    Reply,
+    UpdatedAt,
+}
@@ -11,4 +11,10 @@ pub enum Posts {
     CategoryId,
     UserId,
     UserName,
+    Version,
+    CommentPolicy,
+    IsHidden,
+    Visibility,
+    PasswordHash,
+    CreatedAt,
 }
@@ -0,0 +1,13 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum Reactions {
+    Table,
+    Id,
+    UserId,
+    TargetType,
+    TargetId,
+    Kind,
+    CreatedAt,
+}
@@ -0,0 +1,17 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum Reports {
+    Table,
+    Id,
+    ReporterId,
+    TargetType,
+    TargetId,
+    Reason,
+    Details,
+    Status,
+    ResolvedBy,
+    ResolvedAt,
+    CreatedAt,
+}
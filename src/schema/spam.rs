@@ -0,0 +1,25 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum SpamTokens {
+    Table,
+    Token,
+    SpamCount,
+    HamCount,
+}
+
+#[derive(Debug, Iden)]
+pub enum SpamDocuments {
+    Table,
+    Label,
+    Count,
+}
+
+#[derive(Debug, Iden)]
+pub enum SpamTraining {
+    Table,
+    CommentId,
+    Label,
+    Tokens,
+}
@@ -9,4 +9,13 @@ pub enum Users {
     Lastname,
     Email,
     Password,
+    Version,
+    Role,
+    Username,
+    Bio,
+    Avatar,
+    Website,
+    IsActive,
+    TokenVersion,
+    PasswordResetRequired,
 }
\ No newline at end of file
@@ -0,0 +1,13 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum UserTokens {
+    Table,
+    Id,
+    UserId,
+    Purpose,
+    TokenHash,
+    NewEmail,
+    ExpiresAt,
+    CreatedAt,
+}
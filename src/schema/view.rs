@@ -0,0 +1,10 @@
+use sea_query::Iden;
+
+
+#[derive(Debug, Iden)]
+pub enum PostViewsDaily {
+    Table,
+    PostId,
+    Day,
+    Views,
+}
@@ -0,0 +1,222 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use tracing::info;
+
+use crate::{
+    abstract_trait::{
+        AdminServiceTrait, DynPrivacyService, DynUserRepository, DynUserTokenRepository,
+    },
+    config::Hashing,
+    domain::{
+        ApiResponse, ApiResponsePagination, EraseUserRequest, ErasureResponse, ErrorResponse,
+        FindUsersRequest, Pagination, PasswordResetResponse, TokenPurpose, UserResponse, UserRole,
+    },
+    service::auth::issue_token,
+    utils::AppError,
+};
+
+/// How long an admin-issued reset token can be redeemed.
+const RESET_TOKEN_TTL_HOURS: i64 = 24;
+
+pub struct AdminService {
+    user_repository: DynUserRepository,
+    token_repository: DynUserTokenRepository,
+    privacy_service: DynPrivacyService,
+    hashing: Hashing,
+}
+
+impl AdminService {
+    pub fn new(
+        user_repository: DynUserRepository,
+        token_repository: DynUserTokenRepository,
+        privacy_service: DynPrivacyService,
+        hashing: Hashing,
+    ) -> Self {
+        Self {
+            user_repository,
+            token_repository,
+            privacy_service,
+            hashing,
+        }
+    }
+
+    /// Admins may not lock themselves out.
+    fn ensure_not_self(admin_id: i32, user_id: i32, action: &str) -> Result<(), ErrorResponse> {
+        if admin_id == user_id {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "You cannot {} your own account",
+                action
+            ))));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AdminServiceTrait for AdminService {
+    async fn ensure_admin(&self, user_id: i32) -> Result<(), ErrorResponse> {
+        let is_admin = self
+            .user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|user| UserRole::parse(&user.role).is_admin());
+
+        if !is_admin {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Admin role required".into(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_users(
+        &self,
+        admin_id: i32,
+        mut req: FindUsersRequest,
+    ) -> Result<ApiResponsePagination<Vec<UserResponse>>, ErrorResponse> {
+        self.ensure_admin(admin_id).await?;
+
+        req.page = req.page.max(1);
+        req.page_size = req.page_size.clamp(1, 100);
+
+        let (users, total_items) = self
+            .user_repository
+            .find_paginated(&req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / req.page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Users retrieved successfully".to_string(),
+            data: users.into_iter().map(UserResponse::from).collect(),
+            pagination: Pagination {
+                page: req.page,
+                page_size: req.page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+
+    async fn set_user_active(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        active: bool,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        self.ensure_admin(admin_id).await?;
+
+        if !active {
+            Self::ensure_not_self(admin_id, user_id, "deactivate")?;
+        }
+
+        let user = self
+            .user_repository
+            .set_active(user_id, active)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        info!(
+            "Admin {} {} user {}",
+            admin_id,
+            if active { "reactivated" } else { "deactivated" },
+            user_id
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: if active {
+                "User reactivated successfully"
+            } else {
+                "User deactivated successfully"
+            }
+            .to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+
+    async fn force_password_reset(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+    ) -> Result<ApiResponse<PasswordResetResponse>, ErrorResponse> {
+        self.ensure_admin(admin_id).await?;
+
+        let user = self
+            .user_repository
+            .require_password_reset(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let (reset_token, expires_at) = issue_token(
+            &self.token_repository,
+            &self.hashing,
+            user.id,
+            TokenPurpose::PasswordReset,
+            None,
+            Duration::hours(RESET_TOKEN_TTL_HOURS),
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
+        info!("Admin {} forced a password reset for user {}", admin_id, user_id);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Password reset required; pass the token on to the user".to_string(),
+            data: PasswordResetResponse {
+                user_id: user.id,
+                reset_token,
+                expires_at,
+            },
+        })
+    }
+
+    async fn set_user_role(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        role: UserRole,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        self.ensure_admin(admin_id).await?;
+
+        if !role.is_admin() {
+            Self::ensure_not_self(admin_id, user_id, "demote")?;
+        }
+
+        let user = self
+            .user_repository
+            .set_role(user_id, role)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        info!("Admin {} set the role of user {} to {}", admin_id, user_id, role.as_str());
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Role updated successfully".to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+
+    async fn erase_user(
+        &self,
+        admin_id: i32,
+        user_id: i32,
+        input: &EraseUserRequest,
+    ) -> Result<ApiResponse<ErasureResponse>, ErrorResponse> {
+        self.ensure_admin(admin_id).await?;
+
+        Self::ensure_not_self(admin_id, user_id, "erase")?;
+
+        self.privacy_service
+            .erase_account(admin_id, user_id, input.posts, input.reassign_to)
+            .await
+    }
+}
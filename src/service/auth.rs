@@ -1,20 +1,136 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use tracing::error;
+use uuid::Uuid;
 use crate::{
-    abstract_trait::{AuthServiceTrait, DynUserRepository},
+    abstract_trait::{AuthServiceTrait, DynMailer, DynUserRepository, DynUserTokenRepository},
     config::{Hashing, JwtConfig},
-    domain::{ApiResponse, CreateUserRequest, ErrorResponse, LoginRequest, RegisterRequest, UserResponse},
+    domain::{
+        ApiResponse, ChangeEmailRequest, ChangePasswordRequest, ConfirmEmailRequest,
+        CreateUserRequest, ErrorResponse, LoginRequest, RegisterRequest, ResetPasswordRequest,
+        TokenPurpose, UserResponse,
+    },
+    model::user::User,
+    model::user_token::UserToken,
+    service::user::assign_username,
     utils::AppError,
 };
 
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+const MAX_EMAIL_LENGTH: usize = 255;
+
+/// How long a confirmation sent to a new email address stays valid.
+const EMAIL_TOKEN_TTL_HOURS: i64 = 24;
+
+pub(crate) fn check_password(password: &str) -> Result<(), AppError> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AppError::ValidationError(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_email(email: &str) -> Result<(), AppError> {
+    let well_formed = email.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty() && domain.contains('.') && !domain.contains('@')
+    });
+
+    if !well_formed || email.chars().any(char::is_whitespace) || email.len() > MAX_EMAIL_LENGTH {
+        return Err(AppError::ValidationError("Email address is not valid".into()));
+    }
+
+    Ok(())
+}
+
+/// Issues a one-time token shaped `<id>.<secret>`; only a hash of the secret
+/// is stored, so a leaked table cannot be replayed.
+pub(crate) async fn issue_token(
+    repository: &DynUserTokenRepository,
+    hashing: &Hashing,
+    user_id: i32,
+    purpose: TokenPurpose,
+    new_email: Option<&str>,
+    ttl: Duration,
+) -> Result<(String, DateTime<Utc>), AppError> {
+    let secret = Uuid::new_v4().simple().to_string();
+    let token_hash = hashing.hash_password(&secret).await?;
+    let expires_at = Utc::now() + ttl;
+
+    let token = repository
+        .create(user_id, purpose, &token_hash, new_email, expires_at)
+        .await?;
+
+    Ok((format!("{}.{}", token.id, secret), expires_at))
+}
+
+/// Checks a token from [`issue_token`]; the caller consumes it once the
+/// action it guards has succeeded.
+pub(crate) async fn redeem_token(
+    repository: &DynUserTokenRepository,
+    hashing: &Hashing,
+    token: &str,
+    purpose: TokenPurpose,
+) -> Result<UserToken, AppError> {
+    let invalid = || AppError::ValidationError("Invalid or expired token".into());
+
+    let (id, secret) = token.split_once('.').ok_or_else(invalid)?;
+    let id: i32 = id.parse().map_err(|_| invalid())?;
+
+    let stored = repository
+        .find_by_id(id)
+        .await?
+        .filter(|stored| stored.purpose == purpose.as_str() && stored.expires_at > Utc::now())
+        .ok_or_else(invalid)?;
+
+    hashing
+        .compare_password(&stored.token_hash, secret)
+        .await
+        .map_err(|_| invalid())?;
+
+    Ok(stored)
+}
+
+/// Loads the signed-in user and checks their current password, for actions
+/// a stolen session alone must not be able to take.
+pub(crate) async fn verify_current_password(
+    repository: &DynUserRepository,
+    hashing: &Hashing,
+    user_id: i32,
+    password: &str,
+) -> Result<User, AppError> {
+    let user = repository
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User with id {} not found", user_id)))?;
+
+    if hashing.compare_password(&user.password, password).await.is_err() {
+        return Err(AppError::ValidationError("Current password is incorrect".into()));
+    }
+
+    Ok(user)
+}
+
 pub struct AuthService {
     repository: DynUserRepository,
+    token_repository: DynUserTokenRepository,
+    mailer: DynMailer,
     hashing: Hashing,
     jwt_config: JwtConfig,
 }
 
 impl AuthService {
-    pub fn new(repository: DynUserRepository, hashing: Hashing, jwt_config: JwtConfig) -> Self {
-        Self { repository, hashing, jwt_config }
+    pub fn new(
+        repository: DynUserRepository,
+        token_repository: DynUserTokenRepository,
+        mailer: DynMailer,
+        hashing: Hashing,
+        jwt_config: JwtConfig,
+    ) -> Self {
+        Self { repository, token_repository, mailer, hashing, jwt_config }
     }
 }
 
@@ -22,13 +138,21 @@ impl AuthService {
 impl AuthServiceTrait for AuthService {
     async fn register_user(&self, input: &RegisterRequest) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
         let exists = self.repository.find_by_email_exists(&input.email).await
-            .map_err(AppError::from)  
             .map_err(ErrorResponse::from)?; 
 
         if exists {
             return Err(ErrorResponse::from(AppError::EmailAlreadyExists));
         }
 
+        let username = assign_username(
+            &self.repository,
+            input.username.as_deref(),
+            &input.firstname,
+            &input.lastname,
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
         let hashed_password = self.hashing.hash_password(&input.password).await
             .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))?;
 
@@ -37,10 +161,10 @@ impl AuthServiceTrait for AuthService {
             lastname: input.lastname.clone(),
             email: input.email.clone(),
             password: hashed_password,
+            username: Some(username.clone()),
         };
 
-        let create_user = self.repository.create_user(&request).await
-            .map_err(AppError::from)
+        let create_user = self.repository.create_user(&request, &username).await
             .map_err(ErrorResponse::from)?;
 
         Ok(ApiResponse {
@@ -52,7 +176,6 @@ impl AuthServiceTrait for AuthService {
 
     async fn login_user(&self, input: &LoginRequest) -> Result<ApiResponse<String>, ErrorResponse> {
         let user = self.repository.find_by_email(&input.email).await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?
             .ok_or_else(|| ErrorResponse::from(AppError::NotFound("User not found".to_string())))?;
 
@@ -60,7 +183,19 @@ impl AuthServiceTrait for AuthService {
             return Err(ErrorResponse::from(AppError::InvalidCredentials));
         }
 
-        let token = self.jwt_config.generate_token(user.id as i64)
+        if !user.is_active {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "This account has been deactivated".into(),
+            )));
+        }
+
+        if user.password_reset_required {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "A password reset is required before logging in".into(),
+            )));
+        }
+
+        let token = self.jwt_config.generate_token(user.id as i64, user.token_version)
             .map_err(ErrorResponse::from)?;
 
         Ok(ApiResponse {
@@ -70,7 +205,188 @@ impl AuthServiceTrait for AuthService {
         })
     }
 
-    fn verify_token(&self, token: &str) -> Result<i64, AppError> {
-        return self.jwt_config.verify_token(token).map_err(AppError::from);
+    async fn reset_password(&self, input: &ResetPasswordRequest) -> Result<ApiResponse<()>, ErrorResponse> {
+        let token = redeem_token(
+            &self.token_repository,
+            &self.hashing,
+            &input.token,
+            TokenPurpose::PasswordReset,
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
+        check_password(&input.password).map_err(ErrorResponse::from)?;
+
+        let hashed_password = self.hashing.hash_password(&input.password).await
+            .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))?;
+
+        self.repository.set_password(token.user_id, &hashed_password).await
+            .map_err(ErrorResponse::from)?;
+
+        self.token_repository
+            .delete_for_user(token.user_id, TokenPurpose::PasswordReset)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Password reset successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn change_password(
+        &self,
+        user_id: i32,
+        input: &ChangePasswordRequest,
+    ) -> Result<ApiResponse<String>, ErrorResponse> {
+        verify_current_password(&self.repository, &self.hashing, user_id, &input.current_password)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        check_password(&input.new_password).map_err(ErrorResponse::from)?;
+
+        let hashed_password = self.hashing.hash_password(&input.new_password).await
+            .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))?;
+
+        // Bumps the token version, signing out every session including this
+        // one, which gets a fresh token below.
+        let user = self.repository.set_password(user_id, &hashed_password).await
+            .map_err(ErrorResponse::from)?;
+
+        self.token_repository
+            .delete_for_user(user_id, TokenPurpose::PasswordReset)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let token = self.jwt_config.generate_token(user.id as i64, user.token_version)
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Password changed; other sessions have been signed out".to_string(),
+            data: token,
+        })
+    }
+
+    async fn request_email_change(
+        &self,
+        user_id: i32,
+        input: &ChangeEmailRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let user = verify_current_password(&self.repository, &self.hashing, user_id, &input.current_password)
+            .await
+            .map_err(ErrorResponse::from)?;
+        let new_email = input.new_email.trim();
+
+        check_email(new_email).map_err(ErrorResponse::from)?;
+
+        if new_email == user.email {
+            return Err(ErrorResponse::from(AppError::ValidationError(
+                "This is already your email address".into(),
+            )));
+        }
+
+        let taken = self.repository.find_by_email_exists(new_email).await
+            .map_err(ErrorResponse::from)?;
+
+        if taken {
+            return Err(ErrorResponse::from(AppError::EmailAlreadyExists));
+        }
+
+        let (token, expires_at) = issue_token(
+            &self.token_repository,
+            &self.hashing,
+            user.id,
+            TokenPurpose::EmailChange,
+            Some(new_email),
+            Duration::hours(EMAIL_TOKEN_TTL_HOURS),
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
+        self.mailer
+            .send(
+                new_email,
+                "Confirm your new email address",
+                &format!(
+                    "Confirm this address by posting the token below to /api/auth/confirm-email before {}.\n\n{}",
+                    expires_at.to_rfc3339(),
+                    token
+                ),
+            )
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        // The old address only hears about it; a failure here must not block the change.
+        if let Err(e) = self
+            .mailer
+            .send(
+                &user.email,
+                "Your email address is being changed",
+                "A change of the email address on your account was requested. If this was not you, change your password.",
+            )
+            .await
+        {
+            error!("Failed to notify user {} of an email change: {}", user.id, e);
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Check the new address for a confirmation token".to_string(),
+            data: (),
+        })
+    }
+
+    async fn confirm_email_change(
+        &self,
+        input: &ConfirmEmailRequest,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let token = redeem_token(
+            &self.token_repository,
+            &self.hashing,
+            &input.token,
+            TokenPurpose::EmailChange,
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
+        let new_email = token.new_email.ok_or_else(|| {
+            ErrorResponse::from(AppError::ValidationError("Invalid or expired token".into()))
+        })?;
+
+        let user = self.repository.set_email(token.user_id, &new_email).await
+            .map_err(ErrorResponse::from)?;
+
+        self.token_repository
+            .delete_for_user(token.user_id, TokenPurpose::EmailChange)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Email address updated successfully".to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+
+    async fn authenticate(&self, token: &str) -> Result<i64, AppError> {
+        let claims = self.jwt_config.verify_token(token)?;
+
+        let user = self
+            .repository
+            .find_by_id(claims.user_id as i32)
+            .await?
+            .ok_or(AppError::TokenValidationError)?;
+
+        if !user.is_active {
+            return Err(AppError::Forbidden("This account has been deactivated".into()));
+        }
+
+        if user.token_version != claims.ver {
+            return Err(AppError::TokenValidationError);
+        }
+
+        Ok(claims.user_id)
     }
 }
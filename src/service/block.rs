@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{BlockServiceTrait, DynBlockRepository, DynUserRepository},
+    domain::{
+        ApiResponse, ApiResponsePagination, BlockKind, BlockResponse, BlockedUserResponse,
+        ErrorResponse, FindBlocksRequest, Pagination, PublicUserResponse,
+    },
+    utils::AppError,
+};
+
+pub struct BlockService {
+    repository: DynBlockRepository,
+    user_repository: DynUserRepository,
+}
+
+impl BlockService {
+    pub fn new(repository: DynBlockRepository, user_repository: DynUserRepository) -> Self {
+        Self {
+            repository,
+            user_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl BlockServiceTrait for BlockService {
+    async fn set_block(
+        &self,
+        user_id: i32,
+        username: &str,
+        kind: BlockKind,
+        active: bool,
+    ) -> Result<ApiResponse<BlockResponse>, ErrorResponse> {
+        let target_id = self
+            .user_repository
+            .find_by_username(username)
+            .await
+            .map_err(ErrorResponse::from)?
+            .map(|user| user.id)
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!("User {} not found", username)))
+            })?;
+
+        if target_id == user_id {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "You cannot {} yourself",
+                kind.as_str()
+            ))));
+        }
+
+        let message = if active {
+            self.repository
+                .add(user_id, target_id, kind)
+                .await
+                .map_err(ErrorResponse::from)?;
+
+            match kind {
+                BlockKind::Block => "User blocked",
+                BlockKind::Mute => "User muted",
+            }
+        } else {
+            let removed = self
+                .repository
+                .remove(user_id, target_id, kind)
+                .await
+                .map_err(ErrorResponse::from)?;
+
+            if !removed {
+                return Err(ErrorResponse::from(AppError::NotFound(format!(
+                    "You have not {} this user",
+                    match kind {
+                        BlockKind::Block => "blocked",
+                        BlockKind::Mute => "muted",
+                    }
+                ))));
+            }
+
+            match kind {
+                BlockKind::Block => "User unblocked",
+                BlockKind::Mute => "User unmuted",
+            }
+        };
+
+        let kinds = self
+            .repository
+            .find_kinds(user_id, target_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data: BlockResponse {
+                blocked: kinds.contains(&BlockKind::Block),
+                muted: kinds.contains(&BlockKind::Mute),
+            },
+        })
+    }
+
+    async fn get_blocks(
+        &self,
+        user_id: i32,
+        mut req: FindBlocksRequest,
+    ) -> Result<ApiResponsePagination<Vec<BlockedUserResponse>>, ErrorResponse> {
+        req.page = req.page.max(1);
+        req.page_size = req.page_size.clamp(1, 100);
+
+        let (blocks, total_items) = self
+            .repository
+            .find_by_blocker(user_id, &req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let ids: Vec<i32> = blocks.iter().map(|block| block.blocked_id).collect();
+
+        let users: HashMap<i32, PublicUserResponse> = self
+            .user_repository
+            .find_by_ids(&ids)
+            .await
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|user| (user.id, PublicUserResponse::from(user)))
+            .collect();
+
+        let data = blocks
+            .into_iter()
+            .filter_map(|block| {
+                Some(BlockedUserResponse {
+                    user: users.get(&block.blocked_id)?.clone(),
+                    kind: BlockKind::parse(&block.kind),
+                    created_at: block.created_at,
+                })
+            })
+            .collect();
+
+        let total_pages = (total_items as f64 / req.page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Blocked users retrieved successfully".to_string(),
+            data,
+            pagination: Pagination {
+                page: req.page,
+                page_size: req.page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+
+    async fn find_hidden(&self, viewer_id: i32) -> Result<Vec<i32>, ErrorResponse> {
+        self.repository
+            .find_hidden(viewer_id)
+            .await
+            .map_err(ErrorResponse::from)
+    }
+}
@@ -1,22 +1,188 @@
+use std::collections::HashMap;
+
 use crate::{
-    abstract_trait::{CategoryServiceTrait, DynCategoryRepository},
+    abstract_trait::{CategoryServiceTrait, DynCategoryRepository, DynUserRepository},
     domain::{
-        ApiResponse, ApiResponsePagination, CategoryResponse, CreateCategoryRequest, ErrorResponse,
-        FindAllCategoryRequest, Pagination, UpdateCategoryRequest,
+        ApiResponse, ApiResponsePagination, CategoryMergeResponse, CategoryResponse,
+        CategoryTreeResponse, CreateCategoryRequest, ErrorResponse, FindAllCategoryRequest,
+        Pagination, UpdateCategoryRequest, UserRole,
     },
-    utils::AppError,
+    model::category::Category,
+    utils::{generate_slug, AppError},
 };
 use async_trait::async_trait;
 use tracing::info;
 
 pub struct CategoryService {
     repository: DynCategoryRepository,
+    user_repository: DynUserRepository,
 }
 
+const MAX_NAME_LENGTH: usize = 200;
+
+const MAX_DESCRIPTION_LENGTH: usize = 2000;
+
+const MAX_SLUG_LENGTH: usize = 220;
+
 impl CategoryService {
-    pub fn new(repository: DynCategoryRepository) -> Self {
-        Self { repository }
+    pub fn new(repository: DynCategoryRepository, user_repository: DynUserRepository) -> Self {
+        Self {
+            repository,
+            user_repository,
+        }
+    }
+
+    async fn ensure_moderator(&self, user_id: i32) -> Result<(), ErrorResponse> {
+        let is_moderator = self
+            .user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|user| UserRole::parse(&user.role).can_moderate());
+
+        if !is_moderator {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Moderator role required".into(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Trims the name and makes sure no other category uses it, ignoring case.
+    async fn check_name(&self, name: &str, id: Option<i32>) -> Result<String, AppError> {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(AppError::ValidationError("Name is required".into()));
+        }
+
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Name cannot be longer than {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+
+        if let Some(existing) = self.repository.find_by_name(name).await?
+            && Some(existing.id) != id
+        {
+            return Err(AppError::Conflict(format!(
+                "A category named {} already exists",
+                existing.name
+            )));
+        }
+
+        Ok(name.to_string())
+    }
+
+    async fn check_slug(&self, slug: &str, id: Option<i32>) -> Result<(), AppError> {
+        if slug.is_empty() || generate_slug(slug) != slug {
+            return Err(AppError::ValidationError(
+                "Slug may only contain lowercase letters, digits and dashes".into(),
+            ));
+        }
+
+        if slug.len() > MAX_SLUG_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Slug cannot be longer than {} characters",
+                MAX_SLUG_LENGTH
+            )));
+        }
+
+        if let Some(existing) = self.repository.find_by_slug(slug).await?
+            && Some(existing.id) != id
+        {
+            return Err(AppError::Conflict(format!("Slug {} is already taken", slug)));
+        }
+
+        Ok(())
+    }
+
+    /// A slug derived from the name, numbered when the plain one is taken.
+    async fn unique_slug(&self, name: &str) -> Result<String, AppError> {
+        let base = Some(generate_slug(name))
+            .filter(|slug| !slug.is_empty())
+            .unwrap_or_else(|| "category".to_string());
+
+        let mut slug = base.clone();
+        let mut suffix = 1;
+
+        while self.repository.find_by_slug(&slug).await?.is_some() {
+            suffix += 1;
+            slug = format!("{}-{}", base, suffix);
+        }
+
+        Ok(slug)
     }
+
+    async fn reassign(
+        &self,
+        id: i32,
+        target_id: i32,
+        adopt_children: bool,
+    ) -> Result<CategoryMergeResponse, ErrorResponse> {
+        let (moved_posts, moved_categories) = self
+            .repository
+            .reassign(id, target_id, adopt_children)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(CategoryMergeResponse {
+            source_id: id,
+            target_id,
+            moved_posts,
+            moved_categories,
+        })
+    }
+
+    fn check_description(description: Option<&str>) -> Result<(), AppError> {
+        if description.is_some_and(|text| text.chars().count() > MAX_DESCRIPTION_LENGTH) {
+            return Err(AppError::ValidationError(format!(
+                "Description cannot be longer than {} characters",
+                MAX_DESCRIPTION_LENGTH
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Nests categories below their parents, keeping the given order among
+/// siblings. Categories whose parent is missing become roots.
+fn build_tree(categories: Vec<Category>) -> Vec<CategoryTreeResponse> {
+    fn attach(
+        node: &mut CategoryTreeResponse,
+        children: &mut HashMap<i32, Vec<CategoryTreeResponse>>,
+    ) {
+        if let Some(mut nodes) = children.remove(&node.category.id) {
+            for child in &mut nodes {
+                attach(child, children);
+            }
+            node.children = nodes;
+        }
+    }
+
+    let ids: Vec<i32> = categories.iter().map(|category| category.id).collect();
+
+    let mut roots = Vec::new();
+    let mut children: HashMap<i32, Vec<CategoryTreeResponse>> = HashMap::new();
+
+    for category in categories {
+        match category.parent_id.filter(|parent_id| ids.contains(parent_id)) {
+            Some(parent_id) => children
+                .entry(parent_id)
+                .or_default()
+                .push(CategoryTreeResponse::from(category)),
+            None => roots.push(CategoryTreeResponse::from(category)),
+        }
+    }
+
+    for root in &mut roots {
+        attach(root, &mut children);
+    }
+
+    roots
 }
 
 #[async_trait]
@@ -39,7 +205,7 @@ impl CategoryServiceTrait for CategoryService {
             .await
             .map_err(|e| {
                 tracing::error!("Repository error: {}", e);
-                AppError::from(e)
+                e
             })
             .map_err(|e| {
                 tracing::error!("Repository error: {}", e);
@@ -62,6 +228,7 @@ impl CategoryServiceTrait for CategoryService {
                 page_size,
                 total_items,
                 total_pages,
+                next_cursor: None,
             },
         })
     }
@@ -74,7 +241,6 @@ impl CategoryServiceTrait for CategoryService {
             .repository
             .find_by_id(id)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
         if let Some(category) = category {
@@ -91,15 +257,117 @@ impl CategoryServiceTrait for CategoryService {
         }
     }
 
+    async fn get_category_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<ApiResponse<CategoryResponse>, ErrorResponse> {
+        let category = self
+            .repository
+            .find_by_slug(slug)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Category with slug {} not found",
+                    slug
+                )))
+            })?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Category retrieved successfully".to_string(),
+            data: CategoryResponse::from(category),
+        })
+    }
+
+    async fn get_category_tree(
+        &self,
+    ) -> Result<ApiResponse<Vec<CategoryTreeResponse>>, ErrorResponse> {
+        let categories = self
+            .repository
+            .find_every()
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Category tree retrieved successfully".to_string(),
+            data: build_tree(categories),
+        })
+    }
+
+    async fn get_breadcrumbs(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<Vec<CategoryResponse>>, ErrorResponse> {
+        let ancestors = self
+            .repository
+            .find_ancestors(id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if ancestors.is_empty() {
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Category with id {} not found",
+                id
+            ))));
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Breadcrumbs retrieved successfully".to_string(),
+            data: ancestors.into_iter().map(CategoryResponse::from).collect(),
+        })
+    }
+
     async fn create_category(
         &self,
+        user_id: i32,
         input: &CreateCategoryRequest,
     ) -> Result<ApiResponse<CategoryResponse>, ErrorResponse> {
+        self.ensure_moderator(user_id).await?;
+
+        if let Some(parent_id) = input.parent_id {
+            self.repository
+                .find_by_id(parent_id)
+                .await
+                .map_err(ErrorResponse::from)?
+                .ok_or_else(|| {
+                    ErrorResponse::from(AppError::NotFound(format!(
+                        "Parent category with id {} not found",
+                        parent_id
+                    )))
+                })?;
+        }
+
+        let name = self
+            .check_name(&input.name, None)
+            .await
+            .map_err(ErrorResponse::from)?;
+        Self::check_description(input.description.as_deref()).map_err(ErrorResponse::from)?;
+
+        let slug = match &input.slug {
+            Some(slug) => {
+                self.check_slug(slug, None)
+                    .await
+                    .map_err(ErrorResponse::from)?;
+                slug.clone()
+            }
+            None => self
+                .unique_slug(&name)
+                .await
+                .map_err(ErrorResponse::from)?,
+        };
+
+        let input = CreateCategoryRequest {
+            name,
+            ..input.clone()
+        };
+
         let category = self
             .repository
-            .create(input)
+            .create(&input, &slug)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
         info!("Category created: {:#?}", category);
@@ -113,13 +381,34 @@ impl CategoryServiceTrait for CategoryService {
 
     async fn update_category(
         &self,
+        user_id: i32,
         input: &UpdateCategoryRequest,
     ) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse> {
+        self.ensure_moderator(user_id).await?;
+
+        let mut input = input.clone();
+
+        if let Some(name) = &input.name {
+            input.name = Some(
+                self.check_name(name, input.id)
+                    .await
+                    .map_err(ErrorResponse::from)?,
+            );
+        }
+
+        if let Some(slug) = &input.slug {
+            self.check_slug(slug, input.id)
+                .await
+                .map_err(ErrorResponse::from)?;
+        }
+
+        Self::check_description(input.description.as_ref().and_then(|text| text.as_deref()))
+            .map_err(ErrorResponse::from)?;
+
         let category = self
             .repository
-            .update(input)
+            .update(&input)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
         Ok(Some(ApiResponse {
@@ -129,11 +418,12 @@ impl CategoryServiceTrait for CategoryService {
         }))
     }
 
-    async fn delete_category(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+    async fn delete_category(&self, user_id: i32, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+        self.ensure_moderator(user_id).await?;
+
         self.repository
             .delete(id)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
         Ok(ApiResponse {
@@ -142,4 +432,38 @@ impl CategoryServiceTrait for CategoryService {
             data: (),
         })
     }
+
+    async fn reassign_and_delete_category(
+        &self,
+        user_id: i32,
+        id: i32,
+        target_id: i32,
+    ) -> Result<ApiResponse<CategoryMergeResponse>, ErrorResponse> {
+        self.ensure_moderator(user_id).await?;
+
+        let data = self.reassign(id, target_id, false).await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Category deleted successfully".to_string(),
+            data,
+        })
+    }
+
+    async fn merge_category(
+        &self,
+        user_id: i32,
+        id: i32,
+        target_id: i32,
+    ) -> Result<ApiResponse<CategoryMergeResponse>, ErrorResponse> {
+        self.ensure_moderator(user_id).await?;
+
+        let data = self.reassign(id, target_id, true).await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Category merged successfully".to_string(),
+            data,
+        })
+    }
 }
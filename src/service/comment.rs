@@ -1,70 +1,610 @@
-use crate::{abstract_trait::{CommentServiceTrait, DynCommentRepository, }, domain::{ApiResponse, CommentResponse, CreateCommentRequest, ErrorResponse, UpdateCommentRequest},  utils::AppError};
+use std::collections::HashMap;
+
+use crate::{abstract_trait::{CommentServiceTrait, DynBlockRepository, DynCommentRepository, DynMentionService, DynModerationRepository, DynNotificationService, DynPostsRepository, DynReactionRepository, DynSpamFilter, DynUserRepository}, config::Config, domain::{ApiResponse, ApiResponsePagination, CommentPolicy, CommentResponse, CommentSort, CommentStatus, CommentTreeResponse, CreateCommentRequest, ErrorResponse, FindCommentTreeRequest, FindCommentsRequest, Pagination, PostReader, PublicUserResponse, ReactionTarget, UpdateCommentRequest, UserRole}, model::comment::Comment,  utils::AppError};
 use async_trait::async_trait;
+use tracing::{error, info};
 
 pub struct CommentService {
     repository: DynCommentRepository,
+    reaction_repository: DynReactionRepository,
+    user_repository: DynUserRepository,
+    moderation_repository: DynModerationRepository,
+    post_repository: DynPostsRepository,
+    block_repository: DynBlockRepository,
+    spam_filter: DynSpamFilter,
+    mention_service: DynMentionService,
+    notification_service: DynNotificationService,
+    max_depth: i32,
+    hold_new_users: bool,
+}
+
+/// Everything [`CommentService`] is built from, besides its settings.
+pub struct CommentServiceDeps {
+    pub repository: DynCommentRepository,
+    pub reaction_repository: DynReactionRepository,
+    pub user_repository: DynUserRepository,
+    pub moderation_repository: DynModerationRepository,
+    pub post_repository: DynPostsRepository,
+    pub block_repository: DynBlockRepository,
+    pub spam_filter: DynSpamFilter,
+    pub mention_service: DynMentionService,
+    pub notification_service: DynNotificationService,
 }
 
 impl CommentService {
-    pub fn new(repository: DynCommentRepository) -> Self {
-        Self { repository }
+    pub fn new(deps: CommentServiceDeps, config: &Config) -> Self {
+        let CommentServiceDeps {
+            repository,
+            reaction_repository,
+            user_repository,
+            moderation_repository,
+            post_repository,
+            block_repository,
+            spam_filter,
+            mention_service,
+            notification_service,
+        } = deps;
+
+        Self {
+            repository,
+            reaction_repository,
+            user_repository,
+            moderation_repository,
+            post_repository,
+            block_repository,
+            spam_filter,
+            mention_service,
+            notification_service,
+            max_depth: config.comment_max_depth,
+            hold_new_users: config.comment_hold_new_users,
+        }
+    }
+
+    /// The post's comment policy; fails while comments are closed.
+    async fn open_policy(&self, post_id: i32) -> Result<CommentPolicy, ErrorResponse> {
+        let policy = self
+            .moderation_repository
+            .find_comment_policy(post_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("Posts with id {} not found", post_id))))?;
+
+        if policy == CommentPolicy::Closed {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Comments are closed on this post".into(),
+            )));
+        }
+
+        Ok(policy)
+    }
+
+    /// Decides the initial status of a new comment from the post's policy,
+    /// the spam filters and the author's history.
+    async fn initial_status(&self, post_id: i32, author_id: i32, role: UserRole, text: &str) -> Result<CommentStatus, ErrorResponse> {
+        let policy = self.open_policy(post_id).await?;
+
+        if role.can_moderate() {
+            return Ok(CommentStatus::Approved);
+        }
+
+        if self.spam_filter.is_spam(text).await.map_err(ErrorResponse::from)? {
+            info!("Comment by user {} on post {} held as spam", author_id, post_id);
+            return Ok(CommentStatus::Spam);
+        }
+
+        match policy {
+            CommentPolicy::Open if self.hold_new_users => {
+                let trusted = self
+                    .moderation_repository
+                    .has_approved_comment(author_id)
+                    .await
+                    .map_err(ErrorResponse::from)?;
+
+                Ok(if trusted { CommentStatus::Approved } else { CommentStatus::Pending })
+            }
+            CommentPolicy::Open => Ok(CommentStatus::Approved),
+            _ => Ok(CommentStatus::Pending),
+        }
+    }
+
+    /// Someone blocked by the post's author, or by the author of the comment
+    /// being replied to, cannot comment there; nor can anyone who may not
+    /// read the post.
+    async fn ensure_not_blocked(&self, author_id: i32, post_id: i32, parent_author: Option<i32>) -> Result<(), ErrorResponse> {
+        let post_author = self
+            .post_repository
+            .get_post(post_id, PostReader::User(author_id))
+            .await
+            .map_err(ErrorResponse::from)?
+            .map(|post| post.user_id)
+            .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("Posts with id {} not found", post_id))))?;
+
+        let owners: Vec<i32> = std::iter::once(post_author).chain(parent_author).collect();
+
+        let blockers = self
+            .block_repository
+            .find_blockers(author_id, &owners)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if blockers.contains(&post_author) {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "You cannot comment on this post".into(),
+            )));
+        }
+
+        if !blockers.is_empty() {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "You cannot reply to this comment".into(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Mentions follow the comment's visibility; a failure is logged rather
+    /// than failing a write that already happened.
+    async fn sync_mentions(&self, comment: &Comment) {
+        let result = if comment.status == CommentStatus::Approved.as_str() && !comment.is_deleted {
+            self.mention_service
+                .sync(
+                    ReactionTarget::Comment,
+                    comment.id,
+                    comment.id_post_comment,
+                    comment.user_id,
+                    &comment.comment,
+                )
+                .await
+        } else {
+            self.mention_service
+                .clear(ReactionTarget::Comment, comment.id)
+                .await
+        };
+
+        if let Err(e) = result {
+            error!("Failed to sync mentions for comment {}: {}", comment.id, e);
+        }
+    }
+
+    /// Loads a comment that can still be edited and checks that `user_id`
+    /// either wrote it or is allowed to moderate.
+    async fn find_editable(&self, user_id: i32, comment_id: i32) -> Result<Comment, ErrorResponse> {
+        let comment = self
+            .repository
+            .find_by_id(comment_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .filter(|comment| !comment.is_deleted)
+            .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("Comment with id {} not found", comment_id))))?;
+
+        if comment.user_id == Some(user_id) {
+            return Ok(comment);
+        }
+
+        let is_moderator = self
+            .user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|user| UserRole::parse(&user.role).can_moderate());
+
+        if !is_moderator {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Only the author or a moderator can change this comment".into(),
+            )));
+        }
+
+        Ok(comment)
+    }
+
+    /// The status an edit by `editor_id` moves `comment` to, or `None` to
+    /// keep the current one. The new text goes through the same checks as a
+    /// new comment, so edits cannot slip spam or skip pre-moderation.
+    async fn edit_status(&self, comment: &Comment, editor_id: i32, text: &str) -> Result<Option<CommentStatus>, ErrorResponse> {
+        let editor = self
+            .user_repository
+            .find_by_id(editor_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("User with id {} not found", editor_id))))?;
+
+        let status = self
+            .initial_status(comment.id_post_comment, editor.id, UserRole::parse(&editor.role), text)
+            .await?;
+
+        Ok((status != CommentStatus::Approved).then_some(status))
+    }
+
+    /// Loads a comment `viewer_id` may see: published ones for everybody,
+    /// held or deleted ones only for their author and moderators.
+    async fn find_visible(&self, viewer_id: Option<i32>, comment_id: i32) -> Result<Comment, ErrorResponse> {
+        let not_found = || ErrorResponse::from(AppError::NotFound(format!("Comment with id {} not found", comment_id)));
+
+        let comment = self
+            .repository
+            .find_by_id(comment_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(not_found)?;
+
+        let published = !comment.is_deleted && comment.status == CommentStatus::Approved.as_str();
+
+        if published || (viewer_id.is_some() && comment.user_id == viewer_id) {
+            return Ok(comment);
+        }
+
+        let Some(viewer_id) = viewer_id else {
+            return Err(not_found());
+        };
+
+        let is_moderator = self
+            .user_repository
+            .find_by_id(viewer_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|user| UserRole::parse(&user.role).can_moderate());
+
+        if !is_moderator {
+            return Err(not_found());
+        }
+
+        Ok(comment)
+    }
+
+    /// Loads one page of the level below `parent_id` (or the top level of the
+    /// post) together with a bounded number of replies on every deeper level.
+    async fn load_tree(
+        &self,
+        post_id: i32,
+        parent_id: Option<i32>,
+        req: FindCommentTreeRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentTreeResponse>>, ErrorResponse> {
+        let page = req.page.max(1);
+        let page_size = req.page_size.clamp(1, 100);
+        let replies_page_size = req.replies_page_size.clamp(0, 50);
+
+        let (nodes, total_items) = self
+            .repository
+            .find_tree(
+                post_id,
+                parent_id,
+                &FindCommentTreeRequest {
+                    page,
+                    page_size,
+                    replies_page_size,
+                    ..req
+                },
+                self.max_depth,
+            )
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut nodes: Vec<CommentTreeResponse> = nodes.into_iter().map(CommentTreeResponse::from).collect();
+
+        self.attach_details(nodes.iter_mut().map(|node| &mut node.comment).collect())
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Comments retrieved successfully".to_string(),
+            data: build_tree(nodes, parent_id),
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+
+    /// Fills in reaction counts and author profiles with one query each.
+    async fn attach_details(&self, mut comments: Vec<&mut CommentResponse>) -> Result<(), AppError> {
+        let ids: Vec<i32> = comments.iter().map(|comment| comment.id).collect();
+
+        let mut counts = self
+            .reaction_repository
+            .count_by_targets(ReactionTarget::Comment, &ids)
+            .await?;
+
+        let mut author_ids: Vec<i32> = comments.iter().filter_map(|comment| comment.user_id).collect();
+        author_ids.sort_unstable();
+        author_ids.dedup();
+
+        let authors: HashMap<i32, PublicUserResponse> = self
+            .user_repository
+            .find_by_ids(&author_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, PublicUserResponse::from(user)))
+            .collect();
+
+        for comment in comments.iter_mut() {
+            if let Some(reactions) = counts.remove(&comment.id) {
+                comment.reactions = reactions;
+            }
+
+            comment.author = comment.user_id.and_then(|id| authors.get(&id).cloned());
+        }
+
+        Ok(())
+    }
+}
+
+/// Nests a flat list ordered by depth then id under the nodes it replies to.
+fn build_tree(
+    nodes: Vec<CommentTreeResponse>,
+    parent_id: Option<i32>,
+) -> Vec<CommentTreeResponse> {
+    let mut children: HashMap<i32, Vec<CommentTreeResponse>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    // Walking deepest-first means every node's replies are complete by the
+    // time the node itself is placed.
+    for mut node in nodes.into_iter().rev() {
+        if let Some(mut replies) = children.remove(&node.comment.id) {
+            replies.reverse();
+            node.replies = replies;
+        }
+
+        match node.comment.parent_id {
+            Some(parent) if node.comment.parent_id != parent_id => {
+                children.entry(parent).or_default().push(node)
+            }
+            _ => roots.push(node),
+        }
+    }
+
+    roots.reverse();
+    roots
+}
+
+/// Cursors are `id` for the chronological sorts and `score:id` for `top`.
+fn parse_cursor(sort: CommentSort, cursor: &str) -> Result<(i64, i32), AppError> {
+    let invalid = || AppError::ValidationError("Invalid cursor".into());
+
+    match sort {
+        CommentSort::Top => {
+            let (score, id) = cursor.split_once(':').ok_or_else(invalid)?;
+            Ok((score.parse().map_err(|_| invalid())?, id.parse().map_err(|_| invalid())?))
+        }
+        CommentSort::Newest | CommentSort::Oldest => Ok((0, cursor.parse().map_err(|_| invalid())?)),
     }
 }
 
 #[async_trait]
 impl CommentServiceTrait for CommentService {
-    async fn get_comments(&self) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse> {
-        let comments = self.repository.find_all().await .map_err(AppError::from).map_err(ErrorResponse::from)?;
-        
-        let response = comments.into_iter().map(|comment| {
-            CommentResponse::from(comment)
-        }).collect();
-        
-        Ok(ApiResponse{
+    async fn get_comments(
+        &self,
+        mut req: FindCommentsRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentResponse>>, ErrorResponse> {
+        req.page = req.page.max(1);
+        req.page_size = req.page_size.clamp(1, 100);
+
+        let after = req
+            .cursor
+            .as_deref()
+            .map(|cursor| parse_cursor(req.sort, cursor))
+            .transpose()
+            .map_err(ErrorResponse::from)?;
+
+        let (comments, total_items) = self
+            .repository
+            .find_paginated(&req, CommentStatus::Approved, after)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let next_cursor = match comments.last() {
+            Some(last) if comments.len() == req.page_size as usize => Some(match req.sort {
+                CommentSort::Top => format!("{}:{}", last.score, last.comment.id),
+                CommentSort::Newest | CommentSort::Oldest => last.comment.id.to_string(),
+            }),
+            _ => None,
+        };
+
+        let mut response: Vec<CommentResponse> = comments
+            .into_iter()
+            .map(|ranked| CommentResponse::from(ranked.comment))
+            .collect();
+
+        self.attach_details(response.iter_mut().collect()).await.map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / req.page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
             status: "success".to_string(),
             message: "Comments retrieved successfully".to_string(),
-            data: response
+            data: response,
+            pagination: Pagination {
+                page: req.page,
+                page_size: req.page_size,
+                total_items,
+                total_pages,
+                next_cursor,
+            },
         })
     }
 
-    async fn get_comment(&self, id: i32) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
-        let comment = self.repository.find_by_id(id).await .map_err(AppError::from).map_err(ErrorResponse::from)?;
+    async fn get_post_comments(
+        &self,
+        post_id: i32,
+        mut req: FindCommentsRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentResponse>>, ErrorResponse> {
+        req.post_id = Some(post_id);
 
-        
-        
-        if let Some(comment) = comment{
-            Ok(Some(ApiResponse{
-                status: "success".to_string(),
-                message: "Comment retrieved successfully".to_string(),
-                data: CommentResponse::from(comment),
-            }))
-        }else{
-            Err(ErrorResponse::from(AppError::NotFound(format!("Comment with id {} not found", id))))
-        }
+        self.get_comments(req).await
+    }
+
+    async fn get_comment(&self, viewer_id: i32, id: i32) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
+        let comment = self.find_visible(Some(viewer_id), id).await?;
+
+        let mut data = CommentResponse::from(comment);
+
+        self.attach_details(vec![&mut data]).await.map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse{
+            status: "success".to_string(),
+            message: "Comment retrieved successfully".to_string(),
+            data,
+        }))
+    }
+
+    async fn find_published(&self, id: i32) -> Result<Option<CommentResponse>, AppError> {
+        let comment = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .filter(|comment| !comment.is_deleted && comment.status == CommentStatus::Approved.as_str());
+
+        let Some(comment) = comment else {
+            return Ok(None);
+        };
+
+        let mut data = CommentResponse::from(comment);
+        self.attach_details(vec![&mut data]).await?;
+
+        Ok(Some(data))
+    }
+
+    async fn get_comment_tree(
+        &self,
+        post_id: i32,
+        req: FindCommentTreeRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentTreeResponse>>, ErrorResponse> {
+        self.load_tree(post_id, None, req).await
+    }
+
+    async fn get_replies(
+        &self,
+        comment_id: i32,
+        req: FindCommentTreeRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentTreeResponse>>, ErrorResponse> {
+        let comment = self.find_visible(req.viewer_id, comment_id).await?;
+
+        self.load_tree(comment.id_post_comment, Some(comment.id), req).await
     }
 
     async fn create_comment(&self, input: &CreateCommentRequest) -> Result<ApiResponse<CommentResponse>, ErrorResponse> {
-        let comment = self.repository.create(input).await .map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let author = self
+            .user_repository
+            .find_by_id(input.user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("User with id {} not found", input.user_id))))?;
+
+        let status = self
+            .initial_status(input.id_post_comment, author.id, UserRole::parse(&author.role), &input.comment)
+            .await?;
+
+        let (depth, parent_author) = match input.parent_id {
+            Some(parent_id) => {
+                let parent = self
+                    .repository
+                    .find_by_id(parent_id)
+                    .await
+                    .map_err(ErrorResponse::from)?
+                    .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("Comment with id {} not found", parent_id))))?;
+
+                if parent.id_post_comment != input.id_post_comment {
+                    return Err(ErrorResponse::from(AppError::ValidationError(
+                        "Parent comment belongs to a different post".into(),
+                    )));
+                }
+
+                if parent.is_deleted || parent.status != CommentStatus::Approved.as_str() {
+                    return Err(ErrorResponse::from(AppError::ValidationError(
+                        "Cannot reply to a deleted or unpublished comment".into(),
+                    )));
+                }
+
+                if parent.depth >= self.max_depth {
+                    return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                        "Replies cannot be nested more than {} levels deep",
+                        self.max_depth
+                    ))));
+                }
+
+                (parent.depth + 1, parent.user_id)
+            }
+            None => (0, None),
+        };
+
+        self.ensure_not_blocked(author.id, input.id_post_comment, parent_author).await?;
+
+        let user_name = format!("{} {}", author.firstname, author.lastname);
+
+        let comment = self.repository.create(input, &user_name, depth, status).await.map_err(ErrorResponse::from)?;
+
+        self.sync_mentions(&comment).await;
+
+        if status == CommentStatus::Approved
+            && let Err(e) = self.notification_service.comment_published(&comment).await
+        {
+            error!("Failed to send notifications for comment {}: {}", comment.id, e);
+        }
+
+        let mut data = CommentResponse::from(comment);
+        data.author = Some(PublicUserResponse::from(author));
         
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Comment created successfully".to_string(),
-            data: CommentResponse::from(comment),
+            message: match status {
+                CommentStatus::Pending | CommentStatus::Spam => "Comment submitted for moderation".to_string(),
+                _ => "Comment created successfully".to_string(),
+            },
+            data,
         })
     }
 
-    async fn update_comment(&self, input: &UpdateCommentRequest) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
-        let comment = self.repository.update(input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+    async fn update_comment(&self, user_id: i32, input: &UpdateCommentRequest) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
+        let id = input
+            .id
+            .ok_or_else(|| ErrorResponse::from(AppError::ValidationError("ID is required".into())))?;
+
+        let previous = self.find_editable(user_id, id).await?;
+
+        let status = self.edit_status(&previous, user_id, &input.comment).await?;
+
+        let comment = self.repository.update(input, status).await.map_err(ErrorResponse::from)?;
+
+        self.sync_mentions(&comment).await;
+
+        let withdrawn = previous.status == CommentStatus::Approved.as_str()
+            && comment.status != CommentStatus::Approved.as_str();
+
+        if withdrawn && let Err(e) = self.notification_service.comment_withdrawn(comment.id).await {
+            error!("Failed to withdraw notifications for comment {}: {}", comment.id, e);
+        }
+
+        let mut data = CommentResponse::from(comment);
+
+        self.attach_details(vec![&mut data]).await.map_err(ErrorResponse::from)?;
         
         Ok(Some(ApiResponse {
             status: "success".to_string(),
-            message: "Comment updated successfully".to_string(),
-            data: CommentResponse::from(comment),
+            message: match status {
+                Some(CommentStatus::Pending | CommentStatus::Spam) => "Comment submitted for moderation".to_string(),
+                _ => "Comment updated successfully".to_string(),
+            },
+            data,
         }))
     }
 
-    async fn delete_comment(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        self.repository.delete(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+    async fn delete_comment(&self, user_id: i32, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+        self.find_editable(user_id, id).await?;
+
+        self.repository.delete(id).await.map_err(ErrorResponse::from)?;
+
+        if let Err(e) = self.mention_service.clear(ReactionTarget::Comment, id).await {
+            error!("Failed to clear mentions for comment {}: {}", id, e);
+        }
+
+        if let Err(e) = self.notification_service.comment_withdrawn(id).await {
+            error!("Failed to withdraw notifications for comment {}: {}", id, e);
+        }
         
         Ok(ApiResponse {
             status: "success".to_string(),
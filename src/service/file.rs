@@ -10,6 +10,7 @@ use crate::{
     domain::{DeleteResponse, UploadResponse},
 };
 
+#[derive(Default)]
 pub struct FileService {}
 
 impl FileService {
@@ -56,7 +57,7 @@ impl FileServiceTrait for FileService {
             saved_file_name.push_str(ext);
         }
 
-        let folder_path = Path::new(upload_dir).join(today);
+        let folder_path = Path::new(upload_dir).join(&today);
         if !folder_path.exists() {
             fs::create_dir_all(&folder_path).map_err(|_| {
                 (
@@ -83,7 +84,7 @@ impl FileServiceTrait for FileService {
             )
         })?;
 
-        file.write(&file_data).await.map_err(|_| {
+        file.write_all(&file_data).await.map_err(|_| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(UploadResponse {
@@ -94,9 +95,10 @@ impl FileServiceTrait for FileService {
             )
         })?;
 
+        // Stored relative to `upload_dir` so `delete_image` can locate it later.
         Ok(Json(UploadResponse {
             message: "File uploaded successfully".to_string(),
-            file_name: saved_file_name,
+            file_name: format!("{}/{}", today, saved_file_name),
             file_path: file_path.to_string_lossy().to_string(),
         }))
     }
@@ -129,4 +131,8 @@ impl FileServiceTrait for FileService {
             ))
         }
     }
+
+    async fn read_image(&self, upload_dir: &str, file_name: &str) -> Option<Vec<u8>> {
+        tokio::fs::read(Path::new(upload_dir).join(file_name)).await.ok()
+    }
 }
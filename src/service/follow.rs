@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{
+        DynBlockRepository, DynCategoryRepository, DynFollowRepository, DynUserRepository,
+        FollowServiceTrait,
+    },
+    domain::{
+        ApiResponse, ApiResponsePagination, CategoryResponse, ErrorResponse, FindFollowsRequest,
+        FollowResponse, FollowTarget, Pagination, PublicUserResponse,
+    },
+    utils::AppError,
+};
+
+pub struct FollowService {
+    repository: DynFollowRepository,
+    user_repository: DynUserRepository,
+    category_repository: DynCategoryRepository,
+    block_repository: DynBlockRepository,
+}
+
+impl FollowService {
+    pub fn new(
+        repository: DynFollowRepository,
+        user_repository: DynUserRepository,
+        category_repository: DynCategoryRepository,
+        block_repository: DynBlockRepository,
+    ) -> Self {
+        Self {
+            repository,
+            user_repository,
+            category_repository,
+            block_repository,
+        }
+    }
+
+    async fn find_user_id(&self, username: &str) -> Result<i32, ErrorResponse> {
+        self.user_repository
+            .find_by_username(username)
+            .await
+            .map_err(ErrorResponse::from)?
+            .map(|user| user.id)
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!("User {} not found", username)))
+            })
+    }
+
+    async fn ensure_category(&self, category_id: i32) -> Result<(), ErrorResponse> {
+        self.category_repository
+            .find_by_id(category_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Category with id {} not found",
+                    category_id
+                )))
+            })?;
+
+        Ok(())
+    }
+
+    async fn set_following(
+        &self,
+        follower_id: i32,
+        target: FollowTarget,
+        target_id: i32,
+        following: bool,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse> {
+        if target == FollowTarget::User && target_id == follower_id {
+            return Err(ErrorResponse::from(AppError::ValidationError(
+                "You cannot follow yourself".into(),
+            )));
+        }
+
+        if following && target == FollowTarget::User {
+            let blocked = self
+                .block_repository
+                .find_blockers(follower_id, &[target_id])
+                .await
+                .map_err(ErrorResponse::from)?;
+
+            if !blocked.is_empty() {
+                return Err(ErrorResponse::from(AppError::Forbidden(
+                    "You cannot follow this user".into(),
+                )));
+            }
+        }
+
+        let message = if following {
+            self.repository
+                .follow(follower_id, target, target_id)
+                .await
+                .map_err(ErrorResponse::from)?;
+
+            "Followed successfully"
+        } else {
+            let removed = self
+                .repository
+                .unfollow(follower_id, target, target_id)
+                .await
+                .map_err(ErrorResponse::from)?;
+
+            if !removed {
+                return Err(ErrorResponse::from(AppError::NotFound(
+                    "You are not following this".into(),
+                )));
+            }
+
+            "Unfollowed successfully"
+        };
+
+        let followers = self
+            .repository
+            .count_followers(target, target_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data: FollowResponse {
+                following,
+                followers,
+            },
+        })
+    }
+
+    /// Loads the users behind a page of ids, keeping the page order.
+    async fn user_page(
+        &self,
+        (ids, total_items): (Vec<i32>, i64),
+        req: &FindFollowsRequest,
+        message: &str,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse> {
+        let mut users: HashMap<i32, PublicUserResponse> = self
+            .user_repository
+            .find_by_ids(&ids)
+            .await
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|user| (user.id, PublicUserResponse::from(user)))
+            .collect();
+
+        let data = ids.iter().filter_map(|id| users.remove(id)).collect();
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data,
+            pagination: pagination(req, total_items),
+        })
+    }
+}
+
+fn clamp(req: FindFollowsRequest) -> FindFollowsRequest {
+    FindFollowsRequest {
+        page: req.page.max(1),
+        page_size: req.page_size.clamp(1, 100),
+    }
+}
+
+fn pagination(req: &FindFollowsRequest, total_items: i64) -> Pagination {
+    Pagination {
+        page: req.page,
+        page_size: req.page_size,
+        total_items,
+        total_pages: (total_items as f64 / req.page_size as f64).ceil() as i32,
+        next_cursor: None,
+    }
+}
+
+#[async_trait]
+impl FollowServiceTrait for FollowService {
+    async fn follow_user(
+        &self,
+        follower_id: i32,
+        username: &str,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse> {
+        let user_id = self.find_user_id(username).await?;
+
+        self.set_following(follower_id, FollowTarget::User, user_id, true)
+            .await
+    }
+
+    async fn unfollow_user(
+        &self,
+        follower_id: i32,
+        username: &str,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse> {
+        let user_id = self.find_user_id(username).await?;
+
+        self.set_following(follower_id, FollowTarget::User, user_id, false)
+            .await
+    }
+
+    async fn follow_category(
+        &self,
+        follower_id: i32,
+        category_id: i32,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse> {
+        self.ensure_category(category_id).await?;
+
+        self.set_following(follower_id, FollowTarget::Category, category_id, true)
+            .await
+    }
+
+    async fn unfollow_category(
+        &self,
+        follower_id: i32,
+        category_id: i32,
+    ) -> Result<ApiResponse<FollowResponse>, ErrorResponse> {
+        self.set_following(follower_id, FollowTarget::Category, category_id, false)
+            .await
+    }
+
+    async fn get_user_followers(
+        &self,
+        username: &str,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse> {
+        let req = clamp(req);
+        let user_id = self.find_user_id(username).await?;
+
+        let page = self
+            .repository
+            .find_followers(FollowTarget::User, user_id, &req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        self.user_page(page, &req, "Followers retrieved successfully")
+            .await
+    }
+
+    async fn get_user_following(
+        &self,
+        username: &str,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse> {
+        let req = clamp(req);
+        let user_id = self.find_user_id(username).await?;
+
+        let page = self
+            .repository
+            .find_following(user_id, FollowTarget::User, &req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        self.user_page(page, &req, "Followed users retrieved successfully")
+            .await
+    }
+
+    async fn get_followed_categories(
+        &self,
+        username: &str,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<CategoryResponse>>, ErrorResponse> {
+        let req = clamp(req);
+        let user_id = self.find_user_id(username).await?;
+
+        let (ids, total_items) = self
+            .repository
+            .find_following(user_id, FollowTarget::Category, &req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut categories: HashMap<i32, CategoryResponse> = self
+            .category_repository
+            .find_by_ids(&ids)
+            .await
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|category| (category.id, CategoryResponse::from(category)))
+            .collect();
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Followed categories retrieved successfully".to_string(),
+            data: ids.iter().filter_map(|id| categories.remove(id)).collect(),
+            pagination: pagination(&req, total_items),
+        })
+    }
+
+    async fn get_category_followers(
+        &self,
+        category_id: i32,
+        req: FindFollowsRequest,
+    ) -> Result<ApiResponsePagination<Vec<PublicUserResponse>>, ErrorResponse> {
+        let req = clamp(req);
+        self.ensure_category(category_id).await?;
+
+        let page = self
+            .repository
+            .find_followers(FollowTarget::Category, category_id, &req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        self.user_page(page, &req, "Followers retrieved successfully")
+            .await
+    }
+}
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{abstract_trait::Mailer, utils::AppError};
+
+/// Logs that a message would have been sent instead of sending it; the
+/// default until a transport is configured. Bodies carry one-time tokens, so
+/// only the recipient and subject are written.
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, to: &str, subject: &str, _body: &str) -> Result<(), AppError> {
+        info!("Mail to {}: {}", to, subject);
+
+        Ok(())
+    }
+}
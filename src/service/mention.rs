@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{
+    abstract_trait::{
+        DynBlockRepository, DynMentionRepository, DynNotificationService, DynPostsRepository,
+        MentionServiceTrait,
+    },
+    domain::{CreateNotificationRequest, NotificationKind, PostReader, ReactionTarget},
+    utils::AppError,
+};
+
+/// Handles beyond this many in one text are ignored.
+const MAX_MENTIONS: usize = 20;
+
+const MAX_HANDLE_LENGTH: usize = 64;
+
+/// Lowercase `@handle`s in `text`, e.g. `@jane.doe`. An `@` glued to a
+/// preceding word, as in an email address, does not start a mention.
+fn extract_handles(text: &str) -> Vec<String> {
+    let is_handle_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '_' | '-');
+
+    let mut handles = BTreeSet::new();
+    let mut previous: Option<char> = None;
+
+    for (index, c) in text.char_indices() {
+        let starts_mention = c == '@' && !previous.is_some_and(|p| p.is_alphanumeric() || p == '@');
+        previous = Some(c);
+
+        if !starts_mention {
+            continue;
+        }
+
+        let rest = &text[index + 1..];
+        let end = rest.find(|c: char| !is_handle_char(c)).unwrap_or(rest.len());
+        let handle = rest[..end].trim_end_matches(['.', '_', '-']);
+
+        if !handle.is_empty() && handle.chars().count() <= MAX_HANDLE_LENGTH {
+            handles.insert(handle.to_lowercase());
+        }
+
+        if handles.len() == MAX_MENTIONS {
+            break;
+        }
+    }
+
+    handles.into_iter().collect()
+}
+
+pub struct MentionService {
+    repository: DynMentionRepository,
+    block_repository: DynBlockRepository,
+    post_repository: DynPostsRepository,
+    notification_service: DynNotificationService,
+}
+
+impl MentionService {
+    pub fn new(
+        repository: DynMentionRepository,
+        block_repository: DynBlockRepository,
+        post_repository: DynPostsRepository,
+        notification_service: DynNotificationService,
+    ) -> Self {
+        Self {
+            repository,
+            block_repository,
+            post_repository,
+            notification_service,
+        }
+    }
+
+    async fn apply(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        author_id: Option<i32>,
+        user_ids: &[i32],
+    ) -> Result<(), AppError> {
+        let (added, removed) = self.repository.replace(target, target_id, user_ids).await?;
+
+        let notifications: Vec<CreateNotificationRequest> = added
+            .iter()
+            .map(|&user_id| CreateNotificationRequest {
+                user_id,
+                actor_id: author_id,
+                kind: NotificationKind::Mention,
+                target_type: target,
+                target_id,
+            })
+            .collect();
+
+        self.notification_service.notify(notifications).await?;
+        self.notification_service
+            .withdraw(&[NotificationKind::Mention], target, target_id, Some(&removed))
+            .await?;
+
+        if !added.is_empty() || !removed.is_empty() {
+            info!(
+                "Mentions on {} {}: {} added, {} removed",
+                target.as_str(),
+                target_id,
+                added.len(),
+                removed.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MentionServiceTrait for MentionService {
+    async fn sync(
+        &self,
+        target: ReactionTarget,
+        target_id: i32,
+        post_id: i32,
+        author_id: Option<i32>,
+        text: &str,
+    ) -> Result<(), AppError> {
+        let mut user_ids = self
+            .repository
+            .resolve_handles(&extract_handles(text))
+            .await?;
+
+        // Mentioning yourself is not worth a notification.
+        user_ids.retain(|&user_id| Some(user_id) != author_id);
+
+        // Nor can you reach someone who blocked you this way.
+        if let Some(author_id) = author_id {
+            let blockers = self
+                .block_repository
+                .find_blockers(author_id, &user_ids)
+                .await?;
+
+            user_ids.retain(|user_id| !blockers.contains(user_id));
+        }
+
+        // Nor notify someone about a post they are not allowed to open.
+        let mut readers = Vec::with_capacity(user_ids.len());
+        for user_id in user_ids {
+            if self
+                .post_repository
+                .get_post(post_id, PostReader::User(user_id))
+                .await?
+                .is_some()
+            {
+                readers.push(user_id);
+            }
+        }
+        let user_ids = readers;
+
+        self.apply(target, target_id, author_id, &user_ids).await
+    }
+
+    async fn clear(&self, target: ReactionTarget, target_id: i32) -> Result<(), AppError> {
+        self.apply(target, target_id, None, &[]).await
+    }
+
+    async fn clear_post(&self, post_id: i32) -> Result<(), AppError> {
+        self.notification_service.clear_post(post_id).await?;
+        self.repository.delete_for_post(post_id).await
+    }
+}
@@ -1,13 +1,39 @@
+mod admin;
 mod auth;
+mod block;
 mod category;
 mod comment;
 mod file;
+mod follow;
+mod mailer;
+mod mention;
+mod moderation;
+mod notification;
 mod posts;
+mod privacy;
+mod reaction;
+mod report;
+mod spam;
+mod stream;
 mod user;
+mod view;
 
+pub use self::admin::AdminService;
 pub use self::auth::AuthService;
+pub use self::block::BlockService;
 pub use self::category::CategoryService;
-pub use self::comment::CommentService;
+pub use self::comment::{CommentService, CommentServiceDeps};
 pub use self::file::FileService;
-pub use self::posts::PostService;
+pub use self::follow::FollowService;
+pub use self::mailer::LogMailer;
+pub use self::mention::MentionService;
+pub use self::moderation::ModerationService;
+pub use self::notification::NotificationService;
+pub use self::posts::{PostService, PostServiceDeps};
+pub use self::privacy::PrivacyService;
+pub use self::reaction::ReactionService;
+pub use self::report::ReportService;
+pub use self::spam::{BayesSpamFilter, BlocklistFilter, LinkCountFilter, SpamFilterChain};
+pub use self::stream::StreamService;
 pub use self::user::UserService;
+pub use self::view::PostViewService;
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use tracing::{error, info};
+
+use crate::{
+    abstract_trait::{
+        DynCommentRepository, DynMentionService, DynModerationRepository,
+        DynNotificationService, DynSpamFilter, DynUserRepository, ModerationServiceTrait,
+    },
+    domain::{
+        ApiResponse, ApiResponsePagination, CommentResponse, CommentSort, CommentStatus,
+        CommentStatusUpdateResponse, ErrorResponse, FindCommentsRequest,
+        FindModerationQueueRequest, Pagination, PublicUserResponse, ReactionTarget,
+        UpdateCommentPolicyRequest, UpdateCommentStatusRequest, UserRole,
+    },
+    utils::AppError,
+};
+
+pub struct ModerationService {
+    repository: DynModerationRepository,
+    comment_repository: DynCommentRepository,
+    user_repository: DynUserRepository,
+    spam_filter: DynSpamFilter,
+    mention_service: DynMentionService,
+    notification_service: DynNotificationService,
+}
+
+impl ModerationService {
+    pub fn new(
+        repository: DynModerationRepository,
+        comment_repository: DynCommentRepository,
+        user_repository: DynUserRepository,
+        spam_filter: DynSpamFilter,
+        mention_service: DynMentionService,
+        notification_service: DynNotificationService,
+    ) -> Self {
+        Self {
+            repository,
+            comment_repository,
+            user_repository,
+            spam_filter,
+            mention_service,
+            notification_service,
+        }
+    }
+
+    async fn ensure_moderator(&self, user_id: i32) -> Result<(), ErrorResponse> {
+        let is_moderator = self
+            .user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|user| UserRole::parse(&user.role).can_moderate());
+
+        if !is_moderator {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Moderator role required".into(),
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ModerationServiceTrait for ModerationService {
+    async fn get_queue(
+        &self,
+        moderator_id: i32,
+        req: FindModerationQueueRequest,
+    ) -> Result<ApiResponsePagination<Vec<CommentResponse>>, ErrorResponse> {
+        self.ensure_moderator(moderator_id).await?;
+
+        let page = req.page.max(1);
+        let page_size = req.page_size.clamp(1, 100);
+
+        // Oldest first, so the queue is worked through in arrival order.
+        let filter = FindCommentsRequest {
+            page,
+            page_size,
+            cursor: None,
+            sort: CommentSort::Oldest,
+            post_id: req.post_id,
+            author_id: None,
+            viewer_id: None,
+            post_unlocked: false,
+        };
+
+        let (comments, total_items) = self
+            .comment_repository
+            .find_paginated(&filter, req.status, None)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut author_ids: Vec<i32> = comments
+            .iter()
+            .filter_map(|ranked| ranked.comment.user_id)
+            .collect();
+        author_ids.sort_unstable();
+        author_ids.dedup();
+
+        let authors: HashMap<i32, PublicUserResponse> = self
+            .user_repository
+            .find_by_ids(&author_ids)
+            .await
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|user| (user.id, PublicUserResponse::from(user)))
+            .collect();
+
+        let data = comments
+            .into_iter()
+            .map(|ranked| {
+                let mut comment = CommentResponse::from(ranked.comment);
+                comment.author = comment.user_id.and_then(|id| authors.get(&id).cloned());
+                comment
+            })
+            .collect();
+
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Moderation queue retrieved successfully".to_string(),
+            data,
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+
+    async fn update_comment_status(
+        &self,
+        moderator_id: i32,
+        req: &UpdateCommentStatusRequest,
+    ) -> Result<ApiResponse<CommentStatusUpdateResponse>, ErrorResponse> {
+        self.ensure_moderator(moderator_id).await?;
+
+        if req.ids.is_empty() || req.ids.len() > 500 {
+            return Err(ErrorResponse::from(AppError::ValidationError(
+                "Between 1 and 500 comment ids are required".into(),
+            )));
+        }
+
+        let changed = self
+            .repository
+            .set_comment_status(&req.ids, req.status)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        // Moderator verdicts train the filters; pending carries no verdict.
+        let verdict = match req.status {
+            CommentStatus::Spam => Some(true),
+            CommentStatus::Approved => Some(false),
+            CommentStatus::Pending | CommentStatus::Rejected => None,
+        };
+
+        if let Some(is_spam) = verdict {
+            for comment in &changed {
+                self.spam_filter
+                    .learn(comment.id, &comment.comment, is_spam)
+                    .await
+                    .map_err(ErrorResponse::from)?;
+            }
+        }
+
+        // Only published comments mention or notify anyone.
+        for comment in &changed {
+            let result = if req.status == CommentStatus::Approved {
+                async {
+                    self.mention_service
+                        .sync(
+                            ReactionTarget::Comment,
+                            comment.id,
+                            comment.id_post_comment,
+                            comment.user_id,
+                            &comment.comment,
+                        )
+                        .await?;
+                    self.notification_service.comment_published(comment).await
+                }
+                .await
+            } else {
+                async {
+                    self.mention_service
+                        .clear(ReactionTarget::Comment, comment.id)
+                        .await?;
+                    self.notification_service.comment_withdrawn(comment.id).await
+                }
+                .await
+            };
+
+            if let Err(e) = result {
+                error!("Failed to sync notifications for comment {}: {}", comment.id, e);
+            }
+        }
+
+        let updated = changed.len() as u64;
+
+        info!(
+            "Moderator {} set {} comment(s) to {}",
+            moderator_id,
+            updated,
+            req.status.as_str()
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Comment status updated successfully".to_string(),
+            data: CommentStatusUpdateResponse {
+                status: req.status.as_str().to_string(),
+                updated,
+            },
+        })
+    }
+
+    async fn set_category_policy(
+        &self,
+        moderator_id: i32,
+        category_id: i32,
+        req: &UpdateCommentPolicyRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        self.ensure_moderator(moderator_id).await?;
+
+        let policy = req.policy.ok_or_else(|| {
+            ErrorResponse::from(AppError::ValidationError(
+                "Categories need an explicit comment policy".into(),
+            ))
+        })?;
+
+        let updated = self
+            .repository
+            .set_category_policy(category_id, policy)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if !updated {
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Category with id {} not found",
+                category_id
+            ))));
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Comment policy updated successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn set_post_policy(
+        &self,
+        moderator_id: i32,
+        post_id: i32,
+        req: &UpdateCommentPolicyRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        self.ensure_moderator(moderator_id).await?;
+
+        let updated = self
+            .repository
+            .set_post_policy(post_id, req.policy)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if !updated {
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Posts with id {} not found",
+                post_id
+            ))));
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Comment policy updated successfully".to_string(),
+            data: (),
+        })
+    }
+}
@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{
+    abstract_trait::{
+        DynBlockRepository, DynCommentRepository, DynNotificationRepository, DynPostsRepository, DynUserRepository,
+        NotificationServiceTrait,
+    },
+    domain::{
+        ApiResponse, ApiResponsePagination, CreateNotificationRequest, ErrorResponse,
+        FindNotificationsRequest, NotificationKind, NotificationPreferencesResponse,
+        NotificationReadResponse, NotificationResponse, Pagination, PostReader,
+        PublicUserResponse, ReactionTarget, UnreadCountResponse,
+        UpdateNotificationPreferencesRequest,
+    },
+    model::{comment::Comment, notification::NotificationPreferences},
+    utils::AppError,
+};
+
+pub struct NotificationService {
+    repository: DynNotificationRepository,
+    user_repository: DynUserRepository,
+    post_repository: DynPostsRepository,
+    comment_repository: DynCommentRepository,
+    block_repository: DynBlockRepository,
+}
+
+impl NotificationService {
+    pub fn new(
+        repository: DynNotificationRepository,
+        user_repository: DynUserRepository,
+        post_repository: DynPostsRepository,
+        comment_repository: DynCommentRepository,
+        block_repository: DynBlockRepository,
+    ) -> Self {
+        Self {
+            repository,
+            user_repository,
+            post_repository,
+            comment_repository,
+            block_repository,
+        }
+    }
+
+    async fn load_preferences(&self, user_id: i32) -> Result<NotificationPreferences, AppError> {
+        Ok(self
+            .repository
+            .find_preferences(user_id)
+            .await?
+            .unwrap_or_else(|| NotificationPreferences::defaults(user_id)))
+    }
+}
+
+#[async_trait]
+impl NotificationServiceTrait for NotificationService {
+    async fn notify(&self, inputs: Vec<CreateNotificationRequest>) -> Result<(), AppError> {
+        let mut by_actor: HashMap<i32, Vec<i32>> = HashMap::new();
+
+        for input in &inputs {
+            if let Some(actor_id) = input.actor_id {
+                by_actor.entry(actor_id).or_default().push(input.user_id);
+            }
+        }
+
+        // Nobody hears from someone they blocked or muted.
+        let mut hidden = HashSet::new();
+
+        for (actor_id, user_ids) in by_actor {
+            let hiders = self.block_repository.find_hiders(actor_id, &user_ids).await?;
+            hidden.extend(hiders.into_iter().map(|user_id| (actor_id, user_id)));
+        }
+
+        let mut by_kind: HashMap<NotificationKind, Vec<CreateNotificationRequest>> = HashMap::new();
+
+        for input in inputs {
+            let hides_actor = input
+                .actor_id
+                .is_some_and(|actor_id| hidden.contains(&(actor_id, input.user_id)));
+
+            if Some(input.user_id) != input.actor_id && !hides_actor {
+                by_kind.entry(input.kind).or_default().push(input);
+            }
+        }
+
+        let mut accepted = Vec::new();
+
+        for (kind, inputs) in by_kind {
+            let user_ids: Vec<i32> = inputs.iter().map(|input| input.user_id).collect();
+
+            let recipients = self
+                .repository
+                .recipients_accepting(kind, &user_ids)
+                .await?;
+
+            accepted.extend(
+                inputs
+                    .into_iter()
+                    .filter(|input| recipients.contains(&input.user_id)),
+            );
+        }
+
+        self.repository.create_many(&accepted).await?;
+
+        Ok(())
+    }
+
+    async fn withdraw(
+        &self,
+        kinds: &[NotificationKind],
+        target: ReactionTarget,
+        target_id: i32,
+        user_ids: Option<&[i32]>,
+    ) -> Result<(), AppError> {
+        self.repository
+            .delete_unread(kinds, target, target_id, user_ids)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn comment_published(&self, comment: &Comment) -> Result<(), AppError> {
+        let mut inputs = Vec::new();
+
+        let parent_author = match comment.parent_id {
+            Some(parent_id) => self
+                .comment_repository
+                .find_by_id(parent_id)
+                .await?
+                .and_then(|parent| parent.user_id),
+            None => None,
+        };
+
+        if let Some(user_id) = parent_author {
+            inputs.push(CreateNotificationRequest {
+                user_id,
+                actor_id: comment.user_id,
+                kind: NotificationKind::Reply,
+                target_type: ReactionTarget::Comment,
+                target_id: comment.id,
+            });
+        }
+
+        let post_author = self
+            .post_repository
+            .get_post(comment.id_post_comment, PostReader::Internal)
+            .await?
+            .map(|post| post.user_id);
+
+        // A reply already tells the post author about it.
+        if let Some(user_id) = post_author.filter(|&id| Some(id) != parent_author) {
+            inputs.push(CreateNotificationRequest {
+                user_id,
+                actor_id: comment.user_id,
+                kind: NotificationKind::Comment,
+                target_type: ReactionTarget::Comment,
+                target_id: comment.id,
+            });
+        }
+
+        self.notify(inputs).await
+    }
+
+    async fn comment_withdrawn(&self, comment_id: i32) -> Result<(), AppError> {
+        self.withdraw(
+            &[NotificationKind::Comment, NotificationKind::Reply],
+            ReactionTarget::Comment,
+            comment_id,
+            None,
+        )
+        .await
+    }
+
+    async fn clear_post(&self, post_id: i32) -> Result<(), AppError> {
+        self.repository.delete_for_post(post_id).await?;
+
+        Ok(())
+    }
+
+    async fn get_notification(&self, id: i32) -> Result<Option<NotificationResponse>, AppError> {
+        let Some(notification) = self.repository.find_by_id(id).await? else {
+            return Ok(None);
+        };
+
+        let mut response = NotificationResponse::from(notification);
+
+        if let Some(actor_id) = response.actor_id {
+            response.actor = self
+                .user_repository
+                .find_by_id(actor_id)
+                .await?
+                .map(PublicUserResponse::from);
+        }
+
+        Ok(Some(response))
+    }
+
+    async fn get_notifications(
+        &self,
+        user_id: i32,
+        req: FindNotificationsRequest,
+    ) -> Result<ApiResponsePagination<Vec<NotificationResponse>>, ErrorResponse> {
+        let req = FindNotificationsRequest {
+            page: req.page.max(1),
+            page_size: req.page_size.clamp(1, 100),
+            ..req
+        };
+
+        let (notifications, total_items) = self
+            .repository
+            .find_paginated(user_id, &req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut actor_ids: Vec<i32> = notifications
+            .iter()
+            .filter_map(|notification| notification.actor_id)
+            .collect();
+        actor_ids.sort_unstable();
+        actor_ids.dedup();
+
+        let actors: HashMap<i32, PublicUserResponse> = self
+            .user_repository
+            .find_by_ids(&actor_ids)
+            .await
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|user| (user.id, PublicUserResponse::from(user)))
+            .collect();
+
+        let data = notifications
+            .into_iter()
+            .map(|notification| {
+                let mut response = NotificationResponse::from(notification);
+                response.actor = response.actor_id.and_then(|id| actors.get(&id).cloned());
+                response
+            })
+            .collect();
+
+        let total_pages = (total_items as f64 / req.page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Notifications retrieved successfully".to_string(),
+            data,
+            pagination: Pagination {
+                page: req.page,
+                page_size: req.page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+
+    async fn get_unread_count(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<UnreadCountResponse>, ErrorResponse> {
+        let unread = self
+            .repository
+            .count_unread(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Unread count retrieved successfully".to_string(),
+            data: UnreadCountResponse { unread },
+        })
+    }
+
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+        let found = self
+            .repository
+            .mark_read(user_id, id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if !found {
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Notification with id {} not found",
+                id
+            ))));
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notification marked as read".to_string(),
+            data: (),
+        })
+    }
+
+    async fn mark_all_read(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<NotificationReadResponse>, ErrorResponse> {
+        let updated = self
+            .repository
+            .mark_all_read(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        info!("User {} marked {} notification(s) as read", user_id, updated);
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notifications marked as read".to_string(),
+            data: NotificationReadResponse { updated },
+        })
+    }
+
+    async fn get_preferences(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<NotificationPreferencesResponse>, ErrorResponse> {
+        let preferences = self
+            .load_preferences(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notification preferences retrieved successfully".to_string(),
+            data: NotificationPreferencesResponse::from(preferences),
+        })
+    }
+
+    async fn update_preferences(
+        &self,
+        user_id: i32,
+        input: &UpdateNotificationPreferencesRequest,
+    ) -> Result<ApiResponse<NotificationPreferencesResponse>, ErrorResponse> {
+        let current = self
+            .load_preferences(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let preferences = NotificationPreferences {
+            user_id,
+            mention: input.mention.unwrap_or(current.mention),
+            comment: input.comment.unwrap_or(current.comment),
+            reply: input.reply.unwrap_or(current.reply),
+        };
+
+        let saved = self
+            .repository
+            .save_preferences(&preferences)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notification preferences updated successfully".to_string(),
+            data: NotificationPreferencesResponse::from(saved),
+        })
+    }
+}
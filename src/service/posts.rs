@@ -1,21 +1,269 @@
 use crate::{
-    abstract_trait::{DynPostsRepository, PostsServiceTrait},
+    abstract_trait::{
+        DynBookmarkRepository, DynCategoryRepository, DynCommentRepository, DynMentionService,
+        DynPostsRepository, DynReactionRepository, DynUserRepository, PostsServiceTrait,
+    },
+    config::{Hashing, JwtConfig},
     domain::{
-        ApiResponse, ApiResponsePagination, CreatePostRequest, ErrorResponse, FindAllPostRequest,
-        Pagination, PostRelationResponse, PostResponse, UpdatePostRequest,
+        ApiResponse, ApiResponsePagination, CategoryResponse, CommentResponse, CreatePostRequest,
+        ErrorResponse, FindAllPostRequest, FindFeedRequest, FindPostDetailRequest, Pagination, PostDetailResponse,
+        PostReader, PostResponse, PublicUserResponse, ReactionTarget, UnlockPostRequest,
+        UpdatePostRequest, UserRole,
     },
+    model::posts::Post,
     utils::AppError,
 };
 use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 use tracing::{info, error};
 
+/// Wrong passwords allowed per post and caller within `UNLOCK_FAILURE_WINDOW`.
+const UNLOCK_MAX_FAILURES: u32 = 5;
+const UNLOCK_FAILURE_WINDOW: Duration = Duration::from_secs(15 * 60);
+
 pub struct PostService {
     repository: DynPostsRepository,
+    reaction_repository: DynReactionRepository,
+    bookmark_repository: DynBookmarkRepository,
+    user_repository: DynUserRepository,
+    category_repository: DynCategoryRepository,
+    comment_repository: DynCommentRepository,
+    mention_service: DynMentionService,
+    hashing: Hashing,
+    jwt_config: JwtConfig,
+    /// `(post_id, caller)` to the start of the window and failures in it.
+    unlock_failures: Mutex<HashMap<(i32, String), (Instant, u32)>>,
+}
+
+/// Everything [`PostService`] is built from.
+pub struct PostServiceDeps {
+    pub repository: DynPostsRepository,
+    pub reaction_repository: DynReactionRepository,
+    pub bookmark_repository: DynBookmarkRepository,
+    pub user_repository: DynUserRepository,
+    pub category_repository: DynCategoryRepository,
+    pub comment_repository: DynCommentRepository,
+    pub mention_service: DynMentionService,
+    pub hashing: Hashing,
+    pub jwt_config: JwtConfig,
 }
 
 impl PostService {
-    pub fn new(repository: DynPostsRepository) -> Self {
-        Self { repository }
+    pub fn new(deps: PostServiceDeps) -> Self {
+        let PostServiceDeps {
+            repository,
+            reaction_repository,
+            bookmark_repository,
+            user_repository,
+            category_repository,
+            comment_repository,
+            mention_service,
+            hashing,
+            jwt_config,
+        } = deps;
+
+        Self {
+            repository,
+            reaction_repository,
+            bookmark_repository,
+            user_repository,
+            category_repository,
+            comment_repository,
+            mention_service,
+            hashing,
+            jwt_config,
+            unlock_failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn is_editor(&self, viewer_id: Option<i32>) -> Result<bool, AppError> {
+        let Some(viewer_id) = viewer_id else {
+            return Ok(false);
+        };
+
+        Ok(self
+            .user_repository
+            .find_by_id(viewer_id)
+            .await?
+            .is_some_and(|user| UserRole::parse(&user.role).can_moderate()))
+    }
+
+    /// Finds a post `viewer_id` may read; hidden posts count as missing for
+    /// everyone but their author and moderators.
+    async fn find_readable(&self, post_id: i32, viewer_id: Option<i32>) -> Result<Post, ErrorResponse> {
+        let post = self
+            .repository
+            .get_post(post_id, PostReader::from(viewer_id))
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let not_found = || {
+            ErrorResponse::from(AppError::NotFound(format!(
+                "Posts with id {} not found",
+                post_id
+            )))
+        };
+
+        let post = post.ok_or_else(not_found)?;
+
+        if post.is_hidden
+            && viewer_id != Some(post.user_id)
+            && !self.is_editor(viewer_id).await.map_err(ErrorResponse::from)?
+        {
+            return Err(not_found());
+        }
+
+        Ok(post)
+    }
+
+    /// The author and editors read password-protected posts without the
+    /// password; everyone else needs a valid unlock token.
+    async fn ensure_unlocked(
+        &self,
+        post: &Post,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
+    ) -> Result<(), ErrorResponse> {
+        if post.password_hash.is_none()
+            || viewer_id == Some(post.user_id)
+            || unlock_token.is_some_and(|token| self.jwt_config.verify_unlock_token(token, post.id))
+            || self.is_editor(viewer_id).await.map_err(ErrorResponse::from)?
+        {
+            return Ok(());
+        }
+
+        Err(ErrorResponse::from(AppError::Forbidden(
+            "This post is password protected".into(),
+        )))
+    }
+
+    /// Loads a post `user_id` may change: their own, or any post for editors.
+    async fn find_editable(&self, user_id: i32, post_id: i32) -> Result<Post, ErrorResponse> {
+        let post = self.find_readable(post_id, Some(user_id)).await?;
+
+        if post.user_id != user_id && !self.is_editor(Some(user_id)).await.map_err(ErrorResponse::from)? {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Only the author or an editor can change this post".into(),
+            )));
+        }
+
+        Ok(post)
+    }
+
+    /// Password guesses are counted per address and, when signed in, per
+    /// account, so switching either one does not reset the limit.
+    fn unlock_callers(post_id: i32, viewer_id: Option<i32>, client_ip: IpAddr) -> Vec<(i32, String)> {
+        std::iter::once(format!("ip:{}", client_ip))
+            .chain(viewer_id.map(|id| format!("user:{}", id)))
+            .map(|caller| (post_id, caller))
+            .collect()
+    }
+
+    fn ensure_unlock_allowed(&self, callers: &[(i32, String)]) -> Result<(), ErrorResponse> {
+        let now = Instant::now();
+        let mut failures = self.unlock_failures.lock().unwrap();
+
+        failures.retain(|_, (started, _)| now.duration_since(*started) < UNLOCK_FAILURE_WINDOW);
+
+        if callers
+            .iter()
+            .any(|caller| failures.get(caller).is_some_and(|(_, count)| *count >= UNLOCK_MAX_FAILURES))
+        {
+            return Err(ErrorResponse::from(AppError::TooManyRequests(
+                "Too many incorrect passwords, try again later".into(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn record_unlock_failure(&self, callers: Vec<(i32, String)>) {
+        let now = Instant::now();
+        let mut failures = self.unlock_failures.lock().unwrap();
+
+        for caller in callers {
+            failures.entry(caller).or_insert((now, 0)).1 += 1;
+        }
+    }
+
+    /// Listings never carry unlock tokens, so protected bodies are only kept
+    /// for their author and editors.
+    async fn redact_locked(
+        &self,
+        posts: &mut [PostResponse],
+        viewer_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        if !posts
+            .iter()
+            .any(|post| post.password_protected && Some(post.user_id) != viewer_id)
+        {
+            return Ok(());
+        }
+
+        if self.is_editor(viewer_id).await? {
+            return Ok(());
+        }
+
+        for post in posts
+            .iter_mut()
+            .filter(|post| post.password_protected && Some(post.user_id) != viewer_id)
+        {
+            post.body.clear();
+        }
+
+        Ok(())
+    }
+
+    async fn hash_password(&self, password: &str) -> Result<String, ErrorResponse> {
+        self.hashing
+            .hash_password(password)
+            .await
+            .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))
+    }
+
+    async fn attach_bookmarks(
+        &self,
+        posts: &mut [PostResponse],
+        viewer_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let Some(viewer_id) = viewer_id else {
+            return Ok(());
+        };
+
+        let ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+
+        let bookmarked = self
+            .bookmark_repository
+            .find_bookmarked(viewer_id, &ids)
+            .await?;
+
+        for post in posts {
+            post.bookmarked = Some(bookmarked.contains(&post.id));
+        }
+
+        Ok(())
+    }
+
+    async fn attach_reactions(&self, posts: &mut [PostResponse]) -> Result<(), AppError> {
+        let ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+
+        let mut counts = self
+            .reaction_repository
+            .count_by_targets(ReactionTarget::Post, &ids)
+            .await?;
+
+        for post in posts {
+            if let Some(reactions) = counts.remove(&post.id) {
+                post.reactions = reactions;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -24,23 +272,35 @@ impl PostsServiceTrait for PostService {
     async fn get_all_posts(
         &self,
         req: FindAllPostRequest,
+        viewer_id: Option<i32>,
     ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse> {
         let page = req.page.max(1);
         let page_size = req.page_size.max(1);
-        let search = if req.search.is_empty() {
-            None
-        } else {
-            Some(req.search.clone())
-        };
 
         let (posts, total_items) = self
             .repository
-            .get_all_posts(page, page_size, search)
+            .get_all_posts(
+                &FindAllPostRequest {
+                    page,
+                    page_size,
+                    ..req
+                },
+                viewer_id,
+            )
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
-        let responses: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+        let mut responses: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+
+        self.attach_reactions(&mut responses)
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.attach_bookmarks(&mut responses, viewer_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.redact_locked(&mut responses, viewer_id)
+            .await
+            .map_err(ErrorResponse::from)?;
 
         let total_pages = (total_items as f64 / req.page_size as f64).ceil() as i32;
 
@@ -53,6 +313,7 @@ impl PostsServiceTrait for PostService {
                 page_size: req.page_size,
                 total_items,
                 total_pages,
+                next_cursor: None,
             },
         })
     }
@@ -60,66 +321,392 @@ impl PostsServiceTrait for PostService {
     async fn get_post(
         &self,
         post_id: i32,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
     ) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse> {
-        let post = self
-            .repository
-            .get_post(post_id)
+        let post = self.find_readable(post_id, viewer_id).await?;
+
+        self.ensure_unlocked(&post, viewer_id, unlock_token).await?;
+
+        let mut response = [PostResponse::from(post)];
+
+        self.attach_reactions(&mut response)
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.attach_bookmarks(&mut response, viewer_id)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
-        if let Some(post) = post {
-            Ok(Some(ApiResponse {
-                status: "success".to_string(),
-                message: "Post retrieved successfully".to_string(),
-                data: PostResponse::from(post),
-            }))
+        let [data] = response;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Post retrieved successfully".to_string(),
+            data,
+        }))
+    }
+
+    async fn get_post_detail(
+        &self,
+        post_id: i32,
+        req: FindPostDetailRequest,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
+    ) -> Result<ApiResponse<PostDetailResponse>, ErrorResponse> {
+        let page = req.page.max(1);
+        let page_size = req.page_size.clamp(1, 100);
+
+        let post = self.find_readable(post_id, viewer_id).await?;
+
+        self.ensure_unlocked(&post, viewer_id, unlock_token).await?;
+
+        // Every lookup below is a single query regardless of how many
+        // comments the post has, so the endpoint costs a fixed number of
+        // round trips.
+        let (author, category, (comments, total_items)) = tokio::try_join!(
+            self.user_repository.find_by_id(post.user_id),
+            self.category_repository.find_by_id(post.category_id),
+            self.comment_repository.find_by_post(post_id, page, page_size, viewer_id),
+        )
+        .map_err(ErrorResponse::from)?;
+
+        let mut post = [PostResponse::from(post)];
+
+        self.attach_reactions(&mut post)
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.attach_bookmarks(&mut post, viewer_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut comments: Vec<CommentResponse> =
+            comments.into_iter().map(CommentResponse::from).collect();
+
+        let comment_ids: Vec<i32> = comments.iter().map(|comment| comment.id).collect();
+        let mut counts = self
+            .reaction_repository
+            .count_by_targets(ReactionTarget::Comment, &comment_ids)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut author_ids: Vec<i32> = comments.iter().filter_map(|comment| comment.user_id).collect();
+        author_ids.sort_unstable();
+        author_ids.dedup();
+
+        let comment_authors: HashMap<i32, PublicUserResponse> = self
+            .user_repository
+            .find_by_ids(&author_ids)
+            .await
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|user| (user.id, PublicUserResponse::from(user)))
+            .collect();
+
+        for comment in &mut comments {
+            if let Some(reactions) = counts.remove(&comment.id) {
+                comment.reactions = reactions;
+            }
+
+            comment.author = comment
+                .user_id
+                .and_then(|id| comment_authors.get(&id).cloned());
+        }
+
+        let [post] = post;
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Post retrieved successfully".to_string(),
+            data: PostDetailResponse {
+                post,
+                author: author.map(PublicUserResponse::from),
+                category: category.map(CategoryResponse::from),
+                comments,
+                comments_pagination: Pagination {
+                    page,
+                    page_size,
+                    total_items,
+                    total_pages,
+                    next_cursor: None,
+                },
+            },
+        })
+    }
+
+    async fn get_bookmarked_posts(
+        &self,
+        user_id: i32,
+        req: FindAllPostRequest,
+    ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse> {
+        let page = req.page.max(1);
+        let page_size = req.page_size.clamp(1, 100);
+        let search = if req.search.is_empty() {
+            None
         } else {
-            Err(ErrorResponse::from(AppError::NotFound(format!(
-                "Posts with id {} not found",
-                post_id
-            ))))
+            Some(req.search.clone())
+        };
+
+        let (posts, total_items) = self
+            .repository
+            .get_bookmarked_posts(
+                user_id,
+                page,
+                page_size,
+                search,
+                req.category,
+                req.include_descendants,
+            )
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let mut responses: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+
+        self.attach_reactions(&mut responses)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        for post in &mut responses {
+            post.bookmarked = Some(true);
         }
+
+        self.redact_locked(&mut responses, Some(user_id))
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Bookmarks retrieved successfully".to_string(),
+            data: responses,
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
     }
 
-    async fn get_post_relation(
+    async fn get_feed(
         &self,
-        post_id: i32,
-    ) -> Result<ApiResponse<PostRelationResponse>, ErrorResponse> {
-        let relations = self
+        user_id: i32,
+        req: FindFeedRequest,
+    ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse> {
+        let page = req.page.max(1);
+        let page_size = req.page_size.clamp(1, 100);
+
+        // Cursors are the last post id seen; new posts never shift later pages.
+        let after = req
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                cursor
+                    .parse::<i32>()
+                    .map_err(|_| AppError::ValidationError("Invalid cursor".into()))
+            })
+            .transpose()
+            .map_err(ErrorResponse::from)?;
+
+        let (posts, total_items) = self
             .repository
-            .get_post_relation(post_id)
+            .get_feed(user_id, page, page_size, after)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
-        let first_relation = relations
-            .into_iter()
-            .next()
-            .ok_or_else(|| AppError::NotFound("Post relation not found".to_string()))?;
+        let next_cursor = match posts.last() {
+            Some(last) if posts.len() == page_size as usize => Some(last.id.to_string()),
+            _ => None,
+        };
+
+        let mut responses: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
+
+        self.attach_reactions(&mut responses)
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.attach_bookmarks(&mut responses, Some(user_id))
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.redact_locked(&mut responses, Some(user_id))
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Feed retrieved successfully".to_string(),
+            data: responses,
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items,
+                total_pages,
+                next_cursor,
+            },
+        })
+    }
+
+    async fn bookmark_post(
+        &self,
+        user_id: i32,
+        post_id: i32,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        self.repository
+            .get_post(post_id, PostReader::User(user_id))
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Posts with id {} not found",
+                    post_id
+                )))
+            })?;
+
+        self.bookmark_repository
+            .add(user_id, post_id)
+            .await
+            .map_err(ErrorResponse::from)?;
 
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Post relation retrieved successfully".to_string(),
-            data: first_relation,
+            message: "Post bookmarked successfully".to_string(),
+            data: (),
         })
     }
 
+    async fn remove_bookmark(
+        &self,
+        user_id: i32,
+        post_id: i32,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let removed = self
+            .bookmark_repository
+            .remove(user_id, post_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if !removed {
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "Post with id {} is not bookmarked",
+                post_id
+            ))));
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Bookmark removed successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn ensure_readable(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        unlock_token: Option<&str>,
+    ) -> Result<(), ErrorResponse> {
+        let post = self.find_readable(post_id, viewer_id).await?;
+
+        self.ensure_unlocked(&post, viewer_id, unlock_token).await
+    }
+
+    async fn unlock_post(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        client_ip: IpAddr,
+        input: &UnlockPostRequest,
+    ) -> Result<(String, ApiResponse<PostResponse>), ErrorResponse> {
+        let callers = Self::unlock_callers(post_id, viewer_id, client_ip);
+        self.ensure_unlock_allowed(&callers)?;
+
+        let post = self.find_readable(post_id, viewer_id).await?;
+
+        let Some(password_hash) = post.password_hash.as_deref() else {
+            return Err(ErrorResponse::from(AppError::ValidationError(
+                "This post is not password protected".into(),
+            )));
+        };
+
+        if self
+            .hashing
+            .compare_password(password_hash, &input.password)
+            .await
+            .is_err()
+        {
+            self.record_unlock_failure(callers);
+
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Incorrect password".into(),
+            )));
+        }
+
+        let token = self
+            .jwt_config
+            .generate_unlock_token(post.id)
+            .map_err(ErrorResponse::from)?;
+
+        let mut response = [PostResponse::from(post)];
+
+        self.attach_reactions(&mut response)
+            .await
+            .map_err(ErrorResponse::from)?;
+        self.attach_bookmarks(&mut response, viewer_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let [data] = response;
+
+        Ok((
+            token,
+            ApiResponse {
+                status: "success".to_string(),
+                message: "Post unlocked".to_string(),
+                data,
+            },
+        ))
+    }
+
     async fn create_post(
         &self,
         input: &CreatePostRequest,
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse> {
+        let author = self
+            .user_repository
+            .find_by_id(input.user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| ErrorResponse::from(AppError::NotFound(format!("User with id {} not found", input.user_id))))?;
+
+        let user_name = format!("{} {}", author.firstname, author.lastname);
+
+        let password_hash = match input.password.as_deref() {
+            Some(password) if !password.is_empty() => Some(self.hash_password(password).await?),
+            _ => None,
+        };
+
         let post = self
             .repository
-            .create_post(input)
+            .create_post(input, &user_name, password_hash.as_deref())
             .await
             .map_err(|e| {
                 error!("Failed to create post: {}", e);
-                AppError::from(e)
+                e
             })
-            .map_err(|e| ErrorResponse::from(e))?;
+            .map_err(ErrorResponse::from)?;
     
         info!("Post created successfully with title: {}", input.title);
+
+        if let Err(e) = self
+            .mention_service
+            .sync(ReactionTarget::Post, post.id, post.id, Some(post.user_id), &post.body)
+            .await
+        {
+            error!("Failed to sync mentions for post {}: {}", post.id, e);
+        }
     
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -131,15 +718,33 @@ impl PostsServiceTrait for PostService {
 
     async fn update_post(
         &self,
+        user_id: i32,
         input: &UpdatePostRequest,
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse> {
+        if let Some(post_id) = input.post_id {
+            self.find_editable(user_id, post_id).await?;
+        }
+
+        let password_hash = match input.password.as_deref() {
+            Some("") => Some(None),
+            Some(password) => Some(Some(self.hash_password(password).await?)),
+            None => None,
+        };
+
         let post = self
             .repository
-            .update_post(input)
+            .update_post(input, password_hash.as_ref().map(Option::as_deref))
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
+        if let Err(e) = self
+            .mention_service
+            .sync(ReactionTarget::Post, post.id, post.id, Some(post.user_id), &post.body)
+            .await
+        {
+            error!("Failed to sync mentions for post {}: {}", post.id, e);
+        }
+
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Post updated successfully".to_string(),
@@ -147,11 +752,18 @@ impl PostsServiceTrait for PostService {
         })
     }
 
-    async fn delete_post(&self, post_id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+    async fn delete_post(&self, user_id: i32, post_id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+        self.find_editable(user_id, post_id).await?;
+
+        // Mentions are not tied to the post by a foreign key, so they go
+        // first while the post's comments can still be found.
+        if let Err(e) = self.mention_service.clear_post(post_id).await {
+            error!("Failed to clear mentions for post {}: {}", post_id, e);
+        }
+
         self.repository
             .delete_post(post_id)
             .await
-            .map_err(AppError::from)
             .map_err(ErrorResponse::from)?;
 
         Ok(ApiResponse {
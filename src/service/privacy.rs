@@ -0,0 +1,308 @@
+use std::io::{Cursor, Write};
+
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use chrono::Utc;
+use tracing::{error, info};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    abstract_trait::{
+        DynFileService, DynPrivacyRepository, DynUserRepository, PrivacyServiceTrait,
+    },
+    config::Hashing,
+    domain::{
+        AccountExportResponse, ApiResponse, CommentResponse, DeleteAccountRequest,
+        ErasureResponse, ErrorResponse, ExportArchive, ExportFormat, PostErasurePolicy,
+        PostResponse, UserResponse,
+    },
+    service::auth::verify_current_password,
+    utils::AppError,
+};
+
+/// Upload directories that stored file names are relative to.
+const POST_IMAGE_DIR: &str = "posts";
+const AVATAR_DIR: &str = "avatars";
+
+fn archive_path(dir: &str, file_name: &str) -> String {
+    format!("files/{}/{}", dir, file_name)
+}
+
+/// Images are already compressed, so only `data.json` is deflated.
+fn build_zip(document: &[u8], files: &[(String, Vec<u8>)]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+    writer.start_file(
+        "data.json",
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )?;
+    writer.write_all(document)?;
+
+    for (path, bytes) in files {
+        writer.start_file(
+            path.as_str(),
+            SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        writer.write_all(bytes)?;
+    }
+
+    Ok(writer.finish()?.into_inner())
+}
+
+fn export_failed() -> ErrorResponse {
+    ErrorResponse {
+        status: "error".to_string(),
+        message: "Failed to build the export".to_string(),
+        code: StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+pub struct PrivacyService {
+    repository: DynPrivacyRepository,
+    user_repository: DynUserRepository,
+    file_service: DynFileService,
+    hashing: Hashing,
+}
+
+impl PrivacyService {
+    pub fn new(
+        repository: DynPrivacyRepository,
+        user_repository: DynUserRepository,
+        file_service: DynFileService,
+        hashing: Hashing,
+    ) -> Self {
+        Self {
+            repository,
+            user_repository,
+            file_service,
+            hashing,
+        }
+    }
+
+    /// Runs the erasure and removes the files once it has committed; a file
+    /// that cannot be removed is logged rather than failing the request.
+    async fn erase(
+        &self,
+        actor_id: i32,
+        user_id: i32,
+        reassign_to: Option<(i32, &str)>,
+    ) -> Result<ErasureResponse, ErrorResponse> {
+        let erased = self
+            .repository
+            .erase_user(user_id, actor_id, reassign_to)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let files = erased
+            .avatar
+            .iter()
+            .map(|avatar| (AVATAR_DIR, avatar))
+            .chain(erased.post_images.iter().map(|img| (POST_IMAGE_DIR, img)));
+
+        let mut files_removed = 0;
+
+        for (dir, file_name) in files {
+            match self.file_service.delete_image(dir, file_name).await {
+                Ok(_) => files_removed += 1,
+                Err((_, response)) => error!(
+                    "Failed to remove {}/{} of erased user {}: {}",
+                    dir, file_name, user_id, response.message
+                ),
+            }
+        }
+
+        info!("User {} was erased by user {}", user_id, actor_id);
+
+        Ok(ErasureResponse {
+            user_id,
+            posts_deleted: erased.posts_deleted,
+            posts_reassigned: erased.posts_reassigned,
+            comments_anonymized: erased.comments_anonymized,
+            files_removed,
+        })
+    }
+}
+
+#[async_trait]
+impl PrivacyServiceTrait for PrivacyService {
+    async fn export_account(
+        &self,
+        user_id: i32,
+        format: ExportFormat,
+    ) -> Result<ExportArchive, ErrorResponse> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "User with id {} not found",
+                    user_id
+                )))
+            })?;
+
+        let posts = self
+            .repository
+            .find_user_posts(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let comments = self
+            .repository
+            .find_user_comments(user_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let stored_files: Vec<(&str, String)> = user
+            .avatar
+            .iter()
+            .map(|avatar| (AVATAR_DIR, avatar.clone()))
+            .chain(
+                posts
+                    .iter()
+                    .filter(|post| !post.img.is_empty())
+                    .map(|post| (POST_IMAGE_DIR, post.img.clone())),
+            )
+            .collect();
+
+        let exported_at = Utc::now();
+        let base_name = format!("export-{}-{}", user.username, exported_at.format("%Y%m%d"));
+
+        let document = AccountExportResponse {
+            exported_at,
+            profile: UserResponse::from(user),
+            posts: posts.into_iter().map(PostResponse::from).collect(),
+            comments: comments.into_iter().map(CommentResponse::from).collect(),
+            files: stored_files
+                .iter()
+                .map(|(dir, file_name)| archive_path(dir, file_name))
+                .collect(),
+        };
+
+        let document = serde_json::to_vec_pretty(&document).map_err(|e| {
+            error!("Failed to serialize the export of user {}: {}", user_id, e);
+            export_failed()
+        })?;
+
+        if format == ExportFormat::Json {
+            return Ok(ExportArchive {
+                file_name: format!("{}.json", base_name),
+                content_type: "application/json",
+                data: document,
+            });
+        }
+
+        // Files that went missing on disk are left out of the archive but
+        // stay listed in `data.json`.
+        let mut files = Vec::with_capacity(stored_files.len());
+
+        for (dir, file_name) in &stored_files {
+            match self.file_service.read_image(dir, file_name).await {
+                Some(bytes) => files.push((archive_path(dir, file_name), bytes)),
+                None => error!(
+                    "Missing {}/{} while exporting user {}",
+                    dir, file_name, user_id
+                ),
+            }
+        }
+
+        let data = tokio::task::spawn_blocking(move || build_zip(&document, &files))
+            .await
+            .map_err(|e| {
+                error!("Export task for user {} failed: {}", user_id, e);
+                export_failed()
+            })?
+            .map_err(|e| {
+                error!("Failed to write the export of user {}: {}", user_id, e);
+                export_failed()
+            })?;
+
+        info!("User {} exported their data", user_id);
+
+        Ok(ExportArchive {
+            file_name: format!("{}.zip", base_name),
+            content_type: "application/zip",
+            data,
+        })
+    }
+
+    async fn delete_account(
+        &self,
+        user_id: i32,
+        input: &DeleteAccountRequest,
+    ) -> Result<ApiResponse<ErasureResponse>, ErrorResponse> {
+        verify_current_password(
+            &self.user_repository,
+            &self.hashing,
+            user_id,
+            &input.current_password,
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
+        let erased = self
+            .erase_account(user_id, user_id, input.posts, input.reassign_to)
+            .await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Your account has been deleted".to_string(),
+            data: erased.data,
+        })
+    }
+
+    async fn erase_account(
+        &self,
+        actor_id: i32,
+        user_id: i32,
+        posts: PostErasurePolicy,
+        reassign_to: Option<i32>,
+    ) -> Result<ApiResponse<ErasureResponse>, ErrorResponse> {
+        let target = match (posts, reassign_to) {
+            (PostErasurePolicy::Delete, _) => None,
+            (PostErasurePolicy::Reassign, None) => {
+                return Err(ErrorResponse::from(AppError::ValidationError(
+                    "reassign_to is required to reassign posts".into(),
+                )));
+            }
+            (PostErasurePolicy::Reassign, Some(target_id)) if target_id == user_id => {
+                return Err(ErrorResponse::from(AppError::ValidationError(
+                    "Posts cannot be reassigned to the user being erased".into(),
+                )));
+            }
+            (PostErasurePolicy::Reassign, Some(target_id)) => Some(
+                self.user_repository
+                    .find_by_id(target_id)
+                    .await
+                    .map_err(ErrorResponse::from)?
+                    .ok_or_else(|| {
+                        ErrorResponse::from(AppError::NotFound(format!(
+                            "User with id {} not found",
+                            target_id
+                        )))
+                    })?,
+            ),
+        };
+
+        let erased = self
+            .erase(
+                actor_id,
+                user_id,
+                target
+                    .as_ref()
+                    .map(|target| (target.id, target.username.as_str())),
+            )
+            .await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: match posts {
+                PostErasurePolicy::Delete => "User erased; their posts were deleted",
+                PostErasurePolicy::Reassign => "User erased; their posts were reassigned",
+            }
+            .to_string(),
+            data: erased,
+        })
+    }
+}
@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{
+        DynCommentRepository, DynPostsRepository, DynReactionRepository, ReactionServiceTrait,
+    },
+    domain::{
+        ApiResponse, ApiResponsePagination, ErrorResponse, FindReactionsRequest, Pagination,
+        PostReader, ReactionKind, ReactionResponse, ReactionTarget, ToggleReactionResponse,
+    },
+    utils::AppError,
+};
+
+pub struct ReactionService {
+    repository: DynReactionRepository,
+    post_repository: DynPostsRepository,
+    comment_repository: DynCommentRepository,
+}
+
+impl ReactionService {
+    pub fn new(
+        repository: DynReactionRepository,
+        post_repository: DynPostsRepository,
+        comment_repository: DynCommentRepository,
+    ) -> Self {
+        Self {
+            repository,
+            post_repository,
+            comment_repository,
+        }
+    }
+
+    async fn ensure_target_exists(
+        &self,
+        user_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+    ) -> Result<(), AppError> {
+        let exists = match target {
+            ReactionTarget::Post => self
+                .post_repository
+                .get_post(target_id, PostReader::User(user_id))
+                .await?
+                .is_some(),
+            ReactionTarget::Comment => self
+                .comment_repository
+                .find_by_id(target_id)
+                .await?
+                .is_some(),
+        };
+
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "{} with id {} not found",
+                target.as_str(),
+                target_id
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReactionServiceTrait for ReactionService {
+    async fn toggle_reaction(
+        &self,
+        user_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+        kind: ReactionKind,
+    ) -> Result<ApiResponse<ToggleReactionResponse>, ErrorResponse> {
+        self.ensure_target_exists(user_id, target, target_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let reacted = self
+            .repository
+            .toggle(user_id, target, target_id, kind)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let reactions = self
+            .repository
+            .count_by_targets(target, &[target_id])
+            .await
+            .map_err(ErrorResponse::from)?
+            .remove(&target_id)
+            .unwrap_or_default();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: if reacted {
+                "Reaction added"
+            } else {
+                "Reaction removed"
+            }
+            .to_string(),
+            data: ToggleReactionResponse {
+                kind: kind.as_str().to_string(),
+                reacted,
+                reactions,
+            },
+        })
+    }
+
+    async fn get_reactions(
+        &self,
+        viewer_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+        req: FindReactionsRequest,
+    ) -> Result<ApiResponsePagination<Vec<ReactionResponse>>, ErrorResponse> {
+        let page = req.page.max(1);
+        let page_size = req.page_size.clamp(1, 100);
+
+        self.ensure_target_exists(viewer_id, target, target_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let (reactors, total_items) = self
+            .repository
+            .find_reactors(target, target_id, req.kind, page, page_size)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Reactions retrieved successfully".to_string(),
+            data: reactors.into_iter().map(ReactionResponse::from).collect(),
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::{
+    abstract_trait::{
+        DynCommentRepository, DynPostsRepository, DynReportRepository, DynUserRepository,
+        ReportServiceTrait,
+    },
+    domain::{
+        ApiResponse, ApiResponsePagination, CommentStatus, CreateReportRequest, ErrorResponse,
+        FindReportsRequest, Pagination, PostReader, ReactionTarget, ReportResponse, ReportStatus,
+        UpdateReportRequest, UserRole,
+    },
+    utils::AppError,
+};
+
+const MAX_DETAILS_LENGTH: usize = 1000;
+
+pub struct ReportService {
+    repository: DynReportRepository,
+    post_repository: DynPostsRepository,
+    comment_repository: DynCommentRepository,
+    user_repository: DynUserRepository,
+    hide_threshold: i64,
+}
+
+impl ReportService {
+    pub fn new(
+        repository: DynReportRepository,
+        post_repository: DynPostsRepository,
+        comment_repository: DynCommentRepository,
+        user_repository: DynUserRepository,
+        hide_threshold: i64,
+    ) -> Self {
+        Self {
+            repository,
+            post_repository,
+            comment_repository,
+            user_repository,
+            hide_threshold,
+        }
+    }
+
+    async fn ensure_moderator(&self, user_id: i32) -> Result<(), ErrorResponse> {
+        let is_moderator = self
+            .user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|user| UserRole::parse(&user.role).can_moderate());
+
+        if !is_moderator {
+            return Err(ErrorResponse::from(AppError::Forbidden(
+                "Moderator role required".into(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Only content readers can actually see may be reported.
+    async fn target_is_visible(
+        &self,
+        reporter_id: i32,
+        target: ReactionTarget,
+        target_id: i32,
+    ) -> Result<bool, AppError> {
+        match target {
+            ReactionTarget::Post => Ok(self
+                .post_repository
+                .get_post(target_id, PostReader::User(reporter_id))
+                .await?
+                .is_some_and(|post| !post.is_hidden)),
+            ReactionTarget::Comment => Ok(self
+                .comment_repository
+                .find_by_id(target_id)
+                .await?
+                .is_some_and(|comment| {
+                    !comment.is_deleted && comment.status == CommentStatus::Approved.as_str()
+                })),
+        }
+    }
+}
+
+#[async_trait]
+impl ReportServiceTrait for ReportService {
+    async fn create_report(
+        &self,
+        reporter_id: i32,
+        input: &CreateReportRequest,
+    ) -> Result<ApiResponse<ReportResponse>, ErrorResponse> {
+        if input
+            .details
+            .as_ref()
+            .is_some_and(|details| details.chars().count() > MAX_DETAILS_LENGTH)
+        {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "Details cannot be longer than {} characters",
+                MAX_DETAILS_LENGTH
+            ))));
+        }
+
+        if !self
+            .target_is_visible(reporter_id, input.target_type, input.target_id)
+            .await
+            .map_err(ErrorResponse::from)?
+        {
+            let kind = match input.target_type {
+                ReactionTarget::Post => "Posts",
+                ReactionTarget::Comment => "Comment",
+            };
+
+            return Err(ErrorResponse::from(AppError::NotFound(format!(
+                "{} with id {} not found",
+                kind, input.target_id
+            ))));
+        }
+
+        let report = self
+            .repository
+            .create(reporter_id, input)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::Conflict(
+                    "You have already reported this content".into(),
+                ))
+            })?;
+
+        let open_reports = self
+            .repository
+            .count_open(input.target_type, input.target_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        if self.hide_threshold > 0 && open_reports >= self.hide_threshold {
+            self.repository
+                .hide_target(input.target_type, input.target_id)
+                .await
+                .map_err(ErrorResponse::from)?;
+
+            info!(
+                "{} {} hidden after {} reports",
+                input.target_type.as_str(),
+                input.target_id,
+                open_reports
+            );
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Report submitted successfully".to_string(),
+            data: ReportResponse::from(report),
+        })
+    }
+
+    async fn get_inbox(
+        &self,
+        moderator_id: i32,
+        req: FindReportsRequest,
+    ) -> Result<ApiResponsePagination<Vec<ReportResponse>>, ErrorResponse> {
+        self.ensure_moderator(moderator_id).await?;
+
+        let req = FindReportsRequest {
+            page: req.page.max(1),
+            page_size: req.page_size.clamp(1, 100),
+            ..req
+        };
+
+        let (reports, total_items) = self
+            .repository
+            .find_paginated(&req)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let total_pages = (total_items as f64 / req.page_size as f64).ceil() as i32;
+
+        Ok(ApiResponsePagination {
+            status: "success".to_string(),
+            message: "Reports retrieved successfully".to_string(),
+            data: reports.into_iter().map(ReportResponse::from).collect(),
+            pagination: Pagination {
+                page: req.page,
+                page_size: req.page_size,
+                total_items,
+                total_pages,
+                next_cursor: None,
+            },
+        })
+    }
+
+    async fn update_report(
+        &self,
+        moderator_id: i32,
+        report_id: i32,
+        input: &UpdateReportRequest,
+    ) -> Result<ApiResponse<ReportResponse>, ErrorResponse> {
+        self.ensure_moderator(moderator_id).await?;
+
+        if input.status == ReportStatus::Open {
+            return Err(ErrorResponse::from(AppError::ValidationError(
+                "Reports can only be triaged, resolved or dismissed".into(),
+            )));
+        }
+
+        let report = self
+            .repository
+            .find_by_id(report_id)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "Report with id {} not found",
+                    report_id
+                )))
+            })?;
+
+        if report.status != ReportStatus::Open.as_str()
+            && report.status != ReportStatus::Triaged.as_str()
+        {
+            return Err(ErrorResponse::from(AppError::Conflict(format!(
+                "Report with id {} is already {}",
+                report_id, report.status
+            ))));
+        }
+
+        let updated = self
+            .repository
+            .set_status(&report, input.status, moderator_id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        info!(
+            "Moderator {} marked report {} as {}",
+            moderator_id,
+            report_id,
+            input.status.as_str()
+        );
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Report updated successfully".to_string(),
+            data: ReportResponse::from(updated),
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::abstract_trait::{DynSpamFilter, DynSpamRepository, SpamFilter};
+use crate::utils::AppError;
+
+/// Training documents required per label before the classifier votes.
+const BAYES_MIN_DOCUMENTS: i64 = 10;
+
+fn link_targets(text: &str) -> impl Iterator<Item = &str> {
+    text.split_whitespace().filter_map(|word| {
+        let word = word.trim_start_matches(['(', '<', '[', '"', '\'']);
+        ["http://", "https://", "www."]
+            .iter()
+            .find_map(|prefix| {
+                word
+                    .get(..prefix.len())
+                    .filter(|head| head.eq_ignore_ascii_case(prefix))
+                    .map(|_| &word[prefix.len()..])
+            })
+    })
+}
+
+fn link_host(target: &str) -> String {
+    target
+        .split(['/', '?', '#', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .trim_end_matches(['.', ',', ')', '>', ']', '"', '\''])
+        .to_lowercase()
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| (3..=30).contains(&word.chars().count()))
+        .map(str::to_lowercase)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Flags comments carrying more links than `max_links`.
+pub struct LinkCountFilter {
+    max_links: usize,
+}
+
+impl LinkCountFilter {
+    pub fn new(max_links: usize) -> Self {
+        Self { max_links }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for LinkCountFilter {
+    fn name(&self) -> &'static str {
+        "link_count"
+    }
+
+    async fn is_spam(&self, text: &str) -> Result<bool, AppError> {
+        Ok(link_targets(text).count() > self.max_links)
+    }
+}
+
+/// Flags comments containing a blocked word or linking to a blocked domain.
+pub struct BlocklistFilter {
+    words: Vec<String>,
+    domains: Vec<String>,
+}
+
+impl BlocklistFilter {
+    pub fn new(words: Vec<String>, domains: Vec<String>) -> Self {
+        Self { words, domains }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for BlocklistFilter {
+    fn name(&self) -> &'static str {
+        "blocklist"
+    }
+
+    async fn is_spam(&self, text: &str) -> Result<bool, AppError> {
+        let lower = text.to_lowercase();
+
+        if self.words.iter().any(|word| lower.contains(word.as_str())) {
+            return Ok(true);
+        }
+
+        Ok(link_targets(text).map(link_host).any(|host| {
+            self.domains.iter().any(|domain| {
+                host == *domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|rest| rest.ends_with('.'))
+            })
+        }))
+    }
+}
+
+/// Naive Bayes classifier trained from moderator decisions.
+///
+/// Stays silent until both labels have enough training documents.
+pub struct BayesSpamFilter {
+    repository: DynSpamRepository,
+    threshold: f64,
+}
+
+impl BayesSpamFilter {
+    pub fn new(repository: DynSpamRepository, threshold: f64) -> Self {
+        Self {
+            repository,
+            threshold,
+        }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for BayesSpamFilter {
+    fn name(&self) -> &'static str {
+        "bayes"
+    }
+
+    async fn is_spam(&self, text: &str) -> Result<bool, AppError> {
+        let tokens = tokenize(text);
+        if tokens.is_empty() {
+            return Ok(false);
+        }
+
+        let (spam_docs, ham_docs) = self.repository.document_counts().await?;
+        if spam_docs < BAYES_MIN_DOCUMENTS || ham_docs < BAYES_MIN_DOCUMENTS {
+            return Ok(false);
+        }
+
+        let counts = self.repository.token_counts(&tokens).await?;
+
+        let mut log_odds = (spam_docs as f64).ln() - (ham_docs as f64).ln();
+        for token in &tokens {
+            let (spam, ham) = counts.get(token).copied().unwrap_or((0, 0));
+            let p_spam = (spam as f64 + 1.0) / (spam_docs as f64 + 2.0);
+            let p_ham = (ham as f64 + 1.0) / (ham_docs as f64 + 2.0);
+            log_odds += p_spam.ln() - p_ham.ln();
+        }
+
+        let probability = 1.0 / (1.0 + (-log_odds).exp());
+
+        Ok(probability > self.threshold)
+    }
+
+    async fn learn(&self, comment_id: i32, text: &str, is_spam: bool) -> Result<(), AppError> {
+        self.repository.record(comment_id, &tokenize(text), is_spam).await
+    }
+}
+
+/// Runs every filter in order; a comment is spam as soon as one flags it.
+pub struct SpamFilterChain {
+    filters: Vec<DynSpamFilter>,
+}
+
+impl SpamFilterChain {
+    pub fn new(filters: Vec<DynSpamFilter>) -> Self {
+        Self { filters }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for SpamFilterChain {
+    fn name(&self) -> &'static str {
+        "chain"
+    }
+
+    async fn is_spam(&self, text: &str) -> Result<bool, AppError> {
+        for filter in &self.filters {
+            if filter.is_spam(text).await? {
+                info!("Comment flagged as spam by {} filter", filter.name());
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    async fn learn(&self, comment_id: i32, text: &str, is_spam: bool) -> Result<(), AppError> {
+        for filter in &self.filters {
+            filter.learn(comment_id, text, is_spam).await?;
+        }
+
+        Ok(())
+    }
+}
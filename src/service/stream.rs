@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::json;
+use sqlx::postgres::PgListener;
+use tokio::sync::broadcast;
+use tracing::{error, info};
+
+use crate::{
+    abstract_trait::{DynCommentService, DynNotificationService, DynStreamService, StreamServiceTrait},
+    config::ConnectionPool,
+    domain::{StreamEvent, StreamTopic},
+    utils::AppError,
+};
+
+/// Filled by the triggers on `comments`.
+const COMMENT_CHANNEL: &str = "comment_events";
+
+/// Filled by the trigger on `notifications`.
+const NOTIFICATION_CHANNEL: &str = "notification_events";
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize)]
+struct CommentPayload {
+    id: i32,
+    post_id: i32,
+}
+
+#[derive(Deserialize)]
+struct NotificationPayload {
+    id: i32,
+    user_id: i32,
+}
+
+fn parse_payload<T: DeserializeOwned>(channel: &str, payload: &str) -> Result<T, AppError> {
+    serde_json::from_str(payload).map_err(|e| {
+        AppError::ValidationError(format!("Malformed {} payload: {}", channel, e))
+    })
+}
+
+/// Returns `Ok` when the connection drops, so the caller reconnects.
+async fn listen(
+    service: &DynStreamService,
+    pool: &ConnectionPool,
+    reconnecting: bool,
+) -> Result<(), AppError> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener
+        .listen_all([COMMENT_CHANNEL, NOTIFICATION_CHANNEL])
+        .await?;
+
+    info!("Listening for realtime events");
+
+    // Anything raised while we were disconnected never reached us.
+    if reconnecting {
+        service.resync();
+    }
+
+    loop {
+        // `recv` would reconnect silently and lose the gap; `try_recv`
+        // reports the drop instead.
+        let Some(notification) = listener.try_recv().await? else {
+            return Ok(());
+        };
+
+        if let Err(e) = service
+            .dispatch(notification.channel(), notification.payload())
+            .await
+        {
+            error!(
+                "Failed to dispatch {} event {}: {}",
+                notification.channel(),
+                notification.payload(),
+                e
+            );
+        }
+    }
+}
+
+/// Fans database events out to the SSE and WebSocket clients connected to
+/// this replica. Every replica listens on its own connection, so a write
+/// handled anywhere reaches every subscriber.
+pub struct StreamService {
+    sender: broadcast::Sender<StreamEvent>,
+    comment_service: DynCommentService,
+    notification_service: DynNotificationService,
+}
+
+impl StreamService {
+    pub fn new(
+        comment_service: DynCommentService,
+        notification_service: DynNotificationService,
+        buffer_size: usize,
+    ) -> Self {
+        let (sender, _) = broadcast::channel(buffer_size.max(1));
+
+        Self {
+            sender,
+            comment_service,
+            notification_service,
+        }
+    }
+
+    /// Spawns the `LISTEN` loop, reconnecting whenever the connection drops.
+    pub fn spawn_listener(service: DynStreamService, pool: ConnectionPool) {
+        tokio::spawn(async move {
+            let mut reconnecting = false;
+
+            loop {
+                match listen(&service, &pool, reconnecting).await {
+                    Ok(()) => error!("Realtime listener lost its connection"),
+                    Err(e) => error!("Realtime listener stopped: {}", e),
+                }
+
+                reconnecting = true;
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+    }
+
+    fn publish(
+        &self,
+        topic: StreamTopic,
+        event: &'static str,
+        author_id: Option<i32>,
+        data: impl Serialize,
+    ) {
+        // Sending only fails when nobody on this replica is subscribed.
+        let _ = self.sender.send(StreamEvent {
+            topic,
+            event,
+            data: json!(data).to_string().into(),
+            author_id,
+        });
+    }
+}
+
+#[async_trait]
+impl StreamServiceTrait for StreamService {
+    fn subscribe(&self) -> broadcast::Receiver<StreamEvent> {
+        self.sender.subscribe()
+    }
+
+    async fn dispatch(&self, channel: &str, payload: &str) -> Result<(), AppError> {
+        // Nobody on this replica would receive the event, so skip loading it.
+        if self.sender.receiver_count() == 0 {
+            return Ok(());
+        }
+
+        match channel {
+            COMMENT_CHANNEL => {
+                let payload: CommentPayload = parse_payload(channel, payload)?;
+                let topic = StreamTopic::Post(payload.post_id);
+
+                // Inserts, edits, status changes and deletes of a comment all
+                // land here: a published comment goes out as a fresh copy,
+                // anything else is retracted.
+                match self.comment_service.find_published(payload.id).await? {
+                    Some(comment) => self.publish(topic, "comment", comment.user_id, comment),
+                    None => self.publish(
+                        topic,
+                        "comment_removed",
+                        None,
+                        json!({ "id": payload.id, "post_id": payload.post_id }),
+                    ),
+                }
+            }
+            NOTIFICATION_CHANNEL => {
+                let payload: NotificationPayload = parse_payload(channel, payload)?;
+
+                // Withdrawn before we got to it.
+                if let Some(notification) = self
+                    .notification_service
+                    .get_notification(payload.id)
+                    .await?
+                {
+                    self.publish(
+                        StreamTopic::User(payload.user_id),
+                        "notification",
+                        None,
+                        notification,
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn resync(&self) {
+        self.publish(StreamTopic::All, "resync", None, json!({}));
+    }
+}
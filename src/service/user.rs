@@ -1,17 +1,138 @@
 use crate::{
-    abstract_trait::{DynUserRepository, UserServiceTrait},
-    domain::{ApiResponse, CreateUserRequest, ErrorResponse, UpdateUserRequest, UserResponse},
-    utils::AppError,
+    abstract_trait::{DynFollowRepository, DynPostsService, DynUserRepository, UserServiceTrait},
+    config::Hashing,
+    domain::{
+        ApiResponse, ApiResponsePagination, CreateUserRequest, ErrorResponse, FindAllPostRequest,
+        FollowTarget, PostResponse, UpdateProfileRequest, UpdateUserRequest, UserProfileResponse, UserResponse,
+    },
+    model::user::User,
+    utils::{generate_username, is_valid_username, AppError, MAX_USERNAME_LENGTH},
 };
 use async_trait::async_trait;
 
+const MAX_BIO_LENGTH: usize = 500;
+
+const MAX_WEBSITE_LENGTH: usize = 200;
+
+/// Published posts embedded in a profile.
+const PROFILE_POSTS: i32 = 10;
+
+/// Makes sure a requested username is valid and free, or derives a free one
+/// from the name, numbering it `jane.doe-2`, `jane.doe-3`… on collisions.
+pub(crate) async fn assign_username(
+    repository: &DynUserRepository,
+    requested: Option<&str>,
+    firstname: &str,
+    lastname: &str,
+) -> Result<String, AppError> {
+    if let Some(requested) = requested {
+        let username = requested.trim().to_lowercase();
+
+        if !is_valid_username(&username) {
+            return Err(invalid_username());
+        }
+
+        if repository.find_by_username(&username).await?.is_some() {
+            return Err(AppError::Conflict("This username is already taken".into()));
+        }
+
+        return Ok(username);
+    }
+
+    let mut base = generate_username(firstname, lastname);
+    if !is_valid_username(&base) {
+        base = "user".to_string();
+    }
+
+    let mut candidate = base.clone();
+    let mut counter = 2;
+
+    while repository.find_by_username(&candidate).await?.is_some() {
+        let suffix = format!("-{}", counter);
+        let head = base[..base.len().min(MAX_USERNAME_LENGTH - suffix.len())]
+            .trim_end_matches(['.', '_', '-']);
+        candidate = format!("{}{}", head, suffix);
+        counter += 1;
+    }
+
+    Ok(candidate)
+}
+
+fn invalid_username() -> AppError {
+    AppError::ValidationError(format!(
+        "Username must be 3 to {} lowercase letters, digits, '.', '_' or '-', starting and ending with a letter or digit",
+        MAX_USERNAME_LENGTH
+    ))
+}
+
 pub struct UserService {
     repository: DynUserRepository,
+    post_service: DynPostsService,
+    follow_repository: DynFollowRepository,
+    hashing: Hashing,
 }
 
 impl UserService {
-    pub fn new(repository: DynUserRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: DynUserRepository,
+        post_service: DynPostsService,
+        follow_repository: DynFollowRepository,
+        hashing: Hashing,
+    ) -> Self {
+        Self {
+            repository,
+            post_service,
+            follow_repository,
+            hashing,
+        }
+    }
+
+    async fn find_profile_user(&self, username: &str) -> Result<User, ErrorResponse> {
+        self.repository
+            .find_by_username(username)
+            .await
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::NotFound(format!(
+                    "User {} not found",
+                    username
+                )))
+            })
+    }
+
+    fn check_profile(input: &UpdateProfileRequest) -> Result<(), AppError> {
+        if let Some(Some(bio)) = &input.bio
+            && bio.chars().count() > MAX_BIO_LENGTH
+        {
+            return Err(AppError::ValidationError(format!(
+                "Bio cannot be longer than {} characters",
+                MAX_BIO_LENGTH
+            )));
+        }
+
+        if let Some(Some(website)) = &input.website {
+            let has_host = ["http://", "https://"].iter().any(|scheme| {
+                website
+                    .get(..scheme.len())
+                    .is_some_and(|head| head.eq_ignore_ascii_case(scheme))
+                    && website.len() > scheme.len()
+            });
+
+            if !has_host || website.chars().any(char::is_whitespace) {
+                return Err(AppError::ValidationError(
+                    "Website must be an http or https URL".into(),
+                ));
+            }
+
+            if website.chars().count() > MAX_WEBSITE_LENGTH {
+                return Err(AppError::ValidationError(format!(
+                    "Website cannot be longer than {} characters",
+                    MAX_WEBSITE_LENGTH
+                )));
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -22,7 +143,6 @@ impl UserServiceTrait for UserService {
         input: &CreateUserRequest,
     ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
         let exists = self.repository.find_by_email_exists(&input.email).await
-            .map_err(AppError::from)  
             .map_err(ErrorResponse::from)?; 
 
         if exists {
@@ -30,7 +150,24 @@ impl UserServiceTrait for UserService {
         }
 
 
-        let user = self.repository.create_user(input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let username = assign_username(
+            &self.repository,
+            input.username.as_deref(),
+            &input.firstname,
+            &input.lastname,
+        )
+        .await
+        .map_err(ErrorResponse::from)?;
+
+        let hashed_password = self.hashing.hash_password(&input.password).await
+            .map_err(|e| ErrorResponse::from(AppError::HashingError(e)))?;
+
+        let request = CreateUserRequest {
+            password: hashed_password,
+            ..input.clone()
+        };
+
+        let user = self.repository.create_user(&request, &username).await.map_err(ErrorResponse::from)?;
         
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -40,7 +177,7 @@ impl UserServiceTrait for UserService {
     }
 
     async fn find_by_email_exists(&self, email: &str) -> Result<ApiResponse<bool>, ErrorResponse> {
-        let exists = self.repository.find_by_email_exists(email).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let exists = self.repository.find_by_email_exists(email).await.map_err(ErrorResponse::from)?;
         
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -54,7 +191,7 @@ impl UserServiceTrait for UserService {
         &self,
         email: &str,
     ) -> Result<Option<ApiResponse<UserResponse>>, ErrorResponse> {
-        let user = self.repository.find_by_email(email).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let user = self.repository.find_by_email(email).await.map_err(ErrorResponse::from)?;
         
         if let Some(user) = user {
             Ok(Some(ApiResponse {
@@ -71,7 +208,7 @@ impl UserServiceTrait for UserService {
         &self,
         id: i32,
     ) -> Result<Option<ApiResponse<UserResponse>>, ErrorResponse> {
-        let user = self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let user = self.repository.find_by_id(id).await.map_err(ErrorResponse::from)?;
         
         if let Some(user) = user {
             Ok(Some(ApiResponse {
@@ -88,7 +225,7 @@ impl UserServiceTrait for UserService {
         &self,
         input: &UpdateUserRequest,
     ) -> Result<Option<ApiResponse<UserResponse>>, ErrorResponse> {
-        let user = self.repository.update_user(input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let user = self.repository.update_user(input).await.map_err(ErrorResponse::from)?;
         
         Ok(Some(ApiResponse {
             status: "success".to_string(),
@@ -97,13 +234,138 @@ impl UserServiceTrait for UserService {
         }))
     }
 
-    async fn delete_user(&self, email: &str) -> Result<ApiResponse<()>, ErrorResponse> {
-        self.repository.delete_user(email).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
-        
+    async fn get_profile(
+        &self,
+        username: &str,
+        viewer_id: Option<i32>,
+    ) -> Result<ApiResponse<UserProfileResponse>, ErrorResponse> {
+        let user = self.find_profile_user(username).await?;
+
+        let posts = self
+            .post_service
+            .get_all_posts(
+                FindAllPostRequest {
+                    page: 1,
+                    page_size: PROFILE_POSTS,
+                    search: String::new(),
+                    category: None,
+                    include_descendants: false,
+                    user_id: Some(user.id),
+                },
+                viewer_id,
+            )
+            .await?;
+
+        let follower_count = self
+            .follow_repository
+            .count_followers(FollowTarget::User, user.id)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let following_count = self
+            .follow_repository
+            .count_following(user.id, FollowTarget::User)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Profile retrieved successfully".to_string(),
+            data: UserProfileResponse {
+                id: user.id,
+                username: user.username,
+                firstname: user.firstname,
+                lastname: user.lastname,
+                bio: user.bio,
+                avatar: user.avatar,
+                website: user.website,
+                post_count: posts.pagination.total_items,
+                follower_count,
+                following_count,
+                posts: posts.data,
+            },
+        })
+    }
+
+    async fn get_user_posts(
+        &self,
+        username: &str,
+        req: FindAllPostRequest,
+        viewer_id: Option<i32>,
+    ) -> Result<ApiResponsePagination<Vec<PostResponse>>, ErrorResponse> {
+        let user = self.find_profile_user(username).await?;
+
+        self.post_service
+            .get_all_posts(
+                FindAllPostRequest {
+                    user_id: Some(user.id),
+                    ..req
+                },
+                viewer_id,
+            )
+            .await
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: i32,
+        input: &UpdateProfileRequest,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        Self::check_profile(input).map_err(ErrorResponse::from)?;
+
+        let mut input = input.clone();
+
+        if let Some(username) = &input.username {
+            let username = username.trim().to_lowercase();
+
+            if !is_valid_username(&username) {
+                return Err(ErrorResponse::from(invalid_username()));
+            }
+
+            let taken = self
+                .repository
+                .find_by_username(&username)
+                .await
+                .map_err(ErrorResponse::from)?
+                .is_some_and(|user| user.id != user_id);
+
+            if taken {
+                return Err(ErrorResponse::from(AppError::Conflict(
+                    "This username is already taken".into(),
+                )));
+            }
+
+            input.username = Some(username);
+        }
+
+        let user = self
+            .repository
+            .update_profile(user_id, &input)
+            .await
+            .map_err(ErrorResponse::from)?;
+
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "User deleted successfully".to_string(),
-            data: (),
+            message: "Profile updated successfully".to_string(),
+            data: UserResponse::from(user),
+        })
+    }
+
+    async fn set_avatar(
+        &self,
+        user_id: i32,
+        avatar: Option<&str>,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let user = self
+            .repository
+            .set_avatar(user_id, avatar)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Avatar updated successfully".to_string(),
+            data: UserResponse::from(user),
         })
     }
 }
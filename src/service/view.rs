@@ -0,0 +1,211 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use tracing::{error, info};
+
+use crate::{
+    abstract_trait::{
+        DynPostViewRepository, DynPostViewService, DynReactionRepository, PostViewServiceTrait,
+    },
+    domain::{
+        ApiResponse, ErrorResponse, FindTrendingPostRequest, ReactionTarget, TrendingPostResponse,
+    },
+    utils::AppError,
+};
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+const DEDUP_WINDOW: Duration = Duration::from_secs(30 * 60);
+
+pub struct PostViewService {
+    repository: DynPostViewRepository,
+    reaction_repository: DynReactionRepository,
+    pending: Mutex<HashMap<(i32, NaiveDate), i64>>,
+    seen: Mutex<HashMap<(i32, String), Instant>>,
+}
+
+impl PostViewService {
+    pub fn new(
+        repository: DynPostViewRepository,
+        reaction_repository: DynReactionRepository,
+    ) -> Self {
+        Self {
+            repository,
+            reaction_repository,
+            pending: Mutex::new(HashMap::new()),
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Periodically writes buffered view counts to Postgres.
+    pub fn spawn_flusher(service: DynPostViewService) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = service.flush().await {
+                    error!("Failed to flush post views: {}", e);
+                }
+            }
+        });
+    }
+
+    fn parse_window(window: &str) -> Result<i32, AppError> {
+        let window = window.trim();
+        let invalid = || AppError::ValidationError(format!("Invalid window: {}", window));
+
+        let parse = |amount: &str| amount.parse::<i32>().map_err(|_| invalid());
+
+        let days = if let Some(hours) = window.strip_suffix('h') {
+            parse(hours)?.checked_add(23).map(|hours| hours / 24)
+        } else if let Some(days) = window.strip_suffix('d') {
+            Some(parse(days)?)
+        } else if let Some(weeks) = window.strip_suffix('w') {
+            parse(weeks)?.checked_mul(7)
+        } else {
+            return Err(invalid());
+        }
+        .ok_or_else(invalid)?;
+
+        if !(1..=365).contains(&days) {
+            return Err(AppError::ValidationError(
+                "Window must be between 1 hour and 365 days".to_string(),
+            ));
+        }
+
+        Ok(days)
+    }
+}
+
+#[async_trait]
+impl PostViewServiceTrait for PostViewService {
+    fn record_view(&self, post_id: i32, visitor: &str) {
+        let now = Instant::now();
+
+        {
+            let mut seen = self.seen.lock().unwrap();
+            let key = (post_id, visitor.to_string());
+
+            if seen
+                .get(&key)
+                .is_some_and(|last| now.duration_since(*last) < DEDUP_WINDOW)
+            {
+                return;
+            }
+
+            seen.insert(key, now);
+        }
+
+        let today = Utc::now().date_naive();
+        *self
+            .pending
+            .lock()
+            .unwrap()
+            .entry((post_id, today))
+            .or_insert(0) += 1;
+    }
+
+    async fn flush(&self) -> Result<(), AppError> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+
+        let now = Instant::now();
+        self.seen
+            .lock()
+            .unwrap()
+            .retain(|_, last| now.duration_since(*last) < DEDUP_WINDOW);
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let batch: Vec<(i32, NaiveDate, i64)> = pending
+            .iter()
+            .map(|((post_id, day), views)| (*post_id, *day, *views))
+            .collect();
+
+        if let Err(e) = self.repository.increment_views(&batch).await {
+            // Put the counts back so the next flush retries them.
+            let mut buffered = self.pending.lock().unwrap();
+            for (key, views) in pending {
+                *buffered.entry(key).or_insert(0) += views;
+            }
+
+            return Err(e);
+        }
+
+        info!("Flushed views for {} post/day pairs", batch.len());
+
+        Ok(())
+    }
+
+    async fn get_trending(
+        &self,
+        req: FindTrendingPostRequest,
+    ) -> Result<ApiResponse<Vec<TrendingPostResponse>>, ErrorResponse> {
+        let window_days = Self::parse_window(&req.window).map_err(ErrorResponse::from)?;
+        let limit = req.limit.clamp(1, 100);
+
+        let posts = self
+            .repository
+            .find_trending(window_days, limit)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let ids: Vec<i32> = posts.iter().map(|trending| trending.post.id).collect();
+
+        let mut counts = self
+            .reaction_repository
+            .count_by_targets(ReactionTarget::Post, &ids)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let data = posts
+            .into_iter()
+            .map(|trending| {
+                let mut response = TrendingPostResponse::from(trending);
+                if let Some(reactions) = counts.remove(&response.post.id) {
+                    response.post.reactions = reactions;
+                }
+                if response.post.password_protected {
+                    response.post.body.clear();
+                }
+                response
+            })
+            .collect();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Trending posts retrieved successfully".to_string(),
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_window_accepts_hours_days_and_weeks() {
+        assert_eq!(PostViewService::parse_window("24h").unwrap(), 1);
+        assert_eq!(PostViewService::parse_window("25h").unwrap(), 2);
+        assert_eq!(PostViewService::parse_window("7d").unwrap(), 7);
+        assert_eq!(PostViewService::parse_window(" 2w ").unwrap(), 14);
+    }
+
+    #[test]
+    fn parse_window_rejects_malformed_and_out_of_range_windows() {
+        for window in ["", "d", "7", "7m", "-1d", "0d", "366d", "2147483647h", "2147483647w", "1é"] {
+            assert!(
+                matches!(PostViewService::parse_window(window), Err(AppError::ValidationError(_))),
+                "{window:?} should be rejected"
+            );
+        }
+    }
+}
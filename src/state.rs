@@ -1,4 +1,4 @@
-use crate::{config::{Hashing, JwtConfig, ConnectionPool}, utils::DependenciesInject};
+use crate::{config::{Config, Hashing, JwtConfig, ConnectionPool}, utils::DependenciesInject};
 
 #[derive(Clone)]
 pub struct AppState {
@@ -7,11 +7,11 @@ pub struct AppState {
 }
 
 impl AppState {
-    pub fn new(pool: ConnectionPool, jwt_secret: &str) -> Self {
-        let jwt_config = JwtConfig::new(jwt_secret);
+    pub fn new(pool: ConnectionPool, config: &Config) -> Self {
+        let jwt_config = JwtConfig::new(&config.jwt_secret);
         let hashing = Hashing::new();
 
-        let di_container = DependenciesInject::new(pool, hashing, jwt_config.clone());
+        let di_container = DependenciesInject::new(pool, hashing, jwt_config.clone(), config);
         
         Self { di_container, jwt_config }
     }
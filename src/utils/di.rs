@@ -3,14 +3,26 @@ use std::sync::Arc;
 
 use crate::{
     abstract_trait::{
-        DynAuthService, DynCategoryRepository, DynCategoryService, DynCommentRepository,
-        DynCommentService, DynFileService, DynPostsRepository, DynPostsService, DynUserRepository,
-        DynUserService,
+        DynAdminService, DynAuthService, DynBlockRepository, DynBlockService, DynBookmarkRepository, DynCategoryRepository, DynCategoryService,
+        DynCommentRepository, DynCommentService, DynFileService, DynFollowRepository, DynMailer,
+        DynFollowService, DynMentionRepository,
+        DynMentionService, DynModerationRepository, DynModerationService,
+        DynNotificationRepository, DynNotificationService, DynPostViewRepository, DynPostViewService, DynPostsRepository,
+        DynPostsService, DynPrivacyRepository, DynPrivacyService, DynReactionRepository, DynReactionService, DynReportRepository,
+        DynReportService, DynSpamFilter, DynSpamRepository, DynStreamService, DynUserRepository,
+        DynUserService, DynUserTokenRepository,
+    },
+    config::{Config, ConnectionPool, Hashing, JwtConfig},
+    repository::{
+        BlockRepository, BookmarkRepository, CategoryRepository, CommentRepository, FollowRepository,
+        MentionRepository,
+        ModerationRepository, NotificationRepository, PostRepository, PostViewRepository, PrivacyRepository,
+        ReactionRepository, ReportRepository, SpamRepository, UserRepository, UserTokenRepository,
     },
-    config::{ConnectionPool, Hashing, JwtConfig},
-    repository::{CategoryRepository, CommentRepository, PostRepository, UserRepository},
     service::{
-        AuthService, CategoryService, CommentService, FileService, PostService, UserService,
+        AdminService, AuthService, BayesSpamFilter, BlockService, BlocklistFilter, CategoryService, CommentService, CommentServiceDeps,
+        FileService, FollowService, LinkCountFilter, LogMailer, MentionService, ModerationService, NotificationService, PostService, PostServiceDeps,
+        PostViewService, PrivacyService, ReactionService, ReportService, SpamFilterChain, StreamService, UserService,
     },
 };
 
@@ -22,37 +34,206 @@ pub struct DependenciesInject {
     pub user_service: DynUserService,
     pub auth_service: DynAuthService,
     pub file_service: DynFileService,
+    pub post_view_service: DynPostViewService,
+    pub reaction_service: DynReactionService,
+    pub moderation_service: DynModerationService,
+    pub report_service: DynReportService,
+    pub notification_service: DynNotificationService,
+    pub stream_service: DynStreamService,
+    pub follow_service: DynFollowService,
+    pub admin_service: DynAdminService,
+    pub privacy_service: DynPrivacyService,
+    pub block_service: DynBlockService,
 }
 
 impl DependenciesInject {
-    pub fn new(pool: ConnectionPool, hashing: Hashing, jwt_config: JwtConfig) -> Self {
+    pub fn new(
+        pool: ConnectionPool,
+        hashing: Hashing,
+        jwt_config: JwtConfig,
+        config: &Config,
+    ) -> Self {
         let category_repository =
             Arc::new(CategoryRepository::new(pool.clone())) as DynCategoryRepository;
 
-        let category_service =
-            Arc::new(CategoryService::new(category_repository)) as DynCategoryService;
+        let reaction_repository =
+            Arc::new(ReactionRepository::new(pool.clone())) as DynReactionRepository;
 
         let post_repository = Arc::new(PostRepository::new(pool.clone())) as DynPostsRepository;
 
-        let post_service = Arc::new(PostService::new(post_repository.clone())) as DynPostsService;
+        let bookmark_repository =
+            Arc::new(BookmarkRepository::new(pool.clone())) as DynBookmarkRepository;
 
         let comment_repository =
             Arc::new(CommentRepository::new(pool.clone())) as DynCommentRepository;
-        let comment_service =
-            Arc::new(CommentService::new(comment_repository)) as DynCommentService;
 
         let user_repository = Arc::new(UserRepository::new(pool.clone())) as DynUserRepository;
 
-        let user_service = Arc::new(UserService::new(user_repository.clone())) as DynUserService;
+        let category_service = Arc::new(CategoryService::new(
+            category_repository.clone(),
+            user_repository.clone(),
+        )) as DynCategoryService;
+
+        let block_repository =
+            Arc::new(BlockRepository::new(pool.clone())) as DynBlockRepository;
+
+        let notification_repository =
+            Arc::new(NotificationRepository::new(pool.clone())) as DynNotificationRepository;
+
+        let notification_service = Arc::new(NotificationService::new(
+            notification_repository,
+            user_repository.clone(),
+            post_repository.clone(),
+            comment_repository.clone(),
+            block_repository.clone(),
+        )) as DynNotificationService;
+
+        let mention_service = Arc::new(MentionService::new(
+            Arc::new(MentionRepository::new(pool.clone())) as DynMentionRepository,
+            block_repository.clone(),
+            post_repository.clone(),
+            notification_service.clone(),
+        )) as DynMentionService;
+
+        let post_service = Arc::new(PostService::new(PostServiceDeps {
+            repository: post_repository.clone(),
+            reaction_repository: reaction_repository.clone(),
+            bookmark_repository,
+            user_repository: user_repository.clone(),
+            category_repository: category_repository.clone(),
+            comment_repository: comment_repository.clone(),
+            mention_service: mention_service.clone(),
+            hashing: hashing.clone(),
+            jwt_config: jwt_config.clone(),
+        })) as DynPostsService;
+
+        let moderation_repository =
+            Arc::new(ModerationRepository::new(pool.clone())) as DynModerationRepository;
+
+        let spam_repository = Arc::new(SpamRepository::new(pool.clone())) as DynSpamRepository;
+
+        // Cheap static checks first; the classifier only runs when they pass.
+        let spam_filter = Arc::new(SpamFilterChain::new(vec![
+            Arc::new(LinkCountFilter::new(config.spam_max_links)) as DynSpamFilter,
+            Arc::new(BlocklistFilter::new(
+                config.spam_blocked_words.clone(),
+                config.spam_blocked_domains.clone(),
+            )),
+            Arc::new(BayesSpamFilter::new(
+                spam_repository,
+                config.spam_bayes_threshold,
+            )),
+        ])) as DynSpamFilter;
+
+        let comment_service = Arc::new(CommentService::new(
+            CommentServiceDeps {
+                repository: comment_repository.clone(),
+                reaction_repository: reaction_repository.clone(),
+                user_repository: user_repository.clone(),
+                moderation_repository: moderation_repository.clone(),
+                post_repository: post_repository.clone(),
+                block_repository: block_repository.clone(),
+                spam_filter: spam_filter.clone(),
+                mention_service: mention_service.clone(),
+                notification_service: notification_service.clone(),
+            },
+            config,
+        )) as DynCommentService;
+
+        let moderation_service = Arc::new(ModerationService::new(
+            moderation_repository,
+            comment_repository.clone(),
+            user_repository.clone(),
+            spam_filter,
+            mention_service,
+            notification_service.clone(),
+        )) as DynModerationService;
+
+        let report_repository =
+            Arc::new(ReportRepository::new(pool.clone())) as DynReportRepository;
+
+        let report_service = Arc::new(ReportService::new(
+            report_repository,
+            post_repository.clone(),
+            comment_repository.clone(),
+            user_repository.clone(),
+            config.report_hide_threshold,
+        )) as DynReportService;
+
+        let reaction_service = Arc::new(ReactionService::new(
+            reaction_repository.clone(),
+            post_repository.clone(),
+            comment_repository.clone(),
+        )) as DynReactionService;
+
+        let follow_repository =
+            Arc::new(FollowRepository::new(pool.clone())) as DynFollowRepository;
+
+        let follow_service = Arc::new(FollowService::new(
+            follow_repository.clone(),
+            user_repository.clone(),
+            category_repository.clone(),
+            block_repository.clone(),
+        )) as DynFollowService;
+
+        let block_service = Arc::new(BlockService::new(
+            block_repository,
+            user_repository.clone(),
+        )) as DynBlockService;
+
+        let user_service = Arc::new(UserService::new(
+            user_repository.clone(),
+            post_service.clone(),
+            follow_repository,
+            hashing.clone(),
+        )) as DynUserService;
+
+        let user_token_repository =
+            Arc::new(UserTokenRepository::new(pool.clone())) as DynUserTokenRepository;
+
+        let mailer = Arc::new(LogMailer) as DynMailer;
 
         let auth_service = Arc::new(AuthService::new(
             user_repository.clone(),
-            hashing,
+            user_token_repository.clone(),
+            mailer,
+            hashing.clone(),
             jwt_config,
         ));
 
         let file_service = Arc::new(FileService::new()) as DynFileService;
 
+        let privacy_service = Arc::new(PrivacyService::new(
+            Arc::new(PrivacyRepository::new(pool.clone())) as DynPrivacyRepository,
+            user_repository.clone(),
+            file_service.clone(),
+            hashing.clone(),
+        )) as DynPrivacyService;
+
+        let admin_service = Arc::new(AdminService::new(
+            user_repository.clone(),
+            user_token_repository,
+            privacy_service.clone(),
+            hashing,
+        )) as DynAdminService;
+
+        let post_view_repository =
+            Arc::new(PostViewRepository::new(pool.clone())) as DynPostViewRepository;
+        let post_view_service = Arc::new(PostViewService::new(
+            post_view_repository,
+            reaction_repository.clone(),
+        )) as DynPostViewService;
+
+        PostViewService::spawn_flusher(post_view_service.clone());
+
+        let stream_service = Arc::new(StreamService::new(
+            comment_service.clone(),
+            notification_service.clone(),
+            config.stream_buffer_size,
+        )) as DynStreamService;
+
+        StreamService::spawn_listener(stream_service.clone(), pool);
+
         Self {
             category_service,
             post_service,
@@ -60,6 +241,16 @@ impl DependenciesInject {
             user_service,
             auth_service,
             file_service,
+            post_view_service,
+            reaction_service,
+            moderation_service,
+            report_service,
+            notification_service,
+            stream_service,
+            follow_service,
+            admin_service,
+            privacy_service,
+            block_service,
         }
     }
 }
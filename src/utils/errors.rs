@@ -38,6 +38,18 @@ pub enum AppError {
     
     #[error("Validation error: {0}")]
     ValidationError(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
 }
 
 
@@ -0,0 +1,64 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    Json,
+};
+use serde_json::{json, Value};
+
+/// Strong entity tag for a row version, e.g. `"3"`.
+pub fn etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("version is a valid header value")
+}
+
+fn parse_tag(tag: &str) -> Option<i32> {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix("W/").unwrap_or(tag);
+
+    tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
+
+/// Reads the version the client expects to overwrite from `If-Match`.
+///
+/// Returns `Ok(None)` for `If-Match: *`, 428 when the header is missing and
+/// 412 when it does not name a version.
+pub fn if_match_version(headers: &HeaderMap) -> Result<Option<i32>, (StatusCode, Json<Value>)> {
+    let value = headers
+        .get(header::IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| {
+            (
+                StatusCode::PRECONDITION_REQUIRED,
+                Json(json!({
+                    "status": "fail",
+                    "message": "If-Match header is required"
+                })),
+            )
+        })?;
+
+    if value.trim() == "*" {
+        return Ok(None);
+    }
+
+    value
+        .split(',')
+        .find_map(parse_tag)
+        .map(Some)
+        .ok_or_else(|| {
+            (
+                StatusCode::PRECONDITION_FAILED,
+                Json(json!({
+                    "status": "fail",
+                    "message": "If-Match does not match the current version"
+                })),
+            )
+        })
+}
+
+/// Whether `If-None-Match` already names `version`, so a 304 can be sent.
+pub fn if_none_match(headers: &HeaderMap, version: i32) -> bool {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value.trim() == "*" || value.split(',').any(|tag| parse_tag(tag) == Some(version))
+        })
+}
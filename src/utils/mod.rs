@@ -1,9 +1,15 @@
 mod errors;
 mod di;
+mod etag;
 mod log;
 mod slug;
+mod username;
 
 pub use self::errors::AppError;
 pub use self::di::DependenciesInject;
+pub use self::etag::{etag, if_match_version, if_none_match};
 pub use self::log::tracing;
-pub use self::slug::generate_slug;
\ No newline at end of file
+pub use self::slug::generate_slug;
+pub use self::username::{
+    generate_username, is_valid_username, MAX_USERNAME_LENGTH, MIN_USERNAME_LENGTH,
+};
\ No newline at end of file
@@ -0,0 +1,33 @@
+pub const MIN_USERNAME_LENGTH: usize = 3;
+
+pub const MAX_USERNAME_LENGTH: usize = 30;
+
+/// Path segments under `/api/users` that a username would shadow.
+const RESERVED_USERNAMES: [&str; 1] = ["me"];
+
+/// Lowercase ASCII letters, digits, `.`, `_` and `-`, starting and ending
+/// with a letter or digit so a trailing `@jane.` still mentions `jane`.
+pub fn is_valid_username(username: &str) -> bool {
+    let edge = |c: Option<char>| c.is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+    (MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&username.len())
+        && username
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'))
+        && edge(username.chars().next())
+        && edge(username.chars().last())
+        && !RESERVED_USERNAMES.contains(&username)
+}
+
+/// Best-effort username built from a name, e.g. `Jane Doe` becomes `jane.doe`.
+/// May still be invalid, e.g. too short, and is not checked for uniqueness.
+pub fn generate_username(firstname: &str, lastname: &str) -> String {
+    let username: String = format!("{}.{}", firstname, lastname)
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'))
+        .take(MAX_USERNAME_LENGTH)
+        .collect();
+
+    username.trim_matches(['.', '_', '-']).to_string()
+}